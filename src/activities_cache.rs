@@ -0,0 +1,248 @@
+//! Windows Timeline (ActivitiesCache.db) parsing module
+//!
+//! Supports parsing of:
+//! - Activity and ActivityOperation tables of the Connected Devices Platform database
+//! - JSON AppId lists (Win32 paths with known folder GUIDs, UWP package names)
+//! - JSON Payload fields (display text, application name, content URI, active duration)
+
+use crate::error::Result;
+use crate::registry::format_guid;
+use crate::shell_item::known_folder_name;
+use crate::sqlite::{Row, SqliteDatabase};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// AppId platforms in order of preference for naming the application
+const APP_ID_PLATFORMS: &[&str] = &["windows_win32", "x_exe_path", "windows_universal", "packageId"];
+
+/// Activity or pending sync operation from ActivitiesCache.db
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Activity {
+    /// Activity ID (GUID)
+    pub id: String,
+    /// Source table (Activity or ActivityOperation)
+    pub table: String,
+    /// ActivityType value
+    pub activity_type: i64,
+    /// Application path or package name from AppId
+    pub application: Option<String>,
+    /// AppId platform the application was taken from
+    pub platform: Option<String>,
+    /// Payload display text (usually the document or page title)
+    pub display_text: Option<String>,
+    /// Payload application display name
+    pub app_display_name: Option<String>,
+    /// File path or URI of the content
+    pub content: Option<String>,
+    /// Start time
+    pub start_time: Option<DateTime<Utc>>,
+    /// End time
+    pub end_time: Option<DateTime<Utc>>,
+    /// Last modified time
+    pub last_modified: Option<DateTime<Utc>>,
+    /// Expiration time
+    pub expiration: Option<DateTime<Utc>>,
+    /// Seconds the user was engaged with the app (ActivityType 6)
+    pub active_duration: Option<i64>,
+    /// Device the activity was recorded on
+    pub platform_device_id: Option<String>,
+    /// OperationType (ActivityOperation only)
+    pub operation_type: Option<i64>,
+}
+
+impl Activity {
+    /// Readable ActivityType
+    pub fn type_name(&self) -> String {
+        match self.activity_type {
+            2 => "Notification".to_string(),
+            3 => "Backup".to_string(),
+            5 => "Open".to_string(),
+            6 => "App in use".to_string(),
+            10 => "Clipboard".to_string(),
+            16 => "Copy/Paste".to_string(),
+            other => format!("Type {}", other),
+        }
+    }
+
+    /// Readable OperationType
+    pub fn operation_name(&self) -> Option<String> {
+        self.operation_type.map(|operation| match operation {
+            1 => "Active".to_string(),
+            2 => "Updated".to_string(),
+            3 => "Deleted".to_string(),
+            4 => "Ignored".to_string(),
+            other => format!("Operation {}", other),
+        })
+    }
+
+    /// Labelled values for event metadata
+    pub fn fields(&self) -> Vec<(String, String)> {
+        let mut fields = vec![("Activity Type".to_string(), self.type_name())];
+        let optional = [
+            ("Application", self.application.clone()),
+            ("Platform", self.platform.clone()),
+            ("App Display Name", self.app_display_name.clone()),
+            ("Display Text", self.display_text.clone()),
+            ("Content", self.content.clone()),
+            ("Active Duration", self.active_duration.map(|s| format!("{}s", s))),
+            ("Expires", self.expiration.map(|t| t.to_rfc3339())),
+            ("Device ID", self.platform_device_id.clone()),
+            ("Operation", self.operation_name()),
+        ];
+        fields.extend(optional.into_iter().filter_map(|(label, value)| Some((label.to_string(), value?))));
+        fields.push(("Table".to_string(), self.table.clone()));
+        fields.push(("Activity ID".to_string(), self.id.clone()));
+        fields
+    }
+
+    /// Build an activity from a row of either table
+    fn from_row(row: &Row, table: &str) -> Self {
+        let (application, platform) = row.text("AppId").map(|app_id| application_from_app_id(&app_id)).unwrap_or_default();
+        let payload: Value = row
+            .text("Payload")
+            .and_then(|payload| serde_json::from_str(&payload).ok())
+            .unwrap_or(Value::Null);
+        let payload_text = |key: &str| payload.get(key).and_then(Value::as_str).map(|s| s.to_string()).filter(|s| !s.is_empty());
+
+        let id = match row.blob("Id") {
+            Some(bytes) if bytes.len() == 16 => format_guid(bytes),
+            _ => row.text("Id").unwrap_or_default(),
+        };
+
+        Activity {
+            id,
+            table: table.to_string(),
+            activity_type: row.integer("ActivityType").unwrap_or(0),
+            application,
+            platform,
+            display_text: payload_text("displayText"),
+            app_display_name: payload_text("appDisplayName"),
+            content: content_location(payload_text("contentUri"), payload_text("description")),
+            start_time: unix_time(row.integer("StartTime")),
+            end_time: unix_time(row.integer("EndTime")),
+            last_modified: unix_time(row.integer("LastModifiedTime")),
+            expiration: unix_time(row.integer("ExpirationTime")),
+            active_duration: payload.get("activeDurationSeconds").and_then(Value::as_i64),
+            platform_device_id: row.text("PlatformDeviceId"),
+            operation_type: row.integer("OperationType"),
+        }
+    }
+}
+
+/// Parse the Activity and ActivityOperation tables of an ActivitiesCache.db image
+pub fn parse_activities(data: &[u8]) -> Result<Vec<Activity>> {
+    let database = SqliteDatabase::open(data)?;
+    let mut activities = Vec::new();
+    for table_name in ["Activity", "ActivityOperation"] {
+        if database.table_schema(table_name).is_none() {
+            continue;
+        }
+        let table = database.read_table(table_name)?;
+        activities.extend(table.records().map(|row| Activity::from_row(&row, table_name)));
+    }
+    Ok(activities)
+}
+
+/// Pick the most descriptive application from an AppId JSON list, resolving known folder GUIDs
+fn application_from_app_id(app_id: &str) -> (Option<String>, Option<String>) {
+    let entries: Vec<Value> = serde_json::from_str(app_id).unwrap_or_default();
+    let entry = |platform: &str| {
+        entries.iter().find_map(|entry| {
+            let application = entry.get("application").and_then(Value::as_str)?;
+            (entry.get("platform").and_then(Value::as_str) == Some(platform) && !application.is_empty())
+                .then(|| application.to_string())
+        })
+    };
+
+    match APP_ID_PLATFORMS.iter().find_map(|platform| Some((entry(platform)?, platform.to_string()))) {
+        Some((application, platform)) => (Some(resolve_known_folder(&application)), Some(platform)),
+        None => (None, None),
+    }
+}
+
+/// Replace a leading {known folder GUID} with the folder name
+fn resolve_known_folder(path: &str) -> String {
+    if let Some(end) = path.find('}').filter(|_| path.starts_with('{')) {
+        if let Some(name) = known_folder_name(&path[..=end]) {
+            return format!("{}{}", name, &path[end + 1..]);
+        }
+    }
+    path.to_string()
+}
+
+/// File path from a file:/// content URI, falling back to a path-like description or the URI itself
+fn content_location(content_uri: Option<String>, description: Option<String>) -> Option<String> {
+    if let Some(path) = content_uri.as_deref().and_then(|uri| uri.strip_prefix("file:///")) {
+        // Drop the ?VolumeId=...&ObjectId=... tracking query
+        let path = path.split('?').next().unwrap_or(path);
+        let path = urlencoding::decode(path).map(|p| p.to_string()).unwrap_or_else(|_| path.to_string());
+        return Some(path.replace('/', "\\"));
+    }
+    let path_like = |d: &String| d.get(1..3) == Some(":\\") || d.starts_with("\\\\");
+    description.filter(path_like).or(content_uri)
+}
+
+/// Convert Unix seconds to a timestamp, treating zero as unset
+fn unix_time(seconds: Option<i64>) -> Option<DateTime<Utc>> {
+    seconds.filter(|&s| s > 0).and_then(|s| DateTime::from_timestamp(s, 0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sqlite::tests::build_database;
+    use crate::sqlite::SqliteValue;
+
+    #[test]
+    fn test_application_from_app_id() {
+        let app_id = r#"[{"application":"Microsoft.Office.WINWORD.EXE.15","platform":"windows_win32"},{"application":"{6D809377-6AF0-444B-8957-A3773F02200E}\\Microsoft Office\\root\\Office16\\WINWORD.EXE","platform":"packageId"}]"#;
+        assert_eq!(
+            application_from_app_id(app_id),
+            (Some("Microsoft.Office.WINWORD.EXE.15".to_string()), Some("windows_win32".to_string()))
+        );
+        let app_id = r#"[{"application":"{1AC14E77-02E7-4E5D-B744-2EB1AE5198B7}\\notepad.exe","platform":"x_exe_path"}]"#;
+        assert_eq!(application_from_app_id(app_id).0.as_deref(), Some("System32\\notepad.exe"));
+        assert_eq!(application_from_app_id("not json"), (None, None));
+    }
+
+    #[test]
+    fn test_content_location() {
+        let uri = "file:///C:/Users/alice/Documents/Q3%20plan.docx?VolumeId={D3E2}&ObjectId={A1B2}".to_string();
+        assert_eq!(content_location(Some(uri), None).as_deref(), Some("C:\\Users\\alice\\Documents\\Q3 plan.docx"));
+        assert_eq!(
+            content_location(Some("https://example.com/".to_string()), Some("D:\\tools\\x.exe".to_string())).as_deref(),
+            Some("D:\\tools\\x.exe")
+        );
+        assert_eq!(content_location(None, Some("Word".to_string())), None);
+    }
+
+    #[test]
+    fn test_parse_activities() {
+        let id = vec![0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF, 0x00];
+        let data = build_database(
+            "CREATE TABLE Activity ([Id] GUID PRIMARY KEY NOT NULL, [AppId] TEXT NOT NULL, [ActivityType] INT NOT NULL, [LastModifiedTime] DATETIME NOT NULL, [Payload] BLOB, [StartTime] DATETIME NOT NULL, [EndTime] DATETIME)",
+            &[vec![
+                SqliteValue::Blob(id),
+                SqliteValue::Text(r#"[{"application":"{1AC14E77-02E7-4E5D-B744-2EB1AE5198B7}\\mspaint.exe","platform":"x_exe_path"}]"#.to_string()),
+                SqliteValue::Integer(5),
+                SqliteValue::Integer(1_600_000_100),
+                SqliteValue::Blob(br#"{"displayText":"cat.png","appDisplayName":"Paint","contentUri":"file:///C:/Users/bob/cat.png?VolumeId={X}"}"#.to_vec()),
+                SqliteValue::Integer(1_600_000_000),
+                SqliteValue::Integer(0),
+            ]],
+        );
+
+        let activities = parse_activities(&data).unwrap();
+        assert_eq!(activities.len(), 1);
+        let activity = &activities[0];
+        assert_eq!(activity.id, "{44332211-6655-8877-99AA-BBCCDDEEFF00}");
+        assert_eq!(activity.type_name(), "Open");
+        assert_eq!(activity.application.as_deref(), Some("System32\\mspaint.exe"));
+        assert_eq!(activity.display_text.as_deref(), Some("cat.png"));
+        assert_eq!(activity.content.as_deref(), Some("C:\\Users\\bob\\cat.png"));
+        assert_eq!(activity.start_time.unwrap().timestamp(), 1_600_000_000);
+        assert!(activity.end_time.is_none());
+        assert!(activity.operation_type.is_none());
+    }
+}
@@ -0,0 +1,256 @@
+//! Amcache.hve parsing module for program inventory and file hashes
+//!
+//! Supports parsing of:
+//! - Root\InventoryApplicationFile (Windows 10+ file entries)
+//! - Root\InventoryApplication (installed programs)
+//! - Root\InventoryDriverBinary (loaded drivers)
+//! - Root\File (legacy Windows 7/8 file entries)
+
+use crate::registry::{RegistryEventType, RegistryHive, RegistryKey, RegistryTimelineEvent};
+use crate::types::EventTimestamps;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Amcache record category
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AmcacheCategory {
+    /// Root\InventoryApplicationFile
+    ApplicationFile,
+    /// Root\InventoryApplication
+    Application,
+    /// Root\InventoryDriverBinary
+    DriverBinary,
+    /// Root\File (legacy layout)
+    File,
+}
+
+impl AmcacheCategory {
+    /// Display name for the category
+    pub fn name(&self) -> &'static str {
+        match self {
+            AmcacheCategory::ApplicationFile => "InventoryApplicationFile",
+            AmcacheCategory::Application => "InventoryApplication",
+            AmcacheCategory::DriverBinary => "InventoryDriverBinary",
+            AmcacheCategory::File => "File",
+        }
+    }
+}
+
+/// Single Amcache inventory entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AmcacheEntry {
+    /// Record category
+    pub category: AmcacheCategory,
+    /// Registry key path of the record
+    pub key_path: String,
+    /// Full path (or install directory for applications)
+    pub path: Option<String>,
+    /// File, program or driver name
+    pub name: Option<String>,
+    /// SHA1 hash of the file (from FileId/DriverId)
+    pub sha1: Option<String>,
+    /// Publisher / company name
+    pub publisher: Option<String>,
+    /// Version
+    pub version: Option<String>,
+    /// PE link date (compile time)
+    pub link_date: Option<DateTime<Utc>>,
+    /// Install date (applications only)
+    pub install_date: Option<DateTime<Utc>>,
+    /// Key last written time
+    pub last_written: Option<DateTime<Utc>>,
+}
+
+/// Parse all supported Amcache record types from a hive
+pub fn parse_amcache(hive: &RegistryHive) -> Vec<AmcacheEntry> {
+    let mut entries = Vec::new();
+
+    if let Some(key) = hive.open_key("Root\\InventoryApplicationFile") {
+        entries.extend(key.subkeys.iter().map(parse_application_file));
+    }
+    if let Some(key) = hive.open_key("Root\\InventoryApplication") {
+        entries.extend(key.subkeys.iter().map(parse_application));
+    }
+    if let Some(key) = hive.open_key("Root\\InventoryDriverBinary") {
+        entries.extend(key.subkeys.iter().map(parse_driver_binary));
+    }
+    if let Some(key) = hive.open_key("Root\\File") {
+        // Root\File\<volume GUID>\<file reference>
+        for volume in &key.subkeys {
+            entries.extend(volume.subkeys.iter().map(parse_legacy_file));
+        }
+    }
+
+    entries
+}
+
+/// Parse a Root\InventoryApplicationFile record
+fn parse_application_file(key: &RegistryKey) -> AmcacheEntry {
+    AmcacheEntry {
+        category: AmcacheCategory::ApplicationFile,
+        key_path: key.path.clone(),
+        path: key.string_value("LowerCaseLongPath"),
+        name: key.string_value("Name"),
+        sha1: key.string_value("FileId").map(|id| file_id_to_sha1(&id)),
+        publisher: key.string_value("Publisher"),
+        version: key.string_value("Version").or_else(|| key.string_value("BinFileVersion")),
+        link_date: key.string_value("LinkDate").and_then(|d| parse_amcache_date(&d)),
+        install_date: None,
+        last_written: key.last_written,
+    }
+}
+
+/// Parse a Root\InventoryApplication record
+fn parse_application(key: &RegistryKey) -> AmcacheEntry {
+    AmcacheEntry {
+        category: AmcacheCategory::Application,
+        key_path: key.path.clone(),
+        path: key.string_value("RootDirPath"),
+        name: key.string_value("Name"),
+        sha1: None,
+        publisher: key.string_value("Publisher"),
+        version: key.string_value("Version"),
+        link_date: None,
+        install_date: key.string_value("InstallDate").and_then(|d| parse_amcache_date(&d)),
+        last_written: key.last_written,
+    }
+}
+
+/// Parse a Root\InventoryDriverBinary record (the key name is the driver path)
+fn parse_driver_binary(key: &RegistryKey) -> AmcacheEntry {
+    let link_date = key
+        .value("DriverTimeStamp")
+        .and_then(|v| v.dword_data())
+        .filter(|&ts| ts != 0)
+        .and_then(|ts| DateTime::from_timestamp(ts as i64, 0));
+
+    AmcacheEntry {
+        category: AmcacheCategory::DriverBinary,
+        key_path: key.path.clone(),
+        path: Some(key.name.clone()),
+        name: key.string_value("DriverName"),
+        sha1: key.string_value("DriverId").map(|id| file_id_to_sha1(&id)),
+        publisher: key.string_value("DriverCompany"),
+        version: key.string_value("DriverVersion"),
+        link_date,
+        install_date: None,
+        last_written: key.last_written,
+    }
+}
+
+/// Parse a legacy Root\File record (numbered values)
+fn parse_legacy_file(key: &RegistryKey) -> AmcacheEntry {
+    let link_date = key
+        .value("f")
+        .and_then(|v| v.dword_data())
+        .filter(|&ts| ts != 0)
+        .and_then(|ts| DateTime::from_timestamp(ts as i64, 0));
+    let path = key.string_value("15");
+    let name = path
+        .as_ref()
+        .and_then(|p| p.rsplit('\\').next())
+        .map(|n| n.to_string());
+
+    AmcacheEntry {
+        category: AmcacheCategory::File,
+        key_path: key.path.clone(),
+        path,
+        name,
+        sha1: key.string_value("101").map(|id| file_id_to_sha1(&id)),
+        publisher: key.string_value("1"),
+        version: key.string_value("5"),
+        link_date,
+        install_date: None,
+        last_written: key.last_written,
+    }
+}
+
+/// FileId values are the SHA1 hash prefixed with "0000"
+fn file_id_to_sha1(file_id: &str) -> String {
+    let file_id = file_id.to_lowercase();
+    match file_id.strip_prefix("0000") {
+        Some(sha1) if sha1.len() == 40 => sha1.to_string(),
+        _ => file_id,
+    }
+}
+
+/// Parse Amcache date strings ("MM/dd/yyyy HH:mm:ss", sometimes without time)
+fn parse_amcache_date(value: &str) -> Option<DateTime<Utc>> {
+    NaiveDateTime::parse_from_str(value, "%m/%d/%Y %H:%M:%S")
+        .or_else(|_| NaiveDateTime::parse_from_str(&format!("{} 00:00:00", value), "%m/%d/%Y %H:%M:%S"))
+        .ok()
+        .map(|dt| dt.and_utc())
+}
+
+/// Extract Amcache records as timeline events
+pub fn extract_timeline_events(hive: &RegistryHive) -> Vec<RegistryTimelineEvent> {
+    parse_amcache(hive)
+        .into_iter()
+        .map(|entry| {
+            let subject = entry
+                .path
+                .clone()
+                .or_else(|| entry.name.clone())
+                .unwrap_or_else(|| entry.key_path.clone());
+
+            let mut metadata = vec![("Category".to_string(), entry.category.name().to_string())];
+            let fields = [
+                ("Path", &entry.path),
+                ("Name", &entry.name),
+                ("SHA1", &entry.sha1),
+                ("Publisher", &entry.publisher),
+                ("Version", &entry.version),
+            ];
+            for (label, value) in fields {
+                if let Some(value) = value {
+                    metadata.push((label.to_string(), value.clone()));
+                }
+            }
+            if let Some(link_date) = entry.link_date {
+                metadata.push(("Link Date".to_string(), link_date.to_rfc3339()));
+            }
+
+            RegistryTimelineEvent {
+                key_path: entry.key_path.clone(),
+                value_name: None,
+                event_type: RegistryEventType::KeyModified,
+                value_type: None,
+                data: None,
+                data_size: None,
+                timestamps: EventTimestamps {
+                    created: entry.install_date.or(entry.link_date),
+                    modified: entry.last_written,
+                    accessed: None,
+                    mft_modified: None,
+                },
+                description: Some(format!("Amcache: {}", subject)),
+                metadata,
+                source: Some("Amcache".to_string()),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Datelike;
+
+    #[test]
+    fn test_file_id_to_sha1() {
+        assert_eq!(
+            file_id_to_sha1("0000A94A8FE5CCB19BA61C4C0873D391E987982FBBD3"),
+            "a94a8fe5ccb19ba61c4c0873d391e987982fbbd3"
+        );
+        assert_eq!(file_id_to_sha1("abc"), "abc");
+    }
+
+    #[test]
+    fn test_parse_amcache_date() {
+        let dt = parse_amcache_date("07/15/2021 13:45:10").unwrap();
+        assert_eq!(dt.year(), 2021);
+        assert_eq!(dt.month(), 7);
+        assert!(parse_amcache_date("03/02/2019").is_some());
+        assert!(parse_amcache_date("not a date").is_none());
+    }
+}
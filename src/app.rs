@@ -10,6 +10,8 @@ use crate::{
     output::{create_writer, OutputWriter},
     types::{Event, ParsingConfig},
     parse_drive_letter,
+    registry::{RegistryHive, RegistryParser},
+    system_context::{hive_owner_directory, SystemContext},
 };
 use rayon::prelude::*;
 use regex::Regex;
@@ -22,15 +24,20 @@ use crate::windows::LiveSystemAccess;
 pub struct App {
     config: Config,
     parser: MftParser,
+    /// Registry hives parsed from the input, kept for cross-hive analysis
+    hives: Vec<RegistryHive>,
+    /// System context derived from the hives (timezone, hostname, OS, users)
+    system_context: Option<SystemContext>,
 }
 
 impl App {
     /// Create a new application instance with the given configuration
     pub fn new(config: Config) -> Self {
         let parsing_config = ParsingConfig::optimized();
-        let parser = MftParser::with_config(parsing_config);
+        let mut parser = MftParser::with_config(parsing_config);
+        parser.set_event_catalog(config.event_catalog.clone());
         
-        Self { config, parser }
+        Self { config, parser, hives: Vec::new(), system_context: None }
     }
 
     /// Run the application with the configured parameters
@@ -38,6 +45,12 @@ impl App {
         // Parse MFT records from various sources
         let records = self.parse_mft_records()?;
 
+        self.apply_system_timezone();
+
+        if let Some(report_path) = self.config.persistence_report.clone() {
+            self.write_persistence_report(&report_path)?;
+        }
+
         // Calculate total timeline events before filtering for accurate footer display
         let total_timeline_events: usize = records
             .par_iter()
@@ -51,6 +64,28 @@ impl App {
         self.handle_output(filtered_records, total_timeline_events)
     }
 
+    /// Apply (with --timezone auto) or suggest the timezone configured on the examined system
+    fn apply_system_timezone(&mut self) {
+        let Some(setting) = self.system_context.as_ref().and_then(|c| c.timezone.clone()) else {
+            return;
+        };
+        let zone = setting.name().unwrap_or("unknown").to_string();
+
+        match setting.to_tz() {
+            Some(tz) if self.config.timezone_auto => {
+                eprintln!("🕒 Using system timezone {} ({})", zone, tz.name());
+                self.config.timezone = tz;
+            }
+            Some(tz) if tz != self.config.timezone => {
+                eprintln!("💡 System timezone is {} ({}); use --timezone auto to display local time", zone, tz.name());
+            }
+            None if self.config.timezone_auto => {
+                eprintln!("⚠️  Could not map system timezone {}; keeping {}", zone, self.config.timezone.name());
+            }
+            _ => {}
+        }
+    }
+
     /// Parse MFT records from the configured input source
     fn parse_mft_records(&mut self) -> Result<Vec<Event>> {
         if let Some(drive_letter) = parse_drive_letter(&self.config.input_file) {
@@ -87,7 +122,16 @@ impl App {
             InputType::ZipContainer | InputType::E01Container | InputType::RawContainer => {
                 // Extract all artifacts from container format
                 let extracted = ContainerExtractor::extract_artifacts(path, self.config.password.as_deref())?;
-                self.process_multiple_artifacts(extracted.artifacts)
+                self.process_multiple_artifacts(extracted.artifacts, &extracted.entry_names)
+            },
+            InputType::Registry => {
+                // Parse standalone hives in memory like container hives so they stay available
+                let artifact = ExtractedArtifact {
+                    name: self.config.input_file.clone(),
+                    artifact_type: InputType::Registry,
+                    data: std::fs::read(path)?,
+                };
+                self.process_multiple_artifacts(vec![artifact], &[])
             },
             _ => {
                 // Handle regular MFT files and other artifact types
@@ -97,8 +141,10 @@ impl App {
     }
 
     /// Process multiple artifacts from container and combine into unified timeline
-    fn process_multiple_artifacts(&mut self, artifacts: Vec<ExtractedArtifact>) -> Result<Vec<Event>> {
+    fn process_multiple_artifacts(&mut self, artifacts: Vec<ExtractedArtifact>, entry_names: &[String]) -> Result<Vec<Event>> {
         let mut all_records = Vec::new();
+        // (hive path, first record index, record count) for per-user attribution
+        let mut hive_ranges = Vec::new();
         
         eprintln!("🔄 Processing {} artifacts from container...", artifacts.len());
         
@@ -116,13 +162,63 @@ impl App {
                     // Process LNK file - create temporary file approach for now
                     self.process_lnk_artifact(&artifact)?
                 },
-                InputType::AutomaticDestinations | InputType::CustomDestinations => {
+                InputType::AutomaticDestinations => {
                     // Process jumplist files
                     self.process_jumplist_artifact(&artifact)?
                 },
+                InputType::CustomDestinations => {
+                    // Process custom destinations in memory, keeping the name for the app ID
+                    self.parser.parse_custom_destinations_data(&artifact.data, Path::new(&artifact.name))?
+                },
+                InputType::Prefetch => {
+                    // Process prefetch files in memory
+                    self.parser.parse_prefetch_data(&artifact.data, Path::new(&artifact.name))?
+                },
+                InputType::Evtx => {
+                    // Process event logs in memory
+                    self.parser.parse_evtx_data(&artifact.data, Path::new(&artifact.name))?
+                },
+                InputType::RecycleBin => {
+                    // Pair $I files with their $R content (a file, or a directory's entries)
+                    self.parser.parse_recycle_bin_data(&artifact.data, Path::new(&artifact.name), |content| {
+                        entry_names.iter().any(|name| {
+                            name == content || name.strip_prefix(content).is_some_and(|rest| rest.starts_with('/'))
+                        })
+                    })?
+                },
+                InputType::ActivitiesCache => {
+                    // Process Windows Timeline databases in memory
+                    self.parser.parse_activities_cache_data(&artifact.data, Path::new(&artifact.name))?
+                },
+                InputType::BrowserHistory => {
+                    // Process Chromium and Firefox history databases in memory
+                    self.parser.parse_browser_history_data(&artifact.data, Path::new(&artifact.name))?
+                },
+                InputType::Srum => {
+                    // Process SRUM databases in memory
+                    self.parser.parse_srum_data(&artifact.data, Path::new(&artifact.name))?
+                },
+                InputType::ScheduledTask => {
+                    // Process scheduled task files in memory
+                    self.parser.parse_scheduled_task_data(&artifact.data, Path::new(&artifact.name))?
+                },
+                InputType::OfficeDocument => {
+                    // Process Office document properties in memory
+                    self.parser.parse_office_data(&artifact.data, Path::new(&artifact.name))?
+                },
                 InputType::Registry => {
-                    // Process registry files
-                    self.process_registry_artifact(&artifact)?
+                    // Process registry files, keeping the hive for cross-hive analysis
+                    match self.process_registry_artifact(&artifact) {
+                        Ok((hive, records)) => {
+                            hive_ranges.push((hive.file_path.clone(), all_records.len(), records.len()));
+                            self.hives.push(hive);
+                            records
+                        }
+                        Err(e) => {
+                            eprintln!("⚠️  Failed to parse registry hive {}: {}", artifact.name, e);
+                            continue;
+                        }
+                    }
                 },
                 _ => {
                     eprintln!("⚠️  Skipping unsupported artifact type: {:?}", artifact.artifact_type);
@@ -133,6 +229,25 @@ impl App {
             eprintln!("📊 Extracted {} records from {}", records.len(), artifact.name);
             all_records.extend(records);
         }
+
+        if !self.hives.is_empty() {
+            let hive_refs: Vec<&RegistryHive> = self.hives.iter().collect();
+            let context = SystemContext::from_hives(&hive_refs);
+            Self::attribute_user_hives(&context, &hive_ranges, &mut all_records);
+            Self::attribute_sids(&context, &mut all_records);
+            context.convert_local_times(&mut all_records);
+            if !context.is_empty() {
+                eprintln!("🖥️  System context: {}", context.summary());
+                self.system_context = Some(context);
+            }
+
+            all_records.extend(Self::process_usb_history(&self.hives)?);
+            Self::correlate_scheduled_tasks(&self.hives, &mut all_records);
+        }
+        // Task files without dates stay only if TaskCache supplied their times
+        MftParser::retain_dated_tasks(&mut all_records);
+
+        Self::link_downloads_to_mft(&mut all_records);
         
         eprintln!("🎉 Combined {} total records from all artifacts", all_records.len());
         Ok(all_records)
@@ -195,28 +310,129 @@ impl App {
         result
     }
 
-    /// Process registry artifact by writing to temporary file and parsing
-    fn process_registry_artifact(&mut self, artifact: &ExtractedArtifact) -> Result<Vec<Event>> {
+    /// Parse a registry artifact in memory, keeping its container path for hive detection
+    fn process_registry_artifact(&mut self, artifact: &ExtractedArtifact) -> Result<(RegistryHive, Vec<Event>)> {
+        let path = Path::new(&artifact.name);
+        let hive = RegistryParser::new().parse_registry_data(&artifact.data, path)?;
+        let records = self.parser.registry_hive_records(&hive, path)?;
+        Ok((hive, records))
+    }
+
+    /// Tag events from NTUSER.DAT / UsrClass.dat hives with the owning user and SID
+    fn attribute_user_hives(context: &SystemContext, hive_ranges: &[(String, usize, usize)], records: &mut [Event]) {
+        for (hive_path, start, count) in hive_ranges {
+            let Some(directory) = hive_owner_directory(hive_path) else {
+                continue;
+            };
+            let profile = context.profile_for_hive(hive_path);
+            let user = profile.map(|p| p.user.clone()).unwrap_or(directory);
+
+            for record in &mut records[*start..*start + *count] {
+                record.metadata.push(("User".to_string(), user.clone()));
+                if let Some(profile) = profile {
+                    record.metadata.push(("SID".to_string(), profile.sid.clone()));
+                }
+            }
+        }
+    }
+
+    /// Resolve the SID of Recycle Bin and SRUM events to a user name
+    fn attribute_sids(context: &SystemContext, records: &mut [Event]) {
+        for record in records.iter_mut().filter(|r| matches!(r.event_source.as_deref(), Some("RecycleBin" | "SRUM"))) {
+            let sid = record.metadata.iter().find(|(k, _)| k == "SID").map(|(_, v)| v.clone());
+            if let Some(profile) = sid.and_then(|sid| context.profiles.iter().find(|p| p.sid == sid)) {
+                record.metadata.push(("User".to_string(), profile.user.clone()));
+            }
+        }
+    }
+
+    /// Point browser download events at the MFT record of the file they saved
+    fn link_downloads_to_mft(records: &mut [Event]) {
+        let mft_paths: std::collections::HashMap<String, (u64, String)> = records
+            .iter()
+            .filter(|r| r.event_source.as_deref() == Some("MFT"))
+            .filter_map(|r| {
+                let location = r.location.as_ref()?;
+                Some((crate::browser::mft_path_key(location), (r.record_number, location.clone())))
+            })
+            .collect();
+        if mft_paths.is_empty() {
+            return;
+        }
+
+        for record in records.iter_mut().filter(|r| r.event_source.as_deref() == Some("Browser")) {
+            let target = record.metadata.iter().find(|(k, _)| k == "Target Path").map(|(_, v)| crate::browser::mft_path_key(v));
+            if let Some((record_number, path)) = target.and_then(|target| mft_paths.get(&target)) {
+                record.metadata.push(("MFT Record".to_string(), record_number.to_string()));
+                record.metadata.push(("MFT Path".to_string(), path.clone()));
+            }
+        }
+    }
+
+    /// Add TaskCache run times to task file events, emitting registrations whose task file is absent
+    fn correlate_scheduled_tasks(hives: &[RegistryHive], records: &mut Vec<Event>) {
+        let entries: Vec<_> = hives.iter().flat_map(crate::scheduled_task::parse_task_cache).collect();
+        if entries.is_empty() {
+            return;
+        }
+        // Task path (lowercase) -> indices of task file events
+        let mut task_records: std::collections::HashMap<String, Vec<usize>> = std::collections::HashMap::new();
+        for (index, record) in records.iter().enumerate().filter(|(_, r)| r.event_source.as_deref() == Some("ScheduledTask")) {
+            if let Some((_, path)) = record.metadata.iter().find(|(k, _)| k == "Task Path") {
+                task_records.entry(path.to_lowercase()).or_default().push(index);
+            }
+        }
+        let task_files_collected = !task_records.is_empty();
+
+        let mut unmatched = Vec::new();
+        for entry in &entries {
+            let Some(indices) = task_records.get(&entry.path.to_lowercase()) else {
+                unmatched.push(MftParser::task_cache_to_mft_record(entry, task_files_collected));
+                continue;
+            };
+            for &index in indices {
+                let record = &mut records[index];
+                let timestamps = &mut record.timestamps;
+                timestamps.created = timestamps.created.or(entry.created);
+                timestamps.modified = timestamps.modified.or(entry.last_success);
+                timestamps.accessed = timestamps.accessed.or(entry.last_run);
+                record.metadata.extend(entry.fields());
+            }
+        }
+        eprintln!("📅 Correlated {} TaskCache registrations ({} without task files)", entries.len(), unmatched.len());
+        records.extend(unmatched);
+    }
+
+    /// Join USB device history across all SYSTEM, SOFTWARE and NTUSER.DAT hives
+    fn process_usb_history(hives: &[RegistryHive]) -> Result<Vec<Event>> {
+        let hive_refs: Vec<&RegistryHive> = hives.iter().collect();
+        let events = crate::usb::extract_timeline_events(&hive_refs);
+        if events.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // Attribute the joined records to the SYSTEM hive
+        let system = hives
+            .iter()
+            .find(|h| h.current_control_set().is_some())
+            .unwrap_or(&hives[0]);
+        let records = MftParser::registry_events_to_records(&events, Path::new(&system.file_path))?;
+        eprintln!("🔌 Extracted {} USB device history records", records.len());
+        Ok(records)
+    }
+
+    /// Write the autostart persistence report for all parsed hives as JSON
+    fn write_persistence_report(&self, report_path: &str) -> Result<()> {
         use std::io::Write;
-        
-        // Create temporary file for registry data
-        let temp_dir = std::env::temp_dir();
-        let temp_path = temp_dir.join(format!("temp_{}.dat", 
-            std::process::id()));
-        
-        // Write artifact data to temp file
-        let mut temp_file = std::fs::File::create(&temp_path)?;
-        temp_file.write_all(&artifact.data)?;
-        temp_file.sync_all()?;
-        drop(temp_file);
-        
-        // Parse using existing infrastructure
-        let result = self.parser.parse_input(&temp_path, None);
-        
-        // Clean up temp file
-        let _ = std::fs::remove_file(&temp_path);
-        
-        result
+
+        let hive_refs: Vec<&RegistryHive> = self.hives.iter().collect();
+        let report = crate::persistence::persistence_report(&hive_refs);
+        eprintln!("🧷 Writing persistence report ({} entries) to {}", report.entries.len(), report_path);
+
+        let mut writer = create_writer(Some(report_path.to_string()))?;
+        serde_json::to_writer_pretty(&mut writer, &report)?;
+        writeln!(writer)?;
+        Ok(())
     }
 
     /// Apply command-line filters to the record set efficiently
@@ -298,7 +514,7 @@ impl App {
         let mut viewer = InteractiveViewer::new_fast(
             filtered_records,
             self.config.timezone,
-            self.config.input_file.clone(),
+            self.header_source(),
             total_timeline_events,
         )?;
         
@@ -308,9 +524,70 @@ impl App {
 
 
 
+    /// Data source shown in the TUI header, with system context when available
+    fn header_source(&self) -> String {
+        match &self.system_context {
+            Some(context) => format!("{} | {}", self.config.input_file, context.summary()),
+            None => self.config.input_file.clone(),
+        }
+    }
+
     /// Write output to file or stdout
     fn write_file_output(&self, filtered_records: Vec<Event>) -> Result<()> {
         let writer = create_writer(self.config.output.clone())?;
         OutputWriter::write_timeline(filtered_records, writer, self.config.timezone)
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::test_hive::TestKey;
+
+    /// Task XML without RegistrationInfo/Date, as left by an attacker-created task
+    const UNDATED_TASK_XML: &str = r#"<?xml version="1.0" encoding="UTF-16"?>
+<Task version="1.2" xmlns="http://schemas.microsoft.com/windows/2004/02/mit/task">
+  <RegistrationInfo><URI>\Updater\Check</URI></RegistrationInfo>
+  <Actions Context="Author"><Exec><Command>C:\ProgramData\u.exe</Command></Exec></Actions>
+</Task>"#;
+
+    #[test]
+    fn test_undated_task_files_are_dated_from_task_cache() {
+        let mut parser = MftParser::new();
+        let mut records = Vec::new();
+        for folder in ["Updater", "Other"] {
+            let xml = UNDATED_TASK_XML.replace("Updater", folder);
+            let path = format!("C/Windows/System32/Tasks/{}/Check", folder);
+            records.extend(parser.parse_scheduled_task_data(xml.as_bytes(), Path::new(&path)).unwrap());
+        }
+        assert_eq!(records.len(), 2);
+
+        // DynamicInfo: version, creation time (2021-01-01) and last run time (2021-01-02)
+        let mut dynamic_info = 3u32.to_le_bytes().to_vec();
+        dynamic_info.extend(132539328000000000u64.to_le_bytes());
+        dynamic_info.extend(132540192000000000u64.to_le_bytes());
+        dynamic_info.resize(36, 0);
+        let id = "{11111111-2222-3333-4444-555555555555}";
+        let hive = TestKey::new("ROOT")
+            .with(
+                "Microsoft\\Windows NT\\CurrentVersion\\Schedule\\TaskCache\\Tasks",
+                TestKey::new(id).string("Path", "\\Updater\\Check").binary("DynamicInfo", &dynamic_info),
+            )
+            .with(
+                "Microsoft\\Windows NT\\CurrentVersion\\Schedule\\TaskCache\\Tree\\Updater",
+                TestKey::new("Check").string("Id", id),
+            )
+            .open("SOFTWARE");
+
+        App::correlate_scheduled_tasks(&[hive], &mut records);
+        MftParser::retain_dated_tasks(&mut records);
+
+        // The correlated task file keeps its actions and takes TaskCache times; the undated,
+        // unregistered task is dropped and no "Task File: Missing" record is emitted
+        assert_eq!(records.len(), 1);
+        let record = &records[0];
+        assert_eq!(record.timestamps.created.unwrap().to_rfc3339(), "2021-01-01T00:00:00+00:00");
+        assert_eq!(record.timestamps.accessed.unwrap().to_rfc3339(), "2021-01-02T00:00:00+00:00");
+        assert!(record.metadata.contains(&("Command Line".to_string(), "C:\\ProgramData\\u.exe".to_string())));
+        assert!(record.metadata.iter().any(|(k, v)| k == "Task File" && v != "Missing"));
+    }
+}
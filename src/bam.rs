@@ -0,0 +1,148 @@
+//! Background/Desktop Activity Moderator (BAM/DAM) parsing module for the SYSTEM hive
+//!
+//! Supports parsing of:
+//! - Services\bam\State\UserSettings\<SID> (Windows 10 1809+)
+//! - Services\bam\UserSettings\<SID> (earlier Windows 10)
+//! - The equivalent dam keys
+
+use crate::registry::{
+    filetime_to_datetime, read_u64, RegistryEventType, RegistryHive, RegistryTimelineEvent,
+};
+use crate::types::EventTimestamps;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Single BAM/DAM execution record
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BamEntry {
+    /// Service the record came from ("bam" or "dam")
+    pub service: String,
+    /// Registry key path of the user's settings
+    pub key_path: String,
+    /// User SID
+    pub sid: String,
+    /// Path as stored (\Device\HarddiskVolumeN\... or an app identifier)
+    pub path: String,
+    /// Last execution time
+    pub last_executed: Option<DateTime<Utc>>,
+}
+
+/// Parse BAM and DAM records from the current control set
+pub fn parse_bam(hive: &RegistryHive) -> Vec<BamEntry> {
+    let Some(control_set) = hive.current_control_set() else {
+        return Vec::new();
+    };
+
+    let mut raw = Vec::new();
+    for service in ["bam", "dam"] {
+        for settings in ["State\\UserSettings", "UserSettings"] {
+            let key_path = format!("{}\\Services\\{}\\{}", control_set, service, settings);
+            let Some(key) = hive.open_key(&key_path) else {
+                continue;
+            };
+
+            for user in &key.subkeys {
+                for value in &user.values {
+                    // Skip bookkeeping values (Version, SequenceNumber)
+                    if value.data.len() < 8
+                        || value.name.eq_ignore_ascii_case("Version")
+                        || value.name.eq_ignore_ascii_case("SequenceNumber")
+                    {
+                        continue;
+                    }
+                    raw.push((
+                        service,
+                        user.path.clone(),
+                        user.name.clone(),
+                        value.name.clone(),
+                        read_u64(&value.data, 0),
+                    ));
+                }
+            }
+        }
+    }
+
+    raw.into_iter()
+        .map(|(service, key_path, sid, path, filetime)| BamEntry {
+            service: service.to_string(),
+            key_path,
+            sid,
+            path,
+            last_executed: filetime.and_then(filetime_to_datetime),
+        })
+        .collect()
+}
+
+/// Check for a \Device\HarddiskVolumeN\ path. These are left as stored: MountedDevices keys
+/// drive letters by disk signature or partition GUID, not by volume number.
+fn is_volume_path(path: &str) -> bool {
+    path.get(..22).is_some_and(|prefix| prefix.eq_ignore_ascii_case("\\Device\\HarddiskVolume"))
+}
+
+/// Extract BAM/DAM records as timeline events
+pub fn extract_timeline_events(hive: &RegistryHive) -> Vec<RegistryTimelineEvent> {
+    parse_bam(hive)
+        .into_iter()
+        .map(|entry| {
+            let source = entry.service.to_uppercase();
+            let mut metadata = vec![
+                ("SID".to_string(), entry.sid.clone()),
+                ("Path".to_string(), entry.path.clone()),
+            ];
+            if is_volume_path(&entry.path) {
+                metadata.push(("Volume".to_string(), "Unresolved (no drive letter mapping)".to_string()));
+            }
+
+            RegistryTimelineEvent {
+                key_path: entry.key_path.clone(),
+                value_name: Some(entry.path.clone()),
+                event_type: RegistryEventType::ValueSet,
+                value_type: None,
+                data: None,
+                data_size: None,
+                timestamps: EventTimestamps {
+                    created: None,
+                    modified: entry.last_executed,
+                    accessed: None,
+                    mft_modified: None,
+                },
+                description: Some(format!("{}: {} [{}]", source, entry.path, entry.sid)),
+                metadata,
+                source: Some(source),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::test_hive::TestKey;
+
+    #[test]
+    fn test_unresolved_volumes_are_labelled() {
+        let filetime = 132223104000000000u64.to_le_bytes();
+        let user = TestKey::new("S-1-5-21-1000")
+            .binary("\\Device\\HarddiskVolume3\\Windows\\System32\\cmd.exe", &filetime)
+            .binary("\\Device\\HarddiskVolume5\\Tools\\evil.exe", &filetime)
+            .binary("Microsoft.Windows.Explorer", &filetime)
+            .dword("Version", 1);
+        let hive = TestKey::new("ROOT")
+            .with("", TestKey::new("Select").dword("Current", 1))
+            .with("ControlSet001\\Services\\bam\\State\\UserSettings", user)
+            .with("", TestKey::new("MountedDevices").binary("\\DosDevices\\D:", &[0u8; 12]))
+            .open("SYSTEM");
+
+        let events = extract_timeline_events(&hive);
+        assert_eq!(events.len(), 3);
+        let unresolved = ("Volume".to_string(), "Unresolved (no drive letter mapping)".to_string());
+        // Volume paths are never rewritten to a guessed drive letter
+        assert_eq!(
+            events[0].description.as_deref(),
+            Some("BAM: \\Device\\HarddiskVolume3\\Windows\\System32\\cmd.exe [S-1-5-21-1000]")
+        );
+        assert!(events[0].metadata.contains(&unresolved));
+        assert!(events[1].metadata.contains(&unresolved));
+        assert!(!events[2].metadata.contains(&unresolved));
+    }
+}
@@ -0,0 +1,500 @@
+//! Browser history parsing module
+//!
+//! Supports parsing of:
+//! - Chromium `History` databases (Chrome, Edge, Brave, ...): urls, visits, downloads and download URL chains
+//! - Firefox `places.sqlite`: moz_places, moz_historyvisits and download annotations (moz_annos)
+//! - WebKit (microseconds since 1601) and PRTime (microseconds since 1970) timestamps
+
+use crate::error::{Error, Result};
+use crate::registry::filetime_to_datetime;
+use crate::sqlite::{SqliteDatabase, Table};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Chromium page transition core types (PageTransition & 0xFF)
+const CHROMIUM_TRANSITIONS: [&str; 11] = [
+    "Link",
+    "Typed",
+    "Auto Bookmark",
+    "Auto Subframe",
+    "Manual Subframe",
+    "Generated",
+    "Auto Toplevel",
+    "Form Submit",
+    "Reload",
+    "Keyword",
+    "Keyword Generated",
+];
+
+/// Chromium download danger types
+const CHROMIUM_DANGER_TYPES: [&str; 11] = [
+    "Not Dangerous",
+    "Dangerous File",
+    "Dangerous URL",
+    "Dangerous Content",
+    "Maybe Dangerous Content",
+    "Uncommon Content",
+    "User Validated",
+    "Dangerous Host",
+    "Potentially Unwanted",
+    "Allowlisted by Policy",
+    "Async Scanning",
+];
+
+/// Firefox visit types (moz_historyvisits.visit_type, 1-based)
+const FIREFOX_VISIT_TYPES: [&str; 9] = [
+    "Link",
+    "Typed",
+    "Bookmark",
+    "Embed",
+    "Permanent Redirect",
+    "Temporary Redirect",
+    "Download",
+    "Framed Link",
+    "Reload",
+];
+
+/// Browser family a history database belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Browser {
+    Chromium,
+    Firefox,
+}
+
+/// One page visit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BrowserVisit {
+    /// Visited URL
+    pub url: String,
+    /// Page title
+    pub title: Option<String>,
+    /// Visit time
+    pub visit_time: Option<DateTime<Utc>>,
+    /// How the visit was made (typed, link, reload, ...)
+    pub transition: Option<String>,
+    /// Total visits to the URL
+    pub visit_count: Option<i64>,
+    /// Times the URL was typed
+    pub typed_count: Option<i64>,
+    /// Time spent on the page in seconds (Chromium)
+    pub duration: Option<f64>,
+}
+
+/// One download
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BrowserDownload {
+    /// Final download URL
+    pub url: Option<String>,
+    /// Referring page
+    pub referrer: Option<String>,
+    /// Saved file path
+    pub target_path: String,
+    /// Download start time
+    pub start_time: Option<DateTime<Utc>>,
+    /// Download end time
+    pub end_time: Option<DateTime<Utc>>,
+    /// Bytes received
+    pub received_bytes: Option<u64>,
+    /// Expected size in bytes
+    pub total_bytes: Option<u64>,
+    /// Download state (complete, cancelled, ...)
+    pub state: Option<String>,
+    /// Chromium danger verdict
+    pub danger_type: Option<String>,
+    /// MIME type
+    pub mime_type: Option<String>,
+}
+
+/// Visits and downloads from one browser profile database
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BrowserHistory {
+    /// Browser family
+    pub browser: Browser,
+    /// Page visits
+    pub visits: Vec<BrowserVisit>,
+    /// Downloads
+    pub downloads: Vec<BrowserDownload>,
+}
+
+impl BrowserHistory {
+    /// Parse a Chromium History or Firefox places.sqlite database image
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        let database = SqliteDatabase::open(data)?;
+        match history_browser(&database) {
+            Some(Browser::Firefox) => Self::parse_firefox(&database),
+            Some(Browser::Chromium) => Self::parse_chromium(&database),
+            None => Err(Error::ParseError("Not a Chromium or Firefox history database".to_string())),
+        }
+    }
+
+    /// Read urls, visits and downloads from a Chromium History database
+    fn parse_chromium(database: &SqliteDatabase) -> Result<Self> {
+        let urls = database.read_table("urls")?;
+        let urls_by_id: HashMap<i64, _> = urls.records().filter_map(|row| Some((row.integer("id")?, row))).collect();
+
+        let visits = database
+            .read_table("visits")?
+            .records()
+            .filter_map(|visit| {
+                let url = urls_by_id.get(&visit.integer("url")?)?;
+                Some(BrowserVisit {
+                    url: url.text("url")?,
+                    title: url.text("title"),
+                    visit_time: webkit_time(visit.integer("visit_time")),
+                    transition: visit
+                        .integer("transition")
+                        .map(|t| CHROMIUM_TRANSITIONS.get((t & 0xFF) as usize).map_or_else(|| format!("Transition {}", t & 0xFF), |s| s.to_string())),
+                    visit_count: url.integer("visit_count"),
+                    typed_count: url.integer("typed_count"),
+                    duration: visit.integer("visit_duration").filter(|&d| d > 0).map(|d| d as f64 / 1_000_000.0),
+                })
+            })
+            .collect();
+
+        // Download URL chains hold the redirect sequence; the last entry is the URL the file came from
+        let mut chains: HashMap<i64, (i64, String)> = HashMap::new();
+        if database.table_schema("downloads_url_chains").is_some() {
+            for link in database.read_table("downloads_url_chains")?.records() {
+                let (Some(id), Some(index), Some(url)) = (link.integer("id"), link.integer("chain_index"), link.text("url")) else {
+                    continue;
+                };
+                if chains.get(&id).is_none_or(|(current, _)| index > *current) {
+                    chains.insert(id, (index, url));
+                }
+            }
+        }
+
+        let downloads = match database.table_schema("downloads") {
+            Some(_) => database
+                .read_table("downloads")?
+                .records()
+                .filter_map(|download| {
+                    let target_path = download.text("target_path").or_else(|| download.text("current_path"))?;
+                    Some(BrowserDownload {
+                        url: download.integer("id").and_then(|id| chains.get(&id)).map(|(_, url)| url.clone()),
+                        referrer: download.text("referrer"),
+                        target_path,
+                        start_time: webkit_time(download.integer("start_time")),
+                        end_time: webkit_time(download.integer("end_time")),
+                        received_bytes: download.integer("received_bytes").map(|b| b as u64),
+                        total_bytes: download.integer("total_bytes").filter(|&b| b > 0).map(|b| b as u64),
+                        state: download.integer("state").map(|s| {
+                            match s {
+                                0 => "In Progress",
+                                1 => "Complete",
+                                2 => "Cancelled",
+                                3 | 4 => "Interrupted",
+                                _ => "Unknown",
+                            }
+                            .to_string()
+                        }),
+                        danger_type: download
+                            .integer("danger_type")
+                            .map(|d| CHROMIUM_DANGER_TYPES.get(d as usize).map_or_else(|| format!("Danger Type {}", d), |s| s.to_string())),
+                        mime_type: download.text("mime_type"),
+                    })
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+
+        Ok(BrowserHistory { browser: Browser::Chromium, visits, downloads })
+    }
+
+    /// Read moz_places, moz_historyvisits and download annotations from a Firefox places.sqlite database
+    fn parse_firefox(database: &SqliteDatabase) -> Result<Self> {
+        let places = database.read_table("moz_places")?;
+        let places_by_id: HashMap<i64, _> = places.records().filter_map(|row| Some((row.integer("id")?, row))).collect();
+
+        let visits = match database.table_schema("moz_historyvisits") {
+            Some(_) => database
+                .read_table("moz_historyvisits")?
+                .records()
+                .filter_map(|visit| {
+                    let place = places_by_id.get(&visit.integer("place_id")?)?;
+                    Some(BrowserVisit {
+                        url: place.text("url")?,
+                        title: place.text("title"),
+                        visit_time: prtime(visit.integer("visit_date")),
+                        transition: visit.integer("visit_type").map(|t| {
+                            FIREFOX_VISIT_TYPES.get((t - 1).max(0) as usize).filter(|_| t > 0).map_or_else(|| format!("Visit Type {}", t), |s| s.to_string())
+                        }),
+                        visit_count: place.integer("visit_count"),
+                        typed_count: place.integer("typed"),
+                        duration: None,
+                    })
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+
+        let downloads = if database.table_schema("moz_annos").is_some() && database.table_schema("moz_anno_attributes").is_some() {
+            Self::firefox_downloads(&database.read_table("moz_annos")?, &database.read_table("moz_anno_attributes")?, &places_by_id)
+        } else {
+            Vec::new()
+        };
+
+        Ok(BrowserHistory { browser: Browser::Firefox, visits, downloads })
+    }
+
+    /// Build downloads from the destinationFileURI and metaData annotations of each downloaded place
+    fn firefox_downloads(annos: &Table, attributes: &Table, places: &HashMap<i64, crate::sqlite::Row>) -> Vec<BrowserDownload> {
+        let attribute_id = |name: &str| attributes.records().find(|a| a.text("name").as_deref() == Some(name)).and_then(|a| a.integer("id"));
+        let Some(destination_id) = attribute_id("downloads/destinationFileURI") else {
+            return Vec::new();
+        };
+        let metadata_id = attribute_id("downloads/metaData");
+
+        // Index the metaData annotations by place once instead of rescanning for every destination
+        let mut metadata_by_place = HashMap::new();
+        for anno in annos.records().filter(|a| metadata_id.is_some() && a.integer("anno_attribute_id") == metadata_id) {
+            if let Some(place_id) = anno.integer("place_id") {
+                metadata_by_place.entry(place_id).or_insert(anno);
+            }
+        }
+
+        let mut downloads = Vec::new();
+        for destination in annos.records().filter(|a| a.integer("anno_attribute_id") == Some(destination_id)) {
+            let Some(place_id) = destination.integer("place_id") else { continue };
+            let Some(target_path) = destination.text("content").map(|uri| file_uri_to_path(&uri)) else { continue };
+            let metadata: serde_json::Value = metadata_by_place
+                .get(&place_id)
+                .and_then(|a| a.text("content"))
+                .and_then(|json| serde_json::from_str(&json).ok())
+                .unwrap_or(serde_json::Value::Null);
+
+            downloads.push(BrowserDownload {
+                url: places.get(&place_id).and_then(|place| place.text("url")),
+                referrer: None,
+                target_path,
+                start_time: prtime(destination.integer("dateAdded")),
+                // metaData endTime is in milliseconds
+                end_time: metadata.get("endTime").and_then(|t| t.as_i64()).and_then(DateTime::from_timestamp_millis),
+                received_bytes: None,
+                total_bytes: metadata.get("fileSize").and_then(|s| s.as_u64()),
+                state: metadata.get("state").and_then(|s| s.as_i64()).map(|s| {
+                    match s {
+                        0 => "In Progress",
+                        1 => "Complete",
+                        2 => "Failed",
+                        3 => "Cancelled",
+                        4 => "Paused",
+                        _ => "Unknown",
+                    }
+                    .to_string()
+                }),
+                danger_type: None,
+                mime_type: None,
+            });
+        }
+        downloads
+    }
+}
+
+/// Whether a file name is a browser history database (Chromium `History` or Firefox `places.sqlite`)
+pub fn is_history_database(filename: &str) -> bool {
+    let name = filename.rsplit(['/', '\\']).next().unwrap_or(filename);
+    name.eq_ignore_ascii_case("History") || name.eq_ignore_ascii_case("places.sqlite")
+}
+
+/// Whether a database image has the tables of a Chromium or Firefox history database
+pub fn is_history_data(data: &[u8]) -> bool {
+    SqliteDatabase::open(data).is_ok_and(|database| history_browser(&database).is_some())
+}
+
+/// Whether a file on disk is named like a browser history database and has its tables
+pub fn is_history_database_file(path: &Path) -> bool {
+    path.file_name().and_then(|name| name.to_str()).is_some_and(is_history_database)
+        && std::fs::read(path).is_ok_and(|data| is_history_data(&data))
+}
+
+/// Browser whose history schema a database has (moz_places, or Chromium's urls and visits)
+fn history_browser(database: &SqliteDatabase) -> Option<Browser> {
+    if database.table_schema("moz_places").is_some() {
+        Some(Browser::Firefox)
+    } else if database.table_schema("urls").is_some() && database.table_schema("visits").is_some() {
+        Some(Browser::Chromium)
+    } else {
+        None
+    }
+}
+
+/// Key for matching a Windows path against MFT locations (root-relative, '/' separated, lowercase)
+pub fn mft_path_key(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    let path = match path.as_bytes() {
+        [drive, b':', ..] if drive.is_ascii_alphabetic() => &path[2..],
+        _ => &path[..],
+    };
+    path.trim_start_matches('/').to_lowercase()
+}
+
+/// Convert a file:/// URI to a Windows path
+fn file_uri_to_path(uri: &str) -> String {
+    let path = uri.strip_prefix("file:///").unwrap_or(uri);
+    let path = urlencoding::decode(path).map(|p| p.to_string()).unwrap_or_else(|_| path.to_string());
+    path.replace('/', "\\")
+}
+
+/// Convert a WebKit timestamp (microseconds since 1601-01-01) to UTC
+fn webkit_time(microseconds: Option<i64>) -> Option<DateTime<Utc>> {
+    microseconds
+        .filter(|&t| t > 0)
+        .and_then(|t| (t as u64).checked_mul(10))
+        .and_then(filetime_to_datetime)
+}
+
+/// Convert a PRTime timestamp (microseconds since 1970-01-01) to UTC
+fn prtime(microseconds: Option<i64>) -> Option<DateTime<Utc>> {
+    microseconds.filter(|&t| t > 0).and_then(DateTime::from_timestamp_micros)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sqlite::tests::build_tables;
+    use crate::sqlite::SqliteValue;
+
+    #[test]
+    fn test_timestamps() {
+        // 2021-01-01 00:00:00 UTC
+        assert_eq!(webkit_time(Some(13_253_932_800_000_000)).unwrap().timestamp(), 1_609_459_200);
+        assert_eq!(prtime(Some(1_609_459_200_000_000)).unwrap().timestamp(), 1_609_459_200);
+        assert!(webkit_time(Some(0)).is_none());
+        assert!(webkit_time(Some(i64::MAX)).is_none());
+    }
+
+    #[test]
+    fn test_path_helpers() {
+        assert_eq!(mft_path_key("C:\\Users\\Alice\\Downloads\\setup.exe"), "users/alice/downloads/setup.exe");
+        assert_eq!(mft_path_key("Users/alice/Downloads/setup.exe"), "users/alice/downloads/setup.exe");
+        assert_eq!(file_uri_to_path("file:///C:/Users/bob/Downloads/My%20File.zip"), "C:\\Users\\bob\\Downloads\\My File.zip");
+        assert!(is_history_database("Users/bob/AppData/Local/Google/Chrome/User Data/Default/History"));
+        assert!(is_history_database("places.sqlite"));
+        assert!(!is_history_database("History.txt"));
+    }
+
+    fn text(s: &str) -> SqliteValue {
+        SqliteValue::Text(s.to_string())
+    }
+
+    #[test]
+    fn test_parse_chromium() {
+        let data = build_tables(&[
+            (
+                "CREATE TABLE urls(id INTEGER PRIMARY KEY AUTOINCREMENT,url LONGVARCHAR,title LONGVARCHAR,visit_count INTEGER DEFAULT 0 NOT NULL,typed_count INTEGER DEFAULT 0 NOT NULL,last_visit_time INTEGER NOT NULL,hidden INTEGER DEFAULT 0 NOT NULL)",
+                vec![vec![SqliteValue::Null, text("https://example.com/"), text("Example"), SqliteValue::Integer(2), SqliteValue::Integer(1)]],
+            ),
+            (
+                "CREATE TABLE visits(id INTEGER PRIMARY KEY AUTOINCREMENT,url INTEGER NOT NULL,visit_time INTEGER NOT NULL,from_visit INTEGER,transition INTEGER DEFAULT 0 NOT NULL,segment_id INTEGER,visit_duration INTEGER DEFAULT 0 NOT NULL)",
+                vec![vec![
+                    SqliteValue::Null,
+                    SqliteValue::Integer(1),
+                    SqliteValue::Integer(13_253_932_800_000_000),
+                    SqliteValue::Integer(0),
+                    SqliteValue::Integer(0x3000_0001),
+                    SqliteValue::Integer(0),
+                    SqliteValue::Integer(2_500_000),
+                ]],
+            ),
+            (
+                "CREATE TABLE downloads (id INTEGER PRIMARY KEY,guid VARCHAR NOT NULL,current_path LONGVARCHAR NOT NULL,target_path LONGVARCHAR NOT NULL,start_time INTEGER NOT NULL,received_bytes INTEGER NOT NULL,total_bytes INTEGER NOT NULL,state INTEGER NOT NULL,danger_type INTEGER NOT NULL,interrupt_reason INTEGER NOT NULL,hash BLOB NOT NULL,end_time INTEGER NOT NULL,opened INTEGER NOT NULL,last_access_time INTEGER NOT NULL,transient INTEGER NOT NULL,referrer VARCHAR NOT NULL,site_url VARCHAR NOT NULL,tab_url VARCHAR NOT NULL,tab_referrer_url VARCHAR NOT NULL,http_method VARCHAR NOT NULL,by_ext_id VARCHAR NOT NULL,by_ext_name VARCHAR NOT NULL,etag VARCHAR NOT NULL,last_modified VARCHAR NOT NULL,mime_type VARCHAR(255) NOT NULL,original_mime_type VARCHAR(255) NOT NULL)",
+                vec![vec![
+                    SqliteValue::Null,
+                    text("G"),
+                    text("C:\\Users\\bob\\Downloads\\setup.exe"),
+                    text("C:\\Users\\bob\\Downloads\\setup.exe"),
+                    SqliteValue::Integer(13_253_932_800_000_000),
+                    SqliteValue::Integer(4096),
+                    SqliteValue::Integer(4096),
+                    SqliteValue::Integer(1),
+                    SqliteValue::Integer(4),
+                    SqliteValue::Integer(0),
+                    SqliteValue::Blob(Vec::new()),
+                    SqliteValue::Integer(13_253_932_805_000_000),
+                ]],
+            ),
+            (
+                "CREATE TABLE downloads_url_chains (id INTEGER NOT NULL,chain_index INTEGER NOT NULL,url LONGVARCHAR NOT NULL, PRIMARY KEY (id, chain_index) )",
+                vec![
+                    vec![SqliteValue::Integer(1), SqliteValue::Integer(0), text("https://example.com/get")],
+                    vec![SqliteValue::Integer(1), SqliteValue::Integer(1), text("https://cdn.example.com/setup.exe")],
+                ],
+            ),
+        ]);
+
+        assert!(is_history_data(&data));
+        let history = BrowserHistory::parse(&data).unwrap();
+        assert_eq!(history.browser, Browser::Chromium);
+        assert_eq!(history.visits.len(), 1);
+        let visit = &history.visits[0];
+        assert_eq!(visit.url, "https://example.com/");
+        assert_eq!(visit.title.as_deref(), Some("Example"));
+        assert_eq!(visit.transition.as_deref(), Some("Typed"));
+        assert_eq!(visit.visit_time.unwrap().timestamp(), 1_609_459_200);
+        assert_eq!(visit.duration, Some(2.5));
+
+        let download = &history.downloads[0];
+        assert_eq!(download.target_path, "C:\\Users\\bob\\Downloads\\setup.exe");
+        assert_eq!(download.url.as_deref(), Some("https://cdn.example.com/setup.exe"));
+        assert_eq!(download.state.as_deref(), Some("Complete"));
+        assert_eq!(download.danger_type.as_deref(), Some("Maybe Dangerous Content"));
+        assert_eq!(download.end_time.unwrap().timestamp(), 1_609_459_205);
+        // Missing trailing columns read as NULL
+        assert!(download.mime_type.is_none());
+    }
+
+    #[test]
+    fn test_parse_firefox() {
+        let data = build_tables(&[
+            (
+                "CREATE TABLE moz_places (id INTEGER PRIMARY KEY, url LONGVARCHAR, title LONGVARCHAR, rev_host LONGVARCHAR, visit_count INTEGER DEFAULT 0, hidden INTEGER DEFAULT 0 NOT NULL, typed INTEGER DEFAULT 0 NOT NULL)",
+                vec![vec![SqliteValue::Null, text("https://example.org/tool.zip"), SqliteValue::Null, text("gro.elpmaxe."), SqliteValue::Integer(1)]],
+            ),
+            (
+                "CREATE TABLE moz_historyvisits (id INTEGER PRIMARY KEY, from_visit INTEGER, place_id INTEGER, visit_date INTEGER, visit_type INTEGER, session INTEGER)",
+                vec![vec![SqliteValue::Null, SqliteValue::Integer(0), SqliteValue::Integer(1), SqliteValue::Integer(1_609_459_200_000_000), SqliteValue::Integer(7)]],
+            ),
+            (
+                "CREATE TABLE moz_anno_attributes (id INTEGER PRIMARY KEY, name VARCHAR(32) UNIQUE NOT NULL)",
+                vec![
+                    vec![SqliteValue::Null, text("downloads/destinationFileURI")],
+                    vec![SqliteValue::Null, text("downloads/metaData")],
+                ],
+            ),
+            (
+                "CREATE TABLE moz_annos (id INTEGER PRIMARY KEY, place_id INTEGER NOT NULL, anno_attribute_id INTEGER, content LONGVARCHAR, flags INTEGER DEFAULT 0, expiration INTEGER DEFAULT 0, type INTEGER DEFAULT 0, dateAdded INTEGER DEFAULT 0, lastModified INTEGER DEFAULT 0)",
+                vec![
+                    vec![SqliteValue::Null, SqliteValue::Integer(1), SqliteValue::Integer(1), text("file:///D:/Tools/tool%20v2.zip"), SqliteValue::Integer(0), SqliteValue::Integer(4), SqliteValue::Integer(3), SqliteValue::Integer(1_609_459_200_000_000)],
+                    vec![SqliteValue::Null, SqliteValue::Integer(1), SqliteValue::Integer(2), text(r#"{"state":1,"endTime":1609459260000,"fileSize":2048}"#)],
+                ],
+            ),
+        ]);
+
+        let history = BrowserHistory::parse(&data).unwrap();
+        assert_eq!(history.browser, Browser::Firefox);
+        assert_eq!(history.visits[0].transition.as_deref(), Some("Download"));
+        assert_eq!(history.visits[0].visit_time.unwrap().timestamp(), 1_609_459_200);
+        assert!(history.visits[0].title.is_none());
+
+        let download = &history.downloads[0];
+        assert_eq!(download.target_path, "D:\\Tools\\tool v2.zip");
+        assert_eq!(download.url.as_deref(), Some("https://example.org/tool.zip"));
+        assert_eq!(download.start_time.unwrap().timestamp(), 1_609_459_200);
+        assert_eq!(download.end_time.unwrap().timestamp(), 1_609_459_260);
+        assert_eq!(download.total_bytes, Some(2048));
+        assert_eq!(download.state.as_deref(), Some("Complete"));
+        assert_eq!(mft_path_key(&download.target_path), "tools/tool v2.zip");
+    }
+
+    #[test]
+    fn test_unrelated_database_named_history() {
+        // e.g. an application's own "History" SQLite file with no browser tables
+        let data = build_tables(&[("CREATE TABLE entries (id INTEGER PRIMARY KEY, command TEXT)", vec![vec![SqliteValue::Null, text("ls")]])]);
+        assert!(!is_history_data(&data));
+        assert!(!is_history_data(b"not a database"));
+        assert!(BrowserHistory::parse(&data).is_err());
+    }
+}
@@ -15,6 +15,22 @@ pub enum InputType {
     CustomDestinations,
     /// Registry hive (NTUSER.DAT, SYSTEM, SOFTWARE, etc.)
     Registry,
+    /// Windows Prefetch file (.pf)
+    Prefetch,
+    /// Windows Event Log (.evtx)
+    Evtx,
+    /// Recycle Bin index file ($I...)
+    RecycleBin,
+    /// Office document (.doc, .xls, .ppt, .msg, .docx, .xlsx, .pptx)
+    OfficeDocument,
+    /// Windows Timeline database (ActivitiesCache.db)
+    ActivitiesCache,
+    /// Browser history database (Chromium History, Firefox places.sqlite)
+    BrowserHistory,
+    /// System Resource Usage Monitor database (SRUDB.dat)
+    Srum,
+    /// Scheduled task file (System32\\Tasks XML, .job)
+    ScheduledTask,
     /// Windows drive letter (C:, D:, etc.)
     LiveSystem,
     /// ZIP archive container (.zip)
@@ -40,7 +56,7 @@ pub enum InputType {
 
 Features ultra-fast parallel processing, interactive TUI viewer, and multiple output formats.")]
 pub struct Args {
-    /// Input file path - supports MFT (.mft, .gz), containers (.zip, .e01, .dd, .raw, .img), LNK (.lnk), Jumplist (.automaticDestinations-ms, .customDestinations-ms), Registry (NTUSER.DAT), or drive letter (e.g., "C:", "mft.bin", "evidence.zip", "image.e01", "disk.dd")
+    /// Input file path - supports MFT (.mft, .gz), containers (.zip, .e01, .dd, .raw, .img), LNK (.lnk), Jumplist (.automaticDestinations-ms, .customDestinations-ms), Registry (NTUSER.DAT), Prefetch (.pf), Event Log (.evtx), Recycle Bin ($I...), Office documents (.doc, .xls, .ppt, .msg, .docx, .xlsx, .pptx), Windows Timeline (ActivitiesCache.db), browser history (History, places.sqlite), SRUM (SRUDB.dat), scheduled tasks (System32\\Tasks files, .xml, .job), or drive letter (e.g., "C:", "mft.bin", "evidence.zip", "image.e01", "disk.dd")
     pub input_file: Option<String>,
 
     /// Filter by filename and location (supports regex patterns)
@@ -59,13 +75,22 @@ pub struct Args {
     #[arg(long)]
     pub output: Option<String>,
 
-    /// Display timestamps in specified timezone (e.g., "UTC+8", "UTC-5", "UTC")  
+    /// Display timestamps in specified timezone (e.g., "UTC+8", "UTC-5", "UTC", "Pacific Standard Time",
+    /// or "auto" to use the timezone configured in the SYSTEM hive)
     #[arg(long, default_value = "UTC")]
     pub timezone: String,
 
     /// Password for encrypted ZIP archives (for forensic collections)
     #[arg(long)]
     pub password: Option<String>,
+
+    /// Write an autostart persistence report (JSON) for the registry hives processed
+    #[arg(long)]
+    pub persistence_report: Option<String>,
+
+    /// Extend the built-in EVTX event-ID catalogue with definitions from a JSON file
+    #[arg(long)]
+    pub event_catalog: Option<String>,
 }
 
 /// Parsed and validated CLI configuration
@@ -78,7 +103,10 @@ pub struct Config {
     pub before_date: Option<chrono::DateTime<chrono::Utc>>,
     pub output: Option<String>,
     pub timezone: chrono_tz::Tz,
+    pub timezone_auto: bool,
     pub password: Option<String>,
+    pub persistence_report: Option<String>,
+    pub event_catalog: crate::event_catalog::EventCatalog,
 }
 
 impl Config {
@@ -90,7 +118,13 @@ impl Config {
         let input_type = Self::detect_input_type(&input_file)?;
 
         // Parse timezone
-        let timezone = crate::datetime::parse_timezone(&args.timezone)?;
+        // "auto" starts from UTC and is resolved from the SYSTEM hive once parsed
+        let timezone_auto = args.timezone.eq_ignore_ascii_case("auto");
+        let timezone = if timezone_auto {
+            chrono_tz::Tz::UTC
+        } else {
+            crate::datetime::parse_timezone(&args.timezone)?
+        };
 
         // Compile regex filter if provided
         let filter_regex = if let Some(filter_pattern) = &args.filter {
@@ -118,6 +152,16 @@ impl Config {
             None
         };
 
+        // Load user event-ID definitions on top of the built-in catalogue
+        let event_catalog = match &args.event_catalog {
+            Some(path) => {
+                let catalog = crate::event_catalog::EventCatalog::with_file(std::path::Path::new(path))?;
+                eprintln!("📖 Loaded event catalogue ({} definitions)", catalog.len());
+                catalog
+            }
+            None => crate::event_catalog::EventCatalog::builtin(),
+        };
+
         Ok(Config {
             input_file,
             input_type,
@@ -126,7 +170,10 @@ impl Config {
             before_date,
             output: args.output,
             timezone,
+            timezone_auto,
             password: args.password,
+            persistence_report: args.persistence_report,
+            event_catalog,
         })
     }
     
@@ -141,6 +188,27 @@ impl Config {
         let path = std::path::Path::new(input_file);
         let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("").to_lowercase();
         
+        // $I files keep the original file's extension, so check the name first
+        if crate::recycle_bin::is_index_file(input_file) {
+            return Ok(InputType::RecycleBin);
+        }
+        
+        let filename = path.file_name().and_then(|n| n.to_str()).unwrap_or("").to_lowercase();
+        if filename == "activitiescache.db" {
+            return Ok(InputType::ActivitiesCache);
+        }
+        if crate::browser::is_history_database_file(path) {
+            return Ok(InputType::BrowserHistory);
+        }
+        if filename == "srudb.dat" {
+            return Ok(InputType::Srum);
+        }
+        if crate::scheduled_task::is_task_file(input_file)
+            || (extension == "xml" && crate::scheduled_task::is_task_xml_file(path))
+        {
+            return Ok(InputType::ScheduledTask);
+        }
+        
         match extension.as_str() {
             "lnk" => Ok(InputType::Lnk),
             "dat" => {
@@ -155,6 +223,10 @@ impl Config {
                     Ok(InputType::Mft)
                 }
             },
+            "hve" => Ok(InputType::Registry), // Amcache.hve and other exported hives
+            "pf" => Ok(InputType::Prefetch),
+            "evtx" => Ok(InputType::Evtx),
+            _ if crate::office::is_office_document(input_file) => Ok(InputType::OfficeDocument),
             "mft" | "bin" | "gz" => Ok(InputType::Mft),
             "zip" => Ok(InputType::ZipContainer),
             "e01" => Ok(InputType::E01Container),
@@ -177,7 +249,7 @@ impl Config {
                     Ok(InputType::AutomaticDestinations)
                 } else if filename.contains("customDestinations") {
                     Ok(InputType::CustomDestinations)
-                } else if filename.contains("ntuser") {
+                } else if filename.contains("ntuser") || is_bare_hive_name(&filename) {
                     Ok(InputType::Registry)
                 } else if filename.contains("$mft") {
                     Ok(InputType::Mft)
@@ -188,4 +260,9 @@ impl Config {
             }
         }
     }
-}
\ No newline at end of file
+}
+
+/// Check for hive files exported without an extension (SYSTEM, SOFTWARE, SAM, SECURITY)
+pub fn is_bare_hive_name(filename: &str) -> bool {
+    matches!(filename.to_lowercase().as_str(), "system" | "software" | "sam" | "security")
+}
@@ -33,6 +33,8 @@ pub struct ExtractedArtifact {
 #[derive(Debug)]
 pub struct ExtractedArtifacts {
     pub artifacts: Vec<ExtractedArtifact>,
+    /// Every entry name in the container, for pairing artifacts with files that aren't extracted
+    pub entry_names: Vec<String>,
 }
 
 /// Container extractor for various forensic archive formats
@@ -276,6 +278,11 @@ impl ContainerExtractor {
             return Some(InputType::Mft);
         }
         
+        // Recycle Bin index files keep the original extension, so match them before anything else
+        if lower_name.contains("$recycle.bin") && crate::recycle_bin::is_index_file(filename) {
+            return Some(InputType::RecycleBin);
+        }
+        
         // LNK files (exclude recycle bin metadata files)
         if lower_name.ends_with(".lnk") {
             // Skip recycle bin metadata files that have .lnk extensions but aren't real LNK files
@@ -296,10 +303,46 @@ impl ContainerExtractor {
             return Some(InputType::CustomDestinations);
         }
         
+        // Prefetch files (checked before registry names, which match any System32 path)
+        if lower_name.ends_with(".pf") {
+            return Some(InputType::Prefetch);
+        }
+        
+        // Event logs (checked before registry names, e.g. System.evtx)
+        if lower_name.ends_with(".evtx") {
+            return Some(InputType::Evtx);
+        }
+        
+        // Windows Timeline database (checked before registry names, which match user names like "Samuel")
+        if lower_name.ends_with("activitiescache.db") {
+            return Some(InputType::ActivitiesCache);
+        }
+        
+        // Browser history databases (checked before registry names, which match user names like "Samuel")
+        if crate::browser::is_history_database(&lower_name) {
+            return Some(InputType::BrowserHistory);
+        }
+        
+        // SRUM database (checked before registry names, which match Windows\System32\sru)
+        if lower_name.ends_with("srudb.dat") {
+            return Some(InputType::Srum);
+        }
+        
+        // Scheduled tasks (checked before registry names, which match any System32 path)
+        if crate::scheduled_task::is_task_file(&lower_name) {
+            return Some(InputType::ScheduledTask);
+        }
+        
+        // Office documents (checked before registry names, e.g. "System Design.docx")
+        if crate::office::is_office_document(&lower_name) {
+            return Some(InputType::OfficeDocument);
+        }
+        
         // Registry files
         if lower_name.contains("ntuser") || lower_name.contains("system") || 
            lower_name.contains("software") || lower_name.contains("sam") || 
-           lower_name.contains("security") || lower_name.ends_with(".dat") {
+           lower_name.contains("security") || lower_name.contains("amcache") ||
+           lower_name.ends_with(".dat") || lower_name.ends_with(".hve") {
             return Some(InputType::Registry);
         }
         
@@ -313,6 +356,7 @@ impl ContainerExtractor {
         let file = File::open(path)?;
         let mut archive = ZipArchive::new(file)?;
         let mut artifacts = Vec::new();
+        let mut entry_names = Vec::new();
         
         // Pre-allocate for better performance
         let mut mft_found = false;
@@ -334,6 +378,7 @@ impl ContainerExtractor {
             let filename = zip_file.name().to_string();
             let file_size = zip_file.size();
             eprintln!("🔍 Found file in ZIP: {} ({} bytes)", filename, file_size);
+            entry_names.push(filename.clone());
 
             // Detect artifact type with early detection for large MFT files
             if let Some(artifact_type) = Self::detect_artifact_type(&filename) {
@@ -352,6 +397,12 @@ impl ContainerExtractor {
 
                 eprintln!("📊 Extracted {} bytes from {} (in-memory)", data.len(), filename);
 
+                // Any file can be called History, so confirm the browser tables are there
+                if artifact_type == InputType::BrowserHistory && !crate::browser::is_history_data(&data) {
+                    eprintln!("⚠️  Skipping {}: not a browser history database", filename);
+                    continue;
+                }
+
                 artifacts.push(ExtractedArtifact {
                     name: filename,
                     artifact_type,
@@ -371,7 +422,7 @@ impl ContainerExtractor {
         eprintln!("🎉 Successfully extracted {} artifacts from ZIP{}", 
                  artifacts.len(),
                  if mft_found { " (optimized in-memory processing)" } else { "" });
-        Ok(ExtractedArtifacts { artifacts })
+        Ok(ExtractedArtifacts { artifacts, entry_names })
     }
     
     /// Extract large files using optimized in-memory processing
@@ -447,7 +498,7 @@ impl ContainerExtractor {
             data: mft_data,
         }];
 
-        Ok(ExtractedArtifacts { artifacts })
+        Ok(ExtractedArtifacts { artifacts, entry_names: vec!["$MFT".to_string()] })
     }
 }
 
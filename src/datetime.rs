@@ -5,7 +5,8 @@ use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, Offset, TimeZone, Tim
 use chrono_tz::Tz;
 
 /// Parse timezone string into a Tz object
-/// Accepts "UTC" or UTC offset notation like "UTC+8", "UTC-5"
+/// Accepts "UTC", UTC offset notation like "UTC+8", "UTC-5", or a Windows time zone name
+/// such as "Pacific Standard Time" (as stored in the SYSTEM hive)
 pub fn parse_timezone(timezone_str: &str) -> Result<Tz> {
     match timezone_str {
         "UTC" => Ok(Tz::UTC),
@@ -48,13 +49,58 @@ pub fn parse_timezone(timezone_str: &str) -> Result<Tz> {
                 )))
             }
         }
-        _ => Err(Error::InvalidInput(format!(
-            "Invalid timezone '{}'. Use 'UTC' or UTC offset notation like 'UTC+8'",
+        _ => windows_timezone_to_tz(timezone_str).ok_or_else(|| Error::InvalidInput(format!(
+            "Invalid timezone '{}'. Use 'UTC', UTC offset notation like 'UTC+8', or a Windows time zone name",
             timezone_str
         )))
     }
 }
 
+/// Map a Windows time zone name (TimeZoneKeyName / StandardName) to a timezone
+pub fn windows_timezone_to_tz(name: &str) -> Option<Tz> {
+    let tz = match name.trim().to_lowercase().as_str() {
+        "utc" | "coordinated universal time" => Tz::UTC,
+        "gmt standard time" => Tz::Europe__London,
+        "greenwich standard time" => Tz::Atlantic__Reykjavik,
+        "w. europe standard time" => Tz::Europe__Berlin,
+        "romance standard time" => Tz::Europe__Paris,
+        "central europe standard time" => Tz::Europe__Budapest,
+        "central european standard time" => Tz::Europe__Warsaw,
+        "e. europe standard time" => Tz::Europe__Chisinau,
+        "fle standard time" => Tz::Europe__Kiev,
+        "gtb standard time" => Tz::Europe__Bucharest,
+        "israel standard time" => Tz::Asia__Jerusalem,
+        "russian standard time" => Tz::Europe__Moscow,
+        "arabian standard time" => Tz::Asia__Dubai,
+        "pakistan standard time" => Tz::Asia__Karachi,
+        "india standard time" => Tz::Asia__Kolkata,
+        "bangladesh standard time" => Tz::Asia__Dhaka,
+        "se asia standard time" => Tz::Asia__Bangkok,
+        "china standard time" => Tz::Asia__Shanghai,
+        "singapore standard time" => Tz::Asia__Singapore,
+        "taipei standard time" => Tz::Asia__Taipei,
+        "w. australia standard time" => Tz::Australia__Perth,
+        "tokyo standard time" => Tz::Asia__Tokyo,
+        "korea standard time" => Tz::Asia__Seoul,
+        "aus eastern standard time" => Tz::Australia__Sydney,
+        "e. australia standard time" => Tz::Australia__Brisbane,
+        "new zealand standard time" => Tz::Pacific__Auckland,
+        "hawaiian standard time" => Tz::Pacific__Honolulu,
+        "alaskan standard time" => Tz::America__Anchorage,
+        "pacific standard time" => Tz::America__Los_Angeles,
+        "us mountain standard time" => Tz::America__Phoenix,
+        "mountain standard time" => Tz::America__Denver,
+        "central standard time" => Tz::America__Chicago,
+        "eastern standard time" => Tz::America__New_York,
+        "atlantic standard time" => Tz::America__Halifax,
+        "e. south america standard time" => Tz::America__Sao_Paulo,
+        "argentina standard time" => Tz::America__Argentina__Buenos_Aires,
+        "south africa standard time" => Tz::Africa__Johannesburg,
+        _ => return None,
+    };
+    Some(tz)
+}
+
 /// Convert UTC datetime to specified timezone
 pub fn convert_to_timezone(utc_dt: DateTime<Utc>, tz: Tz) -> DateTime<Tz> {
     utc_dt.with_timezone(&tz)
@@ -215,6 +261,13 @@ mod tests {
         assert!(parse_timezone("UTC+25").is_err()); // Invalid offset
     }
 
+    #[test]
+    fn test_parse_windows_timezone_name() {
+        assert_eq!(parse_timezone("Pacific Standard Time").unwrap(), Tz::America__Los_Angeles);
+        assert_eq!(parse_timezone("china standard time").unwrap(), Tz::Asia__Shanghai);
+        assert!(parse_timezone("Martian Standard Time").is_err());
+    }
+
     #[test]
     fn test_parse_date_filter() {
         // Test date only format
@@ -0,0 +1,89 @@
+//! Distributed Link Tracking droid decoding
+//!
+//! Supports parsing of:
+//! - Version 1 (time-based) UUIDs used as file object IDs in LNK tracker blocks and DestList entries
+//! - UUID timestamp (object ID creation time), clock sequence and node (MAC address)
+
+use crate::registry::format_guid;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// 100-nanosecond intervals between the UUID epoch (1582-10-15) and the Unix epoch
+const UUID_EPOCH_DIFF: u64 = 0x01B2_1DD2_1381_4000;
+
+/// Decoded version 1 droid GUID
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ObjectId {
+    /// GUID as {XXXXXXXX-XXXX-XXXX-XXXX-XXXXXXXXXXXX}
+    pub guid: String,
+    /// Time the object ID was generated
+    pub created: Option<DateTime<Utc>>,
+    /// Clock sequence
+    pub clock_sequence: u16,
+    /// Node field: MAC address of the machine that generated the object ID
+    pub mac_address: String,
+}
+
+impl ObjectId {
+    /// Decode a 16-byte droid GUID; `None` unless it is a version 1 UUID
+    pub fn parse(bytes: &[u8]) -> Option<Self> {
+        let bytes = bytes.get(..16)?;
+        let time_low = u32::from_le_bytes(bytes[0..4].try_into().ok()?) as u64;
+        let time_mid = u16::from_le_bytes(bytes[4..6].try_into().ok()?) as u64;
+        let time_hi_and_version = u16::from_le_bytes(bytes[6..8].try_into().ok()?);
+        if time_hi_and_version >> 12 != 1 {
+            return None;
+        }
+
+        let ticks = ((time_hi_and_version as u64 & 0x0FFF) << 48) | (time_mid << 32) | time_low;
+        let created = ticks.checked_sub(UUID_EPOCH_DIFF).and_then(|unix| {
+            DateTime::from_timestamp((unix / 10_000_000) as i64, ((unix % 10_000_000) * 100) as u32)
+        });
+
+        Some(ObjectId {
+            guid: format_guid(bytes),
+            created,
+            clock_sequence: ((bytes[8] as u16 & 0x3F) << 8) | bytes[9] as u16,
+            mac_address: bytes[10..16].iter().map(|b| format!("{:02x}", b)).collect::<Vec<_>>().join(":"),
+        })
+    }
+
+    /// Labelled values for event metadata
+    pub fn fields(&self) -> Vec<(String, String)> {
+        let mut fields = vec![("MAC Address".to_string(), self.mac_address.clone())];
+        if let Some(created) = self.created {
+            fields.push(("Object ID Created".to_string(), created.to_rfc3339()));
+        }
+        fields.push(("Clock Sequence".to_string(), self.clock_sequence.to_string()));
+        fields
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Datelike, Timelike};
+
+    #[test]
+    fn test_parse_version1_droid() {
+        // {E2F9C8B4-5D3A-11EA-8A6C-0800275C9F1E}: generated 2020-03-03 10:36:48 on 08:00:27:5c:9f:1e
+        let bytes = [
+            0xB4, 0xC8, 0xF9, 0xE2, 0x3A, 0x5D, 0xEA, 0x11, 0x8A, 0x6C, 0x08, 0x00, 0x27, 0x5C, 0x9F, 0x1E,
+        ];
+        let object_id = ObjectId::parse(&bytes).unwrap();
+        assert_eq!(object_id.guid, "{E2F9C8B4-5D3A-11EA-8A6C-0800275C9F1E}");
+        assert_eq!(object_id.mac_address, "08:00:27:5c:9f:1e");
+        assert_eq!(object_id.clock_sequence, 0x0A6C);
+        let created = object_id.created.unwrap();
+        assert_eq!((created.year(), created.month(), created.day()), (2020, 3, 3));
+        assert_eq!((created.hour(), created.minute(), created.second()), (10, 36, 48));
+    }
+
+    #[test]
+    fn test_rejects_other_versions() {
+        let mut bytes = [0u8; 16];
+        bytes[7] = 0x40; // Version 4 (random)
+        assert!(ObjectId::parse(&bytes).is_none());
+        assert!(ObjectId::parse(&bytes[..8]).is_none());
+    }
+}
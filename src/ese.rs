@@ -0,0 +1,875 @@
+//! Read-only Extensible Storage Engine (ESE / JET Blue) database reader
+//!
+//! Supports parsing of:
+//! - The file header (signature, page size) and page headers for small (2-8 KiB) and large (16/32 KiB) pages
+//! - Page tags and b-tree nodes with common key compression, following branch pages down to leaves
+//! - The MSysObjects catalog (tables, columns and long value trees)
+//! - Record format fixed, variable and tagged columns, including multi-values and 7-bit compressed values
+//! - Separated long values stored in a table's long value tree
+//!
+//! Pages are read as stored in the database file. Transaction logs are not replayed, so a dirty
+//! database yields its last flushed state. Xpress-compressed values and template tables are not decoded.
+
+use crate::error::{Error, Result};
+use crate::registry::{format_guid, read_u16, read_u32};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// File header signature
+const ESE_SIGNATURE: u32 = 0x89AB_CDEF;
+/// File header offset of the page size
+const PAGE_SIZE_OFFSET: usize = 0xEC;
+/// Father data page of the MSysObjects catalog
+const CATALOG_PAGE: u32 = 4;
+/// Maximum b-tree depth followed before the tree is treated as corrupt
+const MAX_TREE_DEPTH: usize = 64;
+
+/// Page flags
+const PAGE_FLAG_LEAF: u32 = 0x0002;
+const PAGE_FLAG_EMPTY: u32 = 0x0008;
+const PAGE_FLAG_SPACE_TREE: u32 = 0x0020;
+
+/// Page tag flags
+const TAG_FLAG_DELETED: u16 = 0x2;
+const TAG_FLAG_COMMON_KEY: u16 = 0x4;
+
+/// Tagged value flags
+const TAGGED_FLAG_COMPRESSED: u8 = 0x02;
+const TAGGED_FLAG_LONG_VALUE: u8 = 0x04;
+const TAGGED_FLAG_MULTI_VALUE: u8 = 0x08;
+
+/// Catalog object types
+const CATALOG_TABLE: i64 = 1;
+const CATALOG_COLUMN: i64 = 2;
+const CATALOG_LONG_VALUE: i64 = 4;
+
+/// Column types (JET_coltyp)
+pub const COLUMN_BIT: u32 = 1;
+pub const COLUMN_UNSIGNED_BYTE: u32 = 2;
+pub const COLUMN_SHORT: u32 = 3;
+pub const COLUMN_LONG: u32 = 4;
+pub const COLUMN_CURRENCY: u32 = 5;
+pub const COLUMN_IEEE_SINGLE: u32 = 6;
+pub const COLUMN_IEEE_DOUBLE: u32 = 7;
+pub const COLUMN_DATE_TIME: u32 = 8;
+pub const COLUMN_BINARY: u32 = 9;
+pub const COLUMN_TEXT: u32 = 10;
+pub const COLUMN_LONG_BINARY: u32 = 11;
+pub const COLUMN_LONG_TEXT: u32 = 12;
+pub const COLUMN_UNSIGNED_LONG: u32 = 14;
+pub const COLUMN_LONG_LONG: u32 = 15;
+pub const COLUMN_GUID: u32 = 16;
+pub const COLUMN_UNSIGNED_SHORT: u32 = 17;
+
+/// Unicode code page of text columns
+const CODEPAGE_UNICODE: u32 = 1200;
+
+/// Value stored in a record column
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum EseValue {
+    Null,
+    Integer(i64),
+    Real(f64),
+    Text(String),
+    Binary(Vec<u8>),
+}
+
+impl EseValue {
+    /// Integer value (real values are truncated)
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            EseValue::Integer(v) => Some(*v),
+            EseValue::Real(v) => Some(*v as i64),
+            _ => None,
+        }
+    }
+
+    /// Floating point value (used by DateTime columns)
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            EseValue::Real(v) => Some(*v),
+            EseValue::Integer(v) => Some(*v as f64),
+            _ => None,
+        }
+    }
+
+    /// Text value
+    pub fn as_text(&self) -> Option<String> {
+        match self {
+            EseValue::Text(s) => Some(s.clone()),
+            EseValue::Integer(v) => Some(v.to_string()),
+            EseValue::Real(v) => Some(v.to_string()),
+            EseValue::Binary(_) | EseValue::Null => None,
+        }
+    }
+
+    /// Binary value
+    pub fn as_blob(&self) -> Option<&[u8]> {
+        match self {
+            EseValue::Binary(b) => Some(b),
+            _ => None,
+        }
+    }
+}
+
+/// Column definition from the catalog
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Column {
+    /// Column ID (1-127 fixed, 128-255 variable, 256+ tagged)
+    pub id: u32,
+    /// Column name
+    pub name: String,
+    /// Column type (JET_coltyp)
+    pub column_type: u32,
+    /// Declared size of fixed columns
+    pub size: u32,
+    /// Code page of text columns
+    pub codepage: u32,
+}
+
+/// Table definition from the catalog
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TableSchema {
+    /// Table name
+    pub name: String,
+    /// Catalog object ID
+    pub object_id: u32,
+    /// Father data page of the table b-tree
+    pub root_page: u32,
+    /// Father data page of the long value b-tree
+    pub long_value_page: Option<u32>,
+    /// Columns ordered by ID
+    pub columns: Vec<Column>,
+}
+
+/// Rows read from one table
+#[derive(Debug, Clone)]
+pub struct Table {
+    /// Table name
+    pub name: String,
+    /// Column names
+    pub columns: Vec<String>,
+    /// Row values in column order
+    pub rows: Vec<Vec<EseValue>>,
+}
+
+/// One row with access by column name
+#[derive(Debug, Clone, Copy)]
+pub struct Row<'a> {
+    columns: &'a [String],
+    values: &'a [EseValue],
+}
+
+impl Table {
+    /// Iterate over rows with named column access
+    pub fn records(&self) -> impl Iterator<Item = Row<'_>> {
+        self.rows.iter().map(|values| Row { columns: &self.columns, values })
+    }
+}
+
+impl<'a> Row<'a> {
+    /// Value of a column (case-insensitive)
+    pub fn get(&self, column: &str) -> Option<&'a EseValue> {
+        let index = self.columns.iter().position(|c| c.eq_ignore_ascii_case(column))?;
+        self.values.get(index)
+    }
+
+    /// Integer value of a column
+    pub fn integer(&self, column: &str) -> Option<i64> {
+        self.get(column).and_then(EseValue::as_i64)
+    }
+
+    /// Floating point value of a column
+    pub fn real(&self, column: &str) -> Option<f64> {
+        self.get(column).and_then(EseValue::as_f64)
+    }
+
+    /// Non-empty text value of a column
+    pub fn text(&self, column: &str) -> Option<String> {
+        self.get(column).and_then(EseValue::as_text).filter(|s| !s.is_empty())
+    }
+
+    /// Binary value of a column
+    pub fn blob(&self, column: &str) -> Option<&'a [u8]> {
+        self.get(column).and_then(EseValue::as_blob)
+    }
+}
+
+/// ESE database opened from an in-memory image
+pub struct EseDatabase<'a> {
+    data: &'a [u8],
+    page_size: usize,
+    small_pages: bool,
+    tables: Vec<TableSchema>,
+}
+
+impl<'a> EseDatabase<'a> {
+    /// Open a database image and read its catalog
+    pub fn open(data: &'a [u8]) -> Result<Self> {
+        if read_u32(data, 4) != Some(ESE_SIGNATURE) {
+            return Err(Error::ParseError("Not an ESE database".to_string()));
+        }
+        let page_size = read_u32(data, PAGE_SIZE_OFFSET).unwrap_or(0) as usize;
+        if !(2048..=32768).contains(&page_size) || !page_size.is_power_of_two() {
+            return Err(Error::ParseError(format!("Invalid ESE page size {}", page_size)));
+        }
+
+        let mut database = EseDatabase { data, page_size, small_pages: page_size <= 8192, tables: Vec::new() };
+        database.tables = database.read_catalog()?;
+        Ok(database)
+    }
+
+    /// Tables defined in the catalog
+    pub fn tables(&self) -> &[TableSchema] {
+        &self.tables
+    }
+
+    /// Definition of a table (case-insensitive)
+    pub fn table_schema(&self, name: &str) -> Option<&TableSchema> {
+        self.tables.iter().find(|t| t.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Read every row of a table, resolving separated long values
+    pub fn read_table(&self, name: &str) -> Result<Table> {
+        let schema = self
+            .table_schema(name)
+            .ok_or_else(|| Error::InvalidInput(format!("ESE table {} not found", name)))?;
+        let long_values = match schema.long_value_page {
+            Some(page) => self.long_values(page)?,
+            None => HashMap::new(),
+        };
+        let rows = self
+            .leaf_nodes(schema.root_page)?
+            .into_iter()
+            .map(|(_, record)| self.decode_record(record, &schema.columns, &long_values))
+            .collect();
+
+        Ok(Table {
+            name: schema.name.clone(),
+            columns: schema.columns.iter().map(|c| c.name.clone()).collect(),
+            rows,
+        })
+    }
+
+    /// Read table, column and long value definitions from MSysObjects
+    fn read_catalog(&self) -> Result<Vec<TableSchema>> {
+        let columns = catalog_columns();
+        let catalog = Table {
+            name: "MSysObjects".to_string(),
+            columns: columns.iter().map(|c| c.name.clone()).collect(),
+            rows: self
+                .leaf_nodes(CATALOG_PAGE)?
+                .into_iter()
+                .map(|(_, record)| self.decode_record(record, &columns, &HashMap::new()))
+                .collect(),
+        };
+        let number = |row: &Row, column: &str| row.integer(column).unwrap_or(0);
+
+        let mut tables: Vec<TableSchema> = catalog
+            .records()
+            .filter(|row| number(row, "Type") == CATALOG_TABLE)
+            .map(|row| TableSchema {
+                name: row.text("Name").unwrap_or_default(),
+                object_id: number(&row, "Id") as u32,
+                root_page: number(&row, "ColtypOrPgnoFDP") as u32,
+                long_value_page: None,
+                columns: Vec::new(),
+            })
+            .collect();
+
+        for row in catalog.records() {
+            let Some(table) = tables.iter_mut().find(|t| t.object_id as i64 == number(&row, "ObjidTable")) else {
+                continue;
+            };
+            match number(&row, "Type") {
+                CATALOG_COLUMN => table.columns.push(Column {
+                    id: number(&row, "Id") as u32,
+                    name: row.text("Name").unwrap_or_default(),
+                    column_type: number(&row, "ColtypOrPgnoFDP") as u32,
+                    size: number(&row, "SpaceUsage") as u32,
+                    codepage: number(&row, "PagesOrLocale") as u32,
+                }),
+                CATALOG_LONG_VALUE => table.long_value_page = Some(number(&row, "ColtypOrPgnoFDP") as u32),
+                _ => {}
+            }
+        }
+        for table in &mut tables {
+            table.columns.sort_by_key(|c| c.id);
+        }
+        Ok(tables)
+    }
+
+    /// Page by number; page 0 follows the file header and its shadow copy
+    fn page(&self, number: u32) -> Option<&'a [u8]> {
+        let start = (number as usize + 1).checked_mul(self.page_size)?;
+        self.data.get(start..start + self.page_size)
+    }
+
+    /// Tag flags and values of a page; tag 0 holds the page's common key prefix
+    fn tags(&self, page: &'a [u8]) -> Vec<(u16, &'a [u8])> {
+        let header_size = if self.small_pages { 40 } else { 80 };
+        let count = read_u16(page, 34).unwrap_or(0) as usize;
+        (0..count)
+            .filter_map(|i| {
+                let tag = self.page_size.checked_sub(4 * (i + 1))?;
+                let (size, offset) = (read_u16(page, tag)?, read_u16(page, tag + 2)?);
+                if self.small_pages {
+                    let start = header_size + (offset & 0x1FFF) as usize;
+                    Some((offset >> 13, page.get(start..start + (size & 0x1FFF) as usize)?))
+                } else {
+                    // Large pages keep the tag flags in the top bits of the value's first word
+                    let start = header_size + (offset & 0x7FFF) as usize;
+                    let value = page.get(start..start + (size & 0x7FFF) as usize)?;
+                    Some((read_u16(value, 0).map_or(0, |v| v >> 13), value))
+                }
+            })
+            .collect()
+    }
+
+    /// Key and data of every live leaf node in a b-tree, in key order
+    fn leaf_nodes(&self, root: u32) -> Result<Vec<(Vec<u8>, &'a [u8])>> {
+        let mut nodes = Vec::new();
+        self.walk(root, 0, &mut HashSet::new(), &mut nodes)?;
+        Ok(nodes)
+    }
+
+    fn walk(&self, number: u32, depth: usize, visited: &mut HashSet<u32>, nodes: &mut Vec<(Vec<u8>, &'a [u8])>) -> Result<()> {
+        if depth > MAX_TREE_DEPTH {
+            return Err(Error::ParseError("ESE b-tree is too deep".to_string()));
+        }
+        if !visited.insert(number) {
+            return Ok(());
+        }
+        let page = self
+            .page(number)
+            .ok_or_else(|| Error::ParseError(format!("ESE page {} is outside the file", number)))?;
+        let flags = read_u32(page, 36).unwrap_or(0);
+        if flags & (PAGE_FLAG_EMPTY | PAGE_FLAG_SPACE_TREE) != 0 {
+            return Ok(());
+        }
+
+        let tags = self.tags(page);
+        let common_key = tags.first().map_or(&[][..], |(_, value)| value);
+        for &(tag_flags, value) in tags.iter().skip(1) {
+            if tag_flags & TAG_FLAG_DELETED != 0 {
+                continue;
+            }
+            let Some((key, data)) = node(value, tag_flags, common_key) else {
+                continue;
+            };
+            if flags & PAGE_FLAG_LEAF != 0 {
+                nodes.push((key, data));
+            } else if let Some(child) = read_u32(data, 0) {
+                self.walk(child, depth + 1, visited, nodes)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Reassemble long values by ID from a long value b-tree
+    fn long_values(&self, root: u32) -> Result<HashMap<u32, Vec<u8>>> {
+        let mut sizes = HashMap::new();
+        let mut chunks: HashMap<u32, Vec<(usize, &[u8])>> = HashMap::new();
+        for (key, data) in self.leaf_nodes(root)? {
+            // Keys are big-endian: the ID alone for the size header, ID and offset for each chunk
+            let id = u32::from_be_bytes(match key.get(..4).and_then(|k| k.try_into().ok()) {
+                Some(id) => id,
+                None => continue,
+            });
+            match key.len() {
+                4 => {
+                    sizes.insert(id, read_u32(data, 4).unwrap_or(0) as usize);
+                }
+                8 => {
+                    let offset = u32::from_be_bytes([key[4], key[5], key[6], key[7]]) as usize;
+                    chunks.entry(id).or_default().push((offset, data));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(chunks
+            .into_iter()
+            .map(|(id, mut parts)| {
+                parts.sort_by_key(|(offset, _)| *offset);
+                let size = sizes.get(&id).copied();
+                let mut value = Vec::new();
+                for (offset, part) in parts {
+                    // Chunk offsets are only trusted within the root's declared size, or contiguously without one
+                    if size.map_or(offset > value.len(), |size| offset >= size) {
+                        continue;
+                    }
+                    value.resize(value.len().max(offset), 0);
+                    value.truncate(offset);
+                    value.extend_from_slice(part);
+                }
+                if let Some(size) = size {
+                    value.truncate(size);
+                }
+                (id, value)
+            })
+            .collect())
+    }
+
+    /// Decode a record into values ordered like `columns`
+    fn decode_record(&self, data: &[u8], columns: &[Column], long_values: &HashMap<u32, Vec<u8>>) -> Vec<EseValue> {
+        let mut values = vec![EseValue::Null; columns.len()];
+        let (Some(&last_fixed), Some(&last_variable), Some(variable_offset)) = (data.first(), data.get(1), read_u16(data, 2)) else {
+            return values;
+        };
+        let (last_fixed, last_variable, variable_offset) = (last_fixed as u32, last_variable as u32, variable_offset as usize);
+        let position = |id: u32| columns.iter().position(|c| c.id == id);
+
+        // Fixed columns in ID order, followed by a null bitmap just before the variable offsets
+        let bitmap = variable_offset.saturating_sub(last_fixed.div_ceil(8) as usize);
+        let mut offset = 4;
+        for column in columns.iter().filter(|c| c.id <= last_fixed) {
+            let size = fixed_size(column);
+            let bit = (column.id - 1) as usize;
+            let null = data.get(bitmap + bit / 8).is_none_or(|byte| byte & (1 << (bit % 8)) != 0);
+            if let (false, Some(bytes), Some(index)) = (null, data.get(offset..offset + size), position(column.id)) {
+                values[index] = convert(column, bytes);
+            }
+            offset += size;
+        }
+
+        // Variable columns: end offsets relative to the data area, high bit marking NULL
+        let count = last_variable.saturating_sub(127) as usize;
+        let area = variable_offset + count * 2;
+        let mut start = 0;
+        for i in 0..count {
+            let Some(raw) = read_u16(data, variable_offset + i * 2) else { break };
+            let end = (raw & 0x7FFF) as usize;
+            if raw & 0x8000 == 0 {
+                if let (Some(bytes), Some(index)) = (data.get(area + start..area + end), position(128 + i as u32)) {
+                    values[index] = convert(&columns[index], bytes);
+                }
+            }
+            start = end;
+        }
+
+        // Tagged columns: (ID, offset) pairs, the first offset also giving the array size
+        let tagged = data.get(area + start..).unwrap_or(&[]);
+        let offset_mask = if self.small_pages { 0x3FFF } else { 0x7FFF };
+        let tag_count = read_u16(tagged, 2).map_or(0, |o| (o & offset_mask) as usize / 4);
+        for i in 0..tag_count {
+            let (Some(id), Some(raw)) = (read_u16(tagged, i * 4), read_u16(tagged, i * 4 + 2)) else { break };
+            let begin = (raw & offset_mask) as usize;
+            let end = if i + 1 < tag_count {
+                read_u16(tagged, (i + 1) * 4 + 2).map_or(tagged.len(), |o| (o & offset_mask) as usize)
+            } else {
+                tagged.len()
+            };
+            let (Some(mut bytes), Some(index)) = (tagged.get(begin..end), position(id as u32)) else { continue };
+            let mut flags = 0;
+            if !self.small_pages || raw & 0x4000 != 0 {
+                let Some((&first, rest)) = bytes.split_first() else { continue };
+                flags = first;
+                bytes = rest;
+            }
+            if let Some(bytes) = tagged_value(bytes, flags, long_values) {
+                values[index] = convert(&columns[index], &bytes);
+            }
+        }
+        values
+    }
+}
+
+/// Split a node into its full key and data
+fn node<'b>(value: &'b [u8], flags: u16, common_key: &[u8]) -> Option<(Vec<u8>, &'b [u8])> {
+    let mut key = Vec::new();
+    let mut pos = 0;
+    if flags & TAG_FLAG_COMMON_KEY != 0 {
+        let common = (read_u16(value, 0)? & 0x1FFF) as usize;
+        key.extend_from_slice(common_key.get(..common)?);
+        pos = 2;
+    }
+    let local = (read_u16(value, pos)? & 0x1FFF) as usize;
+    key.extend_from_slice(value.get(pos + 2..pos + 2 + local)?);
+    Some((key, value.get(pos + 2 + local..)?))
+}
+
+/// Resolve a tagged value's first multi-value, separated long value or compression
+fn tagged_value(bytes: &[u8], flags: u8, long_values: &HashMap<u32, Vec<u8>>) -> Option<Vec<u8>> {
+    let mut bytes = bytes;
+    if flags & TAGGED_FLAG_MULTI_VALUE != 0 {
+        let first = (read_u16(bytes, 0)? & 0x7FFF) as usize;
+        let end = if first > 2 { (read_u16(bytes, 2)? & 0x7FFF) as usize } else { bytes.len() };
+        bytes = bytes.get(first..end)?;
+    }
+    if flags & TAGGED_FLAG_LONG_VALUE != 0 {
+        return long_values.get(&read_u32(bytes, 0)?).cloned();
+    }
+    if flags & TAGGED_FLAG_COMPRESSED != 0 {
+        return decompress(bytes);
+    }
+    Some(bytes.to_vec())
+}
+
+/// Decompress a 7-bit ASCII or 7-bit Unicode value; Xpress values are not supported
+fn decompress(data: &[u8]) -> Option<Vec<u8>> {
+    let (&header, payload) = data.split_first()?;
+    let unicode = match header >> 3 {
+        1 => false,
+        2 => true,
+        _ => return None,
+    };
+    // The low three bits give the number of bits used in the last byte, minus one
+    let bits = payload.len().checked_sub(1)? * 8 + (header & 0x07) as usize + 1;
+    let mut output = Vec::with_capacity(bits / 7 * 2);
+    let (mut buffer, mut buffered) = (0u32, 0);
+    for &byte in payload {
+        buffer |= (byte as u32) << buffered;
+        buffered += 8;
+        while buffered >= 7 && output.len() < (bits / 7) * if unicode { 2 } else { 1 } {
+            let character = (buffer & 0x7F) as u8;
+            output.push(character);
+            if unicode {
+                output.push(0);
+            }
+            buffer >>= 7;
+            buffered -= 7;
+        }
+    }
+    Some(output)
+}
+
+/// Bytes occupied by a fixed column
+fn fixed_size(column: &Column) -> usize {
+    match column.column_type {
+        COLUMN_BIT | COLUMN_UNSIGNED_BYTE => 1,
+        COLUMN_SHORT | COLUMN_UNSIGNED_SHORT => 2,
+        COLUMN_LONG | COLUMN_UNSIGNED_LONG | COLUMN_IEEE_SINGLE => 4,
+        COLUMN_CURRENCY | COLUMN_IEEE_DOUBLE | COLUMN_DATE_TIME | COLUMN_LONG_LONG => 8,
+        COLUMN_GUID => 16,
+        _ => column.size as usize,
+    }
+}
+
+/// Convert column bytes to a value according to the column type
+fn convert(column: &Column, bytes: &[u8]) -> EseValue {
+    let array = |n: usize| -> Option<[u8; 8]> {
+        let mut buffer = [0u8; 8];
+        buffer[..n].copy_from_slice(bytes.get(..n).filter(|_| bytes.len() == n)?);
+        Some(buffer)
+    };
+    let value = match column.column_type {
+        COLUMN_BIT | COLUMN_UNSIGNED_BYTE => array(1).map(|b| EseValue::Integer(b[0] as i64)),
+        COLUMN_SHORT => array(2).map(|b| EseValue::Integer(i16::from_le_bytes([b[0], b[1]]) as i64)),
+        COLUMN_UNSIGNED_SHORT => array(2).map(|b| EseValue::Integer(u16::from_le_bytes([b[0], b[1]]) as i64)),
+        COLUMN_LONG => array(4).map(|b| EseValue::Integer(i32::from_le_bytes([b[0], b[1], b[2], b[3]]) as i64)),
+        COLUMN_UNSIGNED_LONG => array(4).map(|b| EseValue::Integer(u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as i64)),
+        COLUMN_CURRENCY | COLUMN_LONG_LONG => array(8).map(|b| EseValue::Integer(i64::from_le_bytes(b))),
+        COLUMN_IEEE_SINGLE => array(4).map(|b| EseValue::Real(f32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64)),
+        COLUMN_IEEE_DOUBLE | COLUMN_DATE_TIME => array(8).map(|b| EseValue::Real(f64::from_le_bytes(b))),
+        COLUMN_GUID if bytes.len() == 16 => Some(EseValue::Text(format_guid(bytes))),
+        COLUMN_TEXT | COLUMN_LONG_TEXT if column.codepage == CODEPAGE_UNICODE => {
+            let chars: Vec<u16> = bytes.chunks_exact(2).map(|c| u16::from_le_bytes([c[0], c[1]])).collect();
+            Some(EseValue::Text(String::from_utf16_lossy(&chars).trim_end_matches('\0').to_string()))
+        }
+        COLUMN_TEXT | COLUMN_LONG_TEXT => Some(EseValue::Text(bytes.iter().map(|&b| b as char).collect::<String>().trim_end_matches('\0').to_string())),
+        _ => None,
+    };
+    value.unwrap_or_else(|| EseValue::Binary(bytes.to_vec()))
+}
+
+/// Leading fixed columns and the name of MSysObjects
+fn catalog_columns() -> Vec<Column> {
+    let column = |id, name: &str, column_type| Column { id, name: name.to_string(), column_type, size: 0, codepage: 0 };
+    vec![
+        column(1, "ObjidTable", COLUMN_LONG),
+        column(2, "Type", COLUMN_SHORT),
+        column(3, "Id", COLUMN_LONG),
+        column(4, "ColtypOrPgnoFDP", COLUMN_LONG),
+        column(5, "SpaceUsage", COLUMN_LONG),
+        column(6, "Flags", COLUMN_LONG),
+        column(7, "PagesOrLocale", COLUMN_LONG),
+        column(128, "Name", COLUMN_TEXT),
+    ]
+}
+
+/// Convert an OLE automation date (days since 1899-12-30) from a DateTime column to UTC
+pub fn ole_time(days: f64) -> Option<DateTime<Utc>> {
+    if !days.is_finite() || days <= 0.0 {
+        return None;
+    }
+    let epoch = DateTime::from_timestamp(-2_209_161_600, 0)?;
+    epoch.checked_add_signed(chrono::Duration::milliseconds((days * 86_400_000.0).round() as i64))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    const PAGE_SIZE: usize = 4096;
+    /// Tagged binary values longer than this are stored in the long value tree
+    const LONG_VALUE_THRESHOLD: usize = 64;
+    /// Long value chunk size
+    const LONG_VALUE_CHUNK: usize = 32;
+
+    /// Build a small-page page holding an empty tag 0 followed by the given node values
+    fn page(flags: u32, nodes: &[Vec<u8>]) -> Vec<u8> {
+        let mut page = vec![0u8; PAGE_SIZE];
+        page[34..36].copy_from_slice(&(nodes.len() as u16 + 1).to_le_bytes());
+        page[36..40].copy_from_slice(&flags.to_le_bytes());
+        let mut offset = 0;
+        for (i, node) in std::iter::once(&Vec::new()).chain(nodes).enumerate() {
+            page[40 + offset..40 + offset + node.len()].copy_from_slice(node);
+            let tag = PAGE_SIZE - 4 * (i + 1);
+            page[tag..tag + 2].copy_from_slice(&(node.len() as u16).to_le_bytes());
+            page[tag + 2..tag + 4].copy_from_slice(&(offset as u16).to_le_bytes());
+            offset += node.len();
+        }
+        page
+    }
+
+    fn node(key: &[u8], data: &[u8]) -> Vec<u8> {
+        let mut node = (key.len() as u16).to_le_bytes().to_vec();
+        node.extend_from_slice(key);
+        node.extend_from_slice(data);
+        node
+    }
+
+    fn encode(column: &Column, value: &EseValue) -> Vec<u8> {
+        match value {
+            EseValue::Integer(v) => v.to_le_bytes()[..fixed_size(column)].to_vec(),
+            EseValue::Real(v) if fixed_size(column) == 4 => (*v as f32).to_le_bytes().to_vec(),
+            EseValue::Real(v) => v.to_le_bytes().to_vec(),
+            EseValue::Text(s) if column.codepage == CODEPAGE_UNICODE => s.encode_utf16().flat_map(u16::to_le_bytes).collect(),
+            EseValue::Text(s) => s.as_bytes().to_vec(),
+            EseValue::Binary(b) => b.clone(),
+            EseValue::Null => Vec::new(),
+        }
+    }
+
+    /// Encode a record, separating long tagged values into `long_values`
+    fn record(columns: &[Column], values: &[EseValue], long_values: &mut Vec<Vec<u8>>) -> Vec<u8> {
+        let value = |id: u32| columns.iter().position(|c| c.id == id).map_or(&EseValue::Null, |i| &values[i]);
+        let fixed: Vec<&Column> = columns.iter().filter(|c| c.id < 128).collect();
+        let variable: Vec<&Column> = columns.iter().filter(|c| (128..256).contains(&c.id)).collect();
+        let last_fixed = fixed.last().map_or(0, |c| c.id);
+        let last_variable = variable.last().map_or(127, |c| c.id);
+
+        let mut data = vec![last_fixed as u8, last_variable as u8, 0, 0];
+        let mut bitmap = vec![0u8; last_fixed.div_ceil(8) as usize];
+        for column in &fixed {
+            let bytes = encode(column, value(column.id));
+            if bytes.is_empty() {
+                bitmap[(column.id - 1) as usize / 8] |= 1 << ((column.id - 1) % 8);
+                data.extend(vec![0; fixed_size(column)]);
+            } else {
+                data.extend(bytes);
+            }
+        }
+        data.extend(bitmap);
+        let variable_offset = data.len() as u16;
+        data[2..4].copy_from_slice(&variable_offset.to_le_bytes());
+
+        let mut variable_data = Vec::new();
+        for id in 128..=last_variable {
+            let bytes = encode(columns.iter().find(|c| c.id == id).unwrap(), value(id));
+            let null = if bytes.is_empty() { 0x8000 } else { 0 };
+            variable_data.extend(bytes);
+            data.extend((variable_data.len() as u16 | null).to_le_bytes());
+        }
+        data.extend(variable_data);
+
+        let tagged: Vec<(u32, Vec<u8>)> = columns
+            .iter()
+            .filter(|c| c.id >= 256)
+            .map(|c| (c.id, encode(c, value(c.id))))
+            .filter(|(_, bytes)| !bytes.is_empty())
+            .collect();
+        let mut directory = Vec::new();
+        let mut tagged_data = Vec::new();
+        for (id, bytes) in tagged.iter() {
+            let mut offset = (tagged.len() * 4 + tagged_data.len()) as u16;
+            if bytes.len() > LONG_VALUE_THRESHOLD {
+                long_values.push(bytes.clone());
+                offset |= 0x4000;
+                tagged_data.push(TAGGED_FLAG_LONG_VALUE);
+                tagged_data.extend((long_values.len() as u32).to_le_bytes());
+            } else {
+                tagged_data.extend(bytes);
+            }
+            directory.extend((*id as u16).to_le_bytes());
+            directory.extend(offset.to_le_bytes());
+        }
+        data.extend(directory);
+        data.extend(tagged_data);
+        data
+    }
+
+    /// Table name, columns and rows for `build_database`
+    pub(crate) type TableDefinition<'a> = (&'a str, Vec<Column>, Vec<Vec<EseValue>>);
+
+    /// Build a 4 KiB page database. Each table has a branch root with two rows per leaf page and a
+    /// long value tree holding tagged values longer than 64 bytes in 32-byte chunks.
+    pub(crate) fn build_database(tables: &[TableDefinition]) -> Vec<u8> {
+        let mut header = vec![0u8; PAGE_SIZE];
+        header[4..8].copy_from_slice(&ESE_SIGNATURE.to_le_bytes());
+        header[PAGE_SIZE_OFFSET..PAGE_SIZE_OFFSET + 4].copy_from_slice(&(PAGE_SIZE as u32).to_le_bytes());
+        let mut pages: Vec<Vec<u8>> = vec![header.clone(), header, page(0, &[]), page(0, &[]), page(0, &[])];
+
+        let catalog_columns = catalog_columns();
+        let mut catalog_rows = Vec::new();
+        let mut table_pages = Vec::new();
+        for (object_id, (name, columns, rows)) in tables.iter().enumerate() {
+            let object_id = object_id as i64 + 2;
+            // The catalog is page 4 (file page 5); table pages follow it
+            let root_page = 5 + table_pages.len() as i64;
+            let mut long_values = Vec::new();
+            let records: Vec<Vec<u8>> = rows.iter().map(|row| record(columns, row, &mut long_values)).collect();
+
+            let leaves: Vec<Vec<u8>> = records
+                .chunks(2)
+                .enumerate()
+                .map(|(leaf, chunk)| {
+                    let nodes: Vec<Vec<u8>> = chunk
+                        .iter()
+                        .enumerate()
+                        .map(|(i, r)| node(&((leaf * 2 + i) as u32).to_be_bytes(), r))
+                        .collect();
+                    page(PAGE_FLAG_LEAF, &nodes)
+                })
+                .collect();
+            let branches: Vec<Vec<u8>> = (0..leaves.len())
+                .map(|leaf| node(&((leaf * 2) as u32).to_be_bytes(), &((root_page + 1 + leaf as i64) as u32).to_le_bytes()))
+                .collect();
+            table_pages.push(page(0x1, &branches));
+            table_pages.extend(leaves);
+
+            let entry = |kind: i64, id: i64, coltyp_or_page: i64, size: i64, codepage: i64, name: &str| {
+                let values = [object_id, kind, id, coltyp_or_page, size, 0, codepage].map(EseValue::Integer);
+                let mut values = values.to_vec();
+                values.push(EseValue::Text(name.to_string()));
+                record(&catalog_columns, &values, &mut Vec::new())
+            };
+            catalog_rows.push(entry(CATALOG_TABLE, object_id, root_page, 0, 0, name));
+            for column in columns {
+                catalog_rows.push(entry(CATALOG_COLUMN, column.id as i64, column.column_type as i64, column.size as i64, column.codepage as i64, &column.name));
+            }
+            if !long_values.is_empty() {
+                let mut nodes = Vec::new();
+                for (i, value) in long_values.iter().enumerate() {
+                    let id = (i as u32 + 1).to_be_bytes();
+                    let mut size = 1u32.to_le_bytes().to_vec();
+                    size.extend((value.len() as u32).to_le_bytes());
+                    nodes.push(node(&id, &size));
+                    for (chunk, part) in value.chunks(LONG_VALUE_CHUNK).enumerate() {
+                        let mut key = id.to_vec();
+                        key.extend(((chunk * LONG_VALUE_CHUNK) as u32).to_be_bytes());
+                        nodes.push(node(&key, part));
+                    }
+                }
+                catalog_rows.push(entry(CATALOG_LONG_VALUE, object_id + 100, 5 + table_pages.len() as i64, 0, 0, "LV"));
+                table_pages.push(page(PAGE_FLAG_LEAF | 0x1, &nodes));
+            }
+        }
+
+        let catalog_nodes: Vec<Vec<u8>> = catalog_rows.iter().enumerate().map(|(i, r)| node(&(i as u32).to_be_bytes(), r)).collect();
+        pages.push(page(PAGE_FLAG_LEAF | 0x1, &catalog_nodes));
+        pages.extend(table_pages);
+        pages.concat()
+    }
+
+    pub(crate) fn column(id: u32, name: &str, column_type: u32) -> Column {
+        let codepage = if matches!(column_type, COLUMN_TEXT | COLUMN_LONG_TEXT) { CODEPAGE_UNICODE } else { 0 };
+        Column { id, name: name.to_string(), column_type, size: 0, codepage }
+    }
+
+    #[test]
+    fn test_decompress_and_ole_time() {
+        // "ABC" packed into 21 bits, 5 of them in the last byte
+        assert_eq!(decompress(&[0x0C, 0x41, 0xE1, 0x10]), Some(b"ABC".to_vec()));
+        assert_eq!(decompress(&[0x14, 0x41, 0xE1, 0x10]), Some(vec![b'A', 0, b'B', 0, b'C', 0]));
+        assert_eq!(decompress(&[0x18, 0x00]), None);
+        // 2021-01-01 12:00:00 UTC
+        assert_eq!(ole_time(44197.5).unwrap().timestamp(), 1_609_502_400);
+        assert!(ole_time(0.0).is_none());
+    }
+
+    #[test]
+    fn test_read_table() {
+        let columns = vec![
+            column(1, "AutoIncId", COLUMN_LONG),
+            column(2, "TimeStamp", COLUMN_DATE_TIME),
+            column(3, "Flags", COLUMN_UNSIGNED_SHORT),
+            column(128, "Name", COLUMN_TEXT),
+            column(129, "Comment", COLUMN_TEXT),
+            column(256, "Blob", COLUMN_LONG_BINARY),
+        ];
+        let long_blob: Vec<u8> = (0..100).collect();
+        let rows: Vec<Vec<EseValue>> = (0..5)
+            .map(|i| {
+                vec![
+                    EseValue::Integer(i + 1),
+                    EseValue::Real(44197.5),
+                    if i == 1 { EseValue::Null } else { EseValue::Integer(0xFFFF) },
+                    EseValue::Text(format!("row {}", i)),
+                    EseValue::Null,
+                    if i == 3 { EseValue::Binary(long_blob.clone()) } else { EseValue::Binary(vec![i as u8; 3]) },
+                ]
+            })
+            .collect();
+        let data = build_database(&[("Things", columns, rows)]);
+
+        let database = EseDatabase::open(&data).unwrap();
+        let schema = database.table_schema("things").unwrap();
+        assert_eq!(schema.columns.len(), 6);
+        assert!(schema.long_value_page.is_some());
+
+        let table = database.read_table("Things").unwrap();
+        assert_eq!(table.rows.len(), 5);
+        let rows: Vec<Row> = table.records().collect();
+        assert_eq!(rows[4].integer("AutoIncId"), Some(5));
+        assert_eq!(rows[0].real("TimeStamp"), Some(44197.5));
+        assert_eq!(rows[0].integer("Flags"), Some(0xFFFF));
+        assert_eq!(rows[1].get("Flags"), Some(&EseValue::Null));
+        assert_eq!(rows[2].text("Name").as_deref(), Some("row 2"));
+        assert_eq!(rows[2].get("Comment"), Some(&EseValue::Null));
+        assert_eq!(rows[2].blob("Blob"), Some(&[2u8, 2, 2][..]));
+        assert_eq!(rows[3].blob("Blob"), Some(&long_blob[..]));
+        assert!(database.read_table("Missing").is_err());
+    }
+
+    #[test]
+    fn test_long_values_ignore_out_of_range_chunks() {
+        let lv = |id: u32, offset: u32, data: &[u8]| {
+            let mut key = id.to_be_bytes().to_vec();
+            key.extend(offset.to_be_bytes());
+            node(&key, data)
+        };
+        let root = |id: u32, size: u32| {
+            let mut data = 1u32.to_le_bytes().to_vec();
+            data.extend(size.to_le_bytes());
+            node(&id.to_be_bytes(), &data)
+        };
+        let nodes = vec![
+            root(1, 6),
+            lv(1, 0, b"abcd"),
+            lv(1, 4, b"efgh"),
+            lv(1, 0x7FFF_FFF0, b"ijkl"),
+            lv(2, 0, b"mnop"),
+            lv(2, 0x7FFF_FFF0, b"qrst"),
+        ];
+        let mut data = build_database(&[]);
+        data.extend(page(PAGE_FLAG_LEAF | 0x1, &nodes));
+
+        let database = EseDatabase::open(&data).unwrap();
+        // The appended page follows the catalog (page 4)
+        let long_values = database.long_values(5).unwrap();
+        assert_eq!(long_values[&1], b"abcdef");
+        assert_eq!(long_values[&2], b"mnop");
+    }
+
+    #[test]
+    fn test_open_rejects_other_files() {
+        assert!(EseDatabase::open(&[0u8; 8192]).is_err());
+        let mut header = vec![0u8; 8192];
+        header[4..8].copy_from_slice(&ESE_SIGNATURE.to_le_bytes());
+        header[PAGE_SIZE_OFFSET..PAGE_SIZE_OFFSET + 4].copy_from_slice(&1000u32.to_le_bytes());
+        assert!(EseDatabase::open(&header).is_err());
+    }
+}
@@ -0,0 +1,414 @@
+//! Curated event-ID catalogue for Windows Event Log records
+//!
+//! Supports describing:
+//! - Security logons, logoffs, explicit credentials and special privileges (4624/4625/4634/4648/4672)
+//! - Process creation (4688) and service installs (4697/7045)
+//! - Account changes (4720/4726) and log clearing (1102)
+//! - PowerShell script blocks (4104), RDP sessions (21/24/25) and Task Scheduler (106/200/201)
+//! - User-supplied definitions loaded from a JSON file
+
+use crate::error::{Error, Result};
+use crate::evtx::EvtxRecord;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Longest value substituted into a description before it is truncated
+const MAX_VALUE_CHARS: usize = 120;
+
+const SECURITY: &str = "Security";
+const SYSTEM: &str = "System";
+const POWERSHELL: &str = "Microsoft-Windows-PowerShell/Operational";
+const RDP: &str = "Microsoft-Windows-TerminalServices-LocalSessionManager/Operational";
+const TASK_SCHEDULER: &str = "Microsoft-Windows-TaskScheduler/Operational";
+
+/// Logon type names (4624/4625/4634)
+const LOGON_TYPES: &[(&str, &str)] = &[
+    ("0", "System"),
+    ("2", "Interactive"),
+    ("3", "Network"),
+    ("4", "Batch"),
+    ("5", "Service"),
+    ("7", "Unlock"),
+    ("8", "NetworkCleartext"),
+    ("9", "NewCredentials"),
+    ("10", "RemoteInteractive"),
+    ("11", "CachedInteractive"),
+    ("12", "CachedRemoteInteractive"),
+    ("13", "CachedUnlock"),
+];
+
+/// NTSTATUS codes reported by failed logons (4625)
+const LOGON_FAILURES: &[(&str, &str)] = &[
+    ("0xc0000064", "No such user"),
+    ("0xc000006a", "Bad password"),
+    ("0xc000006d", "Bad username or password"),
+    ("0xc000006f", "Outside logon hours"),
+    ("0xc0000070", "Workstation restriction"),
+    ("0xc0000071", "Password expired"),
+    ("0xc0000072", "Account disabled"),
+    ("0xc000015b", "Logon type not granted"),
+    ("0xc0000193", "Account expired"),
+    ("0xc0000224", "Password must change"),
+    ("0xc0000234", "Account locked out"),
+];
+
+/// Service start types (4697)
+const SERVICE_START_TYPES: &[(&str, &str)] = &[
+    ("0", "Boot"),
+    ("1", "System"),
+    ("2", "Automatic"),
+    ("3", "Manual"),
+    ("4", "Disabled"),
+];
+
+/// Catalogue entry for one event ID in one channel
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventDefinition {
+    /// Event identifier
+    pub event_id: u32,
+    /// Channel the event is logged to (matched case-insensitively)
+    pub channel: String,
+    /// Description template; `{Field}` is replaced by the named EventData/UserData value
+    pub description: String,
+    /// Data fields surfaced first in the event metadata
+    #[serde(default)]
+    pub fields: Vec<String>,
+    /// Value names per field (e.g. LogonType "3" => "Network")
+    #[serde(default)]
+    pub lookups: HashMap<String, HashMap<String, String>>,
+}
+
+impl EventDefinition {
+    /// Attach a value lookup table to a field
+    fn lookup(mut self, field: &str, names: &[(&str, &str)]) -> Self {
+        self.lookups.insert(
+            field.to_string(),
+            names.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect(),
+        );
+        self
+    }
+
+    /// Field value with its lookup name appended when known (e.g. "3 (Network)")
+    fn field_value(&self, record: &EvtxRecord, field: &str) -> Option<String> {
+        let value = record.data.iter().find(|(name, _)| name == field).map(|(_, v)| v.trim())?;
+        let name = self.lookups.get(field).and_then(|lookup| lookup.get(&value.to_lowercase()));
+        Some(match name {
+            Some(name) => format!("{} ({})", value, name),
+            None => value.to_string(),
+        })
+    }
+
+    /// Render the description template for a record
+    fn render(&self, record: &EvtxRecord) -> String {
+        let mut output = String::new();
+        let mut rest = self.description.as_str();
+
+        while let Some(start) = rest.find('{') {
+            let Some(end) = rest[start..].find('}') else {
+                break;
+            };
+            output.push_str(&rest[..start]);
+            let field = &rest[start + 1..start + end];
+            let value = self
+                .field_value(record, field)
+                .filter(|v| !v.is_empty())
+                .unwrap_or_else(|| "-".to_string());
+            output.push_str(&truncate(&value.replace(['\r', '\n'], " ")));
+            rest = &rest[start + end + 1..];
+        }
+        output.push_str(rest);
+        output
+    }
+}
+
+/// Shorten long values (script blocks, command lines) for one-line descriptions
+fn truncate(value: &str) -> String {
+    if value.chars().count() > MAX_VALUE_CHARS {
+        format!("{}...", value.chars().take(MAX_VALUE_CHARS).collect::<String>())
+    } else {
+        value.to_string()
+    }
+}
+
+/// Event-ID catalogue keyed by channel and event ID
+#[derive(Debug, Clone)]
+pub struct EventCatalog {
+    definitions: HashMap<(String, u32), EventDefinition>,
+}
+
+impl Default for EventCatalog {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+impl EventCatalog {
+    /// Built-in catalogue of high-value security events
+    pub fn builtin() -> Self {
+        let mut catalog = Self { definitions: HashMap::new() };
+        let logon_fields = [
+            "TargetUserName",
+            "TargetDomainName",
+            "LogonType",
+            "IpAddress",
+            "WorkstationName",
+            "LogonProcessName",
+            "AuthenticationPackageName",
+            "TargetLogonId",
+        ];
+
+        catalog.add(
+            define(4624, SECURITY, "Logon: {TargetDomainName}\\{TargetUserName} type {LogonType} from {IpAddress}", &logon_fields)
+                .lookup("LogonType", LOGON_TYPES),
+        );
+        catalog.add(
+            define(
+                4625,
+                SECURITY,
+                "Failed logon: {TargetDomainName}\\{TargetUserName} type {LogonType} from {IpAddress} - {SubStatus}",
+                &[&logon_fields[..], &["Status", "SubStatus", "FailureReason"]].concat(),
+            )
+            .lookup("LogonType", LOGON_TYPES)
+            .lookup("Status", LOGON_FAILURES)
+            .lookup("SubStatus", LOGON_FAILURES),
+        );
+        catalog.add(
+            define(
+                4634,
+                SECURITY,
+                "Logoff: {TargetDomainName}\\{TargetUserName} type {LogonType}",
+                &["TargetUserName", "TargetDomainName", "LogonType", "TargetLogonId"],
+            )
+            .lookup("LogonType", LOGON_TYPES),
+        );
+        catalog.add(define(
+            4648,
+            SECURITY,
+            "Explicit credential logon: {SubjectUserName} as {TargetDomainName}\\{TargetUserName} to {TargetServerName}",
+            &["SubjectUserName", "TargetUserName", "TargetDomainName", "TargetServerName", "ProcessName", "IpAddress"],
+        ));
+        catalog.add(define(
+            4672,
+            SECURITY,
+            "Special privileges assigned: {SubjectDomainName}\\{SubjectUserName}",
+            &["SubjectUserName", "SubjectDomainName", "SubjectLogonId", "PrivilegeList"],
+        ));
+        catalog.add(define(
+            4688,
+            SECURITY,
+            "Process created: {NewProcessName} by {SubjectUserName}: {CommandLine}",
+            &["NewProcessName", "CommandLine", "ParentProcessName", "NewProcessId", "SubjectUserName", "TargetUserName"],
+        ));
+        catalog.add(
+            define(
+                4697,
+                SECURITY,
+                "Service installed: {ServiceName} ({ServiceFileName}) by {SubjectUserName}",
+                &["ServiceName", "ServiceFileName", "ServiceType", "ServiceStartType", "ServiceAccount", "SubjectUserName"],
+            )
+            .lookup("ServiceStartType", SERVICE_START_TYPES),
+        );
+        catalog.add(define(
+            7045,
+            SYSTEM,
+            "Service installed: {ServiceName} ({ImagePath}) as {AccountName}",
+            &["ServiceName", "ImagePath", "ServiceType", "StartType", "AccountName"],
+        ));
+        catalog.add(define(
+            4720,
+            SECURITY,
+            "User account created: {TargetDomainName}\\{TargetUserName} by {SubjectUserName}",
+            &["TargetUserName", "TargetDomainName", "TargetSid", "SubjectUserName"],
+        ));
+        catalog.add(define(
+            4726,
+            SECURITY,
+            "User account deleted: {TargetDomainName}\\{TargetUserName} by {SubjectUserName}",
+            &["TargetUserName", "TargetDomainName", "TargetSid", "SubjectUserName"],
+        ));
+        catalog.add(define(
+            1102,
+            SECURITY,
+            "Security log cleared by {SubjectDomainName}\\{SubjectUserName}",
+            &["SubjectUserName", "SubjectDomainName", "SubjectUserSid"],
+        ));
+        catalog.add(define(
+            4104,
+            POWERSHELL,
+            "PowerShell script block ({MessageNumber}/{MessageTotal}): {ScriptBlockText}",
+            &["ScriptBlockId", "MessageNumber", "MessageTotal", "Path", "ScriptBlockText"],
+        ));
+        catalog.add(define(
+            21,
+            RDP,
+            "RDP logon: {User} session {SessionID} from {Address}",
+            &["User", "SessionID", "Address"],
+        ));
+        catalog.add(define(
+            24,
+            RDP,
+            "RDP session disconnected: {User} session {SessionID} from {Address}",
+            &["User", "SessionID", "Address"],
+        ));
+        catalog.add(define(
+            25,
+            RDP,
+            "RDP session reconnected: {User} session {SessionID} from {Address}",
+            &["User", "SessionID", "Address"],
+        ));
+        catalog.add(define(
+            106,
+            TASK_SCHEDULER,
+            "Scheduled task registered: {TaskName} by {UserContext}",
+            &["TaskName", "UserContext"],
+        ));
+        catalog.add(define(
+            200,
+            TASK_SCHEDULER,
+            "Scheduled task action started: {TaskName} ({ActionName})",
+            &["TaskName", "ActionName", "TaskInstanceId"],
+        ));
+        catalog.add(define(
+            201,
+            TASK_SCHEDULER,
+            "Scheduled task action completed: {TaskName} ({ActionName}) result {ResultCode}",
+            &["TaskName", "ActionName", "TaskInstanceId", "ResultCode"],
+        ));
+        catalog
+    }
+
+    /// Built-in catalogue extended (or overridden) by definitions from a JSON file
+    pub fn with_file(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        let definitions: Vec<EventDefinition> = serde_json::from_str(&content).map_err(|e| {
+            Error::InvalidInput(format!("Invalid event catalogue {}: {}", path.display(), e))
+        })?;
+
+        let mut catalog = Self::builtin();
+        for definition in definitions {
+            catalog.add(definition);
+        }
+        Ok(catalog)
+    }
+
+    /// Add a definition, replacing any existing entry for the same channel and event ID
+    pub fn add(&mut self, mut definition: EventDefinition) {
+        // Lookup keys are matched against lowercased values
+        for lookup in definition.lookups.values_mut() {
+            *lookup = lookup.drain().map(|(k, v)| (k.to_lowercase(), v)).collect();
+        }
+        self.definitions
+            .insert((definition.channel.to_lowercase(), definition.event_id), definition);
+    }
+
+    /// Number of definitions
+    pub fn len(&self) -> usize {
+        self.definitions.len()
+    }
+
+    /// Whether the catalogue is empty
+    pub fn is_empty(&self) -> bool {
+        self.definitions.is_empty()
+    }
+
+    /// Definition for a record's channel and event ID
+    pub fn lookup(&self, record: &EvtxRecord) -> Option<&EventDefinition> {
+        self.definitions.get(&(record.channel.to_lowercase(), record.event_id))
+    }
+
+    /// Readable description and curated fields for a catalogued record
+    pub fn describe(&self, record: &EvtxRecord) -> Option<(String, Vec<(String, String)>)> {
+        let definition = self.lookup(record)?;
+        let fields = definition
+            .fields
+            .iter()
+            .filter_map(|field| {
+                definition
+                    .field_value(record, field)
+                    .filter(|v| !v.is_empty() && v != "-")
+                    .map(|v| (field.clone(), v))
+            })
+            .collect();
+        Some((definition.render(record), fields))
+    }
+}
+
+/// Build a built-in definition
+fn define(event_id: u32, channel: &str, description: &str, fields: &[&str]) -> EventDefinition {
+    EventDefinition {
+        event_id,
+        channel: channel.to_string(),
+        description: description.to_string(),
+        fields: fields.iter().map(|f| f.to_string()).collect(),
+        lookups: HashMap::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(channel: &str, event_id: u32, data: &[(&str, &str)]) -> EvtxRecord {
+        EvtxRecord {
+            record_id: 1,
+            timestamp: None,
+            provider: "Microsoft-Windows-Security-Auditing".to_string(),
+            event_id,
+            level: None,
+            task: None,
+            opcode: None,
+            keywords: None,
+            channel: channel.to_string(),
+            computer: "WS01".to_string(),
+            user_id: None,
+            process_id: None,
+            thread_id: None,
+            data: data.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect(),
+        }
+    }
+
+    #[test]
+    fn test_describe_logon() {
+        let catalog = EventCatalog::builtin();
+        let logon = record(
+            "security",
+            4625,
+            &[
+                ("TargetUserName", "alice"),
+                ("TargetDomainName", "CORP"),
+                ("LogonType", "3"),
+                ("IpAddress", "10.0.0.5"),
+                ("SubStatus", "0xC000006A"),
+            ],
+        );
+
+        let (description, fields) = catalog.describe(&logon).unwrap();
+        assert_eq!(
+            description,
+            "Failed logon: CORP\\alice type 3 (Network) from 10.0.0.5 - 0xC000006A (Bad password)"
+        );
+        assert!(fields.contains(&("LogonType".to_string(), "3 (Network)".to_string())));
+        assert!(catalog.describe(&record("Application", 4625, &[])).is_none());
+    }
+
+    #[test]
+    fn test_extend_from_file() {
+        let path = std::env::temp_dir().join(format!("tl_catalog_{}.json", std::process::id()));
+        std::fs::write(
+            &path,
+            r#"[{"event_id": 4740, "channel": "Security", "description": "Account locked out: {TargetUserName}"},
+               {"event_id": 1102, "channel": "Security", "description": "Audit log wiped"}]"#,
+        )
+        .unwrap();
+        let catalog = EventCatalog::with_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(catalog.len(), EventCatalog::builtin().len() + 1);
+        let (description, _) = catalog
+            .describe(&record("Security", 4740, &[("TargetUserName", "bob")]))
+            .unwrap();
+        assert_eq!(description, "Account locked out: bob");
+        assert_eq!(catalog.describe(&record("Security", 1102, &[])).unwrap().0, "Audit log wiped");
+    }
+}
@@ -0,0 +1,800 @@
+//! Windows Event Log (.evtx) parsing module with binary XML decoding
+//!
+//! Supports parsing of:
+//! - File header and 64KB chunks (header and record CRC32 checks)
+//! - Event records with BinXML fragments
+//! - Template definitions, template instances and substitutions
+//! - System fields, EventData and UserData
+
+use crate::error::{Error, Result};
+use crate::registry::{filetime_to_datetime, format_guid, format_sid, read_u16, read_u32, read_u64, systemtime};
+use chrono::{DateTime, Utc};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// File header signature
+const FILE_SIGNATURE: &[u8; 8] = b"ElfFile\0";
+/// Chunk header signature
+const CHUNK_SIGNATURE: &[u8; 8] = b"ElfChnk\0";
+/// Event record signature ("**\0\0")
+const RECORD_SIGNATURE: u32 = 0x0000_2A2A;
+/// Size of the file header block
+const FILE_HEADER_SIZE: usize = 4096;
+/// Size of each chunk
+const CHUNK_SIZE: usize = 65536;
+/// Size of the chunk header (records start after it)
+const CHUNK_HEADER_SIZE: usize = 512;
+/// Maximum element nesting accepted while decoding BinXML
+const MAX_DEPTH: usize = 64;
+
+/// Decoded XML node
+#[derive(Debug, Clone)]
+pub enum XmlNode {
+    /// Element with attributes and children
+    Element {
+        name: String,
+        attributes: Vec<(String, Vec<XmlNode>)>,
+        children: Vec<XmlNode>,
+    },
+    /// Character data
+    Text(String),
+    /// Template substitution placeholder (resolved when the template is instantiated)
+    Substitution { id: u16, optional: bool },
+}
+
+impl XmlNode {
+    /// Element name (empty for text)
+    pub fn name(&self) -> &str {
+        match self {
+            XmlNode::Element { name, .. } => name,
+            _ => "",
+        }
+    }
+
+    /// Concatenated text content
+    pub fn text(&self) -> String {
+        match self {
+            XmlNode::Element { children, .. } => children.iter().map(|c| c.text()).collect(),
+            XmlNode::Text(text) => text.clone(),
+            XmlNode::Substitution { .. } => String::new(),
+        }
+    }
+
+    /// Attribute value by name
+    pub fn attribute(&self, attribute: &str) -> Option<String> {
+        match self {
+            XmlNode::Element { attributes, .. } => attributes
+                .iter()
+                .find(|(name, _)| name == attribute)
+                .map(|(_, value)| value.iter().map(|v| v.text()).collect()),
+            _ => None,
+        }
+    }
+
+    /// Child elements
+    pub fn elements(&self) -> impl Iterator<Item = &XmlNode> {
+        let children: &[XmlNode] = match self {
+            XmlNode::Element { children, .. } => children,
+            _ => &[],
+        };
+        children.iter().filter(|c| matches!(c, XmlNode::Element { .. }))
+    }
+
+    /// First child element with the given name
+    pub fn child(&self, name: &str) -> Option<&XmlNode> {
+        self.elements().find(|c| c.name() == name)
+    }
+}
+
+/// Event record rendered to structured fields
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvtxRecord {
+    /// Event record identifier
+    pub record_id: u64,
+    /// Event time (System/TimeCreated, falling back to the record's written time)
+    pub timestamp: Option<DateTime<Utc>>,
+    /// Provider name
+    pub provider: String,
+    /// Event identifier
+    pub event_id: u32,
+    /// Level (1 critical .. 5 verbose, 0 log always)
+    pub level: Option<u8>,
+    /// Task
+    pub task: Option<u16>,
+    /// Opcode
+    pub opcode: Option<u8>,
+    /// Keywords
+    pub keywords: Option<String>,
+    /// Channel (Security, System, ...)
+    pub channel: String,
+    /// Computer name
+    pub computer: String,
+    /// Security user SID
+    pub user_id: Option<String>,
+    /// Process ID
+    pub process_id: Option<u32>,
+    /// Thread ID
+    pub thread_id: Option<u32>,
+    /// EventData / UserData fields
+    pub data: Vec<(String, String)>,
+}
+
+/// EVTX file header fields
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvtxHeader {
+    /// Number of chunks
+    pub chunk_count: u16,
+    /// Next record identifier
+    pub next_record_id: u64,
+    /// Format version (major, minor)
+    pub version: (u16, u16),
+    /// File is dirty (not cleanly closed)
+    pub dirty: bool,
+    /// Header checksum matched
+    pub checksum_valid: bool,
+}
+
+/// EVTX parser
+pub struct EvtxParser;
+
+impl EvtxParser {
+    /// Parse the file header
+    pub fn parse_header(data: &[u8]) -> Result<EvtxHeader> {
+        if data.len() < FILE_HEADER_SIZE || &data[0..8] != FILE_SIGNATURE {
+            return Err(Error::ParseError("Invalid EVTX file signature".to_string()));
+        }
+
+        let checksum = read_u32(data, 124).unwrap_or(0);
+        Ok(EvtxHeader {
+            chunk_count: read_u16(data, 42).unwrap_or(0),
+            next_record_id: read_u64(data, 24).unwrap_or(0),
+            version: (read_u16(data, 38).unwrap_or(0), read_u16(data, 36).unwrap_or(0)),
+            dirty: read_u32(data, 120).unwrap_or(0) & 0x1 != 0,
+            checksum_valid: crc32fast::hash(&data[0..120]) == checksum,
+        })
+    }
+
+    /// Parse all records, decoding chunks in parallel
+    pub fn parse(data: &[u8]) -> Result<Vec<EvtxRecord>> {
+        let header = Self::parse_header(data)?;
+        if !header.checksum_valid {
+            eprintln!("⚠️  EVTX file header checksum mismatch");
+        }
+
+        // Dirty files may hold more chunks than the header's count, so walk everything present
+        let chunks: Vec<&[u8]> = data[FILE_HEADER_SIZE..]
+            .chunks(CHUNK_SIZE)
+            .filter(|chunk| chunk.len() == CHUNK_SIZE && &chunk[0..8] == CHUNK_SIGNATURE)
+            .collect();
+
+        let mut records: Vec<EvtxRecord> = chunks
+            .par_iter()
+            .enumerate()
+            .flat_map_iter(|(index, chunk)| match parse_chunk(chunk) {
+                Ok(records) => records,
+                Err(e) => {
+                    eprintln!("⚠️  Skipping EVTX chunk {}: {}", index, e);
+                    Vec::new()
+                }
+            })
+            .collect();
+
+        records.sort_by_key(|r| r.record_id);
+        Ok(records)
+    }
+}
+
+/// Parse every record in a chunk after validating its checksums
+fn parse_chunk(chunk: &[u8]) -> Result<Vec<EvtxRecord>> {
+    let header_crc = read_u32(chunk, 124).unwrap_or(0);
+    let mut hasher = crc32fast::Hasher::new();
+    hasher.update(&chunk[0..120]);
+    hasher.update(&chunk[128..CHUNK_HEADER_SIZE]);
+    if hasher.finalize() != header_crc {
+        return Err(Error::ParseError("chunk header checksum mismatch".to_string()));
+    }
+
+    let free_offset = (read_u32(chunk, 48).unwrap_or(0) as usize).clamp(CHUNK_HEADER_SIZE, CHUNK_SIZE);
+    if crc32fast::hash(&chunk[CHUNK_HEADER_SIZE..free_offset]) != read_u32(chunk, 52).unwrap_or(0) {
+        eprintln!("⚠️  EVTX chunk record checksum mismatch; parsing records anyway");
+    }
+
+    let mut decoder = BinXmlDecoder {
+        chunk,
+        templates: HashMap::new(),
+    };
+    let mut records = Vec::new();
+    let mut offset = CHUNK_HEADER_SIZE;
+
+    while offset + 24 <= free_offset {
+        if read_u32(chunk, offset) != Some(RECORD_SIGNATURE) {
+            break;
+        }
+        let size = read_u32(chunk, offset + 4).unwrap_or(0) as usize;
+        if size < 28 || offset + size > CHUNK_SIZE {
+            break;
+        }
+
+        let record_id = read_u64(chunk, offset + 8).unwrap_or(0);
+        let written = read_u64(chunk, offset + 16).and_then(filetime_to_datetime);
+        match decoder.parse_fragment(offset + 24, offset + size - 4) {
+            Ok(nodes) => {
+                if let Some(event) = nodes.iter().find(|n| n.name() == "Event") {
+                    records.push(record_from_xml(record_id, written, event));
+                }
+            }
+            Err(e) => eprintln!("⚠️  Failed to decode EVTX record {}: {}", record_id, e),
+        }
+        offset += size;
+    }
+
+    Ok(records)
+}
+
+/// Substitution value from a template instance
+#[derive(Clone)]
+enum SubstitutionValue {
+    Null,
+    Text(String),
+    Xml(Vec<XmlNode>),
+}
+
+/// BinXML decoder for a single chunk (names and templates are chunk-relative)
+struct BinXmlDecoder<'a> {
+    chunk: &'a [u8],
+    templates: HashMap<u32, Arc<Vec<XmlNode>>>,
+}
+
+impl BinXmlDecoder<'_> {
+    /// Decode a fragment between chunk offsets and resolve any template instances
+    fn parse_fragment(&mut self, start: usize, end: usize) -> Result<Vec<XmlNode>> {
+        let mut pos = start;
+        self.parse_nodes(&mut pos, end, false, 0)
+    }
+
+    /// Parse sibling nodes until an end element, end of fragment or `end`
+    fn parse_nodes(&mut self, pos: &mut usize, end: usize, in_value: bool, depth: usize) -> Result<Vec<XmlNode>> {
+        if depth > MAX_DEPTH {
+            return Err(Error::ParseError("BinXML nesting too deep".to_string()));
+        }
+
+        let mut nodes = Vec::new();
+        while *pos < end {
+            let token = self.byte(*pos)?;
+            match token & !0x40 {
+                0x00 => {
+                    *pos += 1;
+                    break;
+                }
+                0x01 => nodes.push(self.parse_element(pos, end, token & 0x40 != 0, in_value, depth)?),
+                0x04 => {
+                    *pos += 1;
+                    break;
+                }
+                0x0C => nodes.extend(self.parse_template_instance(pos, depth)?),
+                0x0F => *pos += 4,
+                _ => nodes.push(self.parse_content(pos)?),
+            }
+        }
+        Ok(nodes)
+    }
+
+    /// Parse an element, its attributes and children
+    fn parse_element(
+        &mut self,
+        pos: &mut usize,
+        end: usize,
+        has_attributes: bool,
+        in_value: bool,
+        depth: usize,
+    ) -> Result<XmlNode> {
+        *pos += 1;
+        // Elements inside embedded BinXML values omit the dependency identifier
+        if !in_value {
+            *pos += 2;
+        }
+        *pos += 4; // data size
+        let name = self.read_name(pos)?;
+
+        let mut attributes = Vec::new();
+        if has_attributes {
+            let list_size = self.u32(*pos)? as usize;
+            *pos += 4;
+            let list_end = *pos + list_size;
+            while *pos < list_end && self.byte(*pos)? & !0x40 == 0x06 {
+                *pos += 1;
+                let attribute = self.read_name(pos)?;
+                let mut value = Vec::new();
+                while *pos < list_end && matches!(self.byte(*pos)? & !0x40, 0x05 | 0x08 | 0x09 | 0x0D | 0x0E) {
+                    value.push(self.parse_content(pos)?);
+                }
+                attributes.push((attribute, value));
+            }
+        }
+
+        let children = match self.byte(*pos)? {
+            0x02 => {
+                *pos += 1;
+                self.parse_nodes(pos, end, in_value, depth + 1)?
+            }
+            0x03 => {
+                *pos += 1;
+                Vec::new()
+            }
+            token => return Err(Error::ParseError(format!("Unexpected BinXML token {:#x} after element", token))),
+        };
+
+        Ok(XmlNode::Element { name, attributes, children })
+    }
+
+    /// Parse a value, substitution, character/entity reference, CDATA or processing instruction
+    fn parse_content(&mut self, pos: &mut usize) -> Result<XmlNode> {
+        let token = self.byte(*pos)?;
+        *pos += 1;
+        match token & !0x40 {
+            0x05 => {
+                let value_type = self.byte(*pos)?;
+                *pos += 1;
+                // Strings are length-prefixed UTF-16, other types are stored at their fixed size
+                if value_type == 0x01 {
+                    return Ok(XmlNode::Text(self.read_prefixed_string(pos)?));
+                }
+                let size = fixed_value_size(value_type)
+                    .ok_or_else(|| Error::ParseError(format!("Unsupported BinXML value type {:#x}", value_type)))?;
+                let bytes = self
+                    .chunk
+                    .get(*pos..*pos + size)
+                    .ok_or_else(|| Error::ParseError("BinXML data truncated".to_string()))?;
+                *pos += size;
+                Ok(XmlNode::Text(render_value(value_type, bytes)))
+            }
+            0x07 => Ok(XmlNode::Text(self.read_prefixed_string(pos)?)),
+            0x08 => {
+                let c = self.u16(*pos)?;
+                *pos += 2;
+                Ok(XmlNode::Text(char::from_u32(c as u32).map(String::from).unwrap_or_default()))
+            }
+            0x09 => {
+                let entity = self.read_name(pos)?;
+                let text = match entity.as_str() {
+                    "amp" => "&",
+                    "lt" => "<",
+                    "gt" => ">",
+                    "quot" => "\"",
+                    "apos" => "'",
+                    _ => "",
+                };
+                Ok(XmlNode::Text(text.to_string()))
+            }
+            0x0A => {
+                self.read_name(pos)?;
+                Ok(XmlNode::Text(String::new()))
+            }
+            0x0B => {
+                self.read_prefixed_string(pos)?;
+                Ok(XmlNode::Text(String::new()))
+            }
+            0x0D | 0x0E => {
+                let id = self.u16(*pos)?;
+                *pos += 3; // identifier + value type
+                Ok(XmlNode::Substitution { id, optional: token & !0x40 == 0x0E })
+            }
+            _ => Err(Error::ParseError(format!("Unknown BinXML token {:#x}", token))),
+        }
+    }
+
+    /// Parse a template instance and return the instantiated nodes
+    fn parse_template_instance(&mut self, pos: &mut usize, depth: usize) -> Result<Vec<XmlNode>> {
+        *pos += 2; // token + unknown
+        *pos += 4; // template identifier
+        let definition = self.u32(*pos)?;
+        *pos += 4;
+
+        let definition_size = self.u32(definition as usize + 20)? as usize;
+        if definition as usize == *pos {
+            // Inline definition: next offset, GUID, data size, fragment
+            *pos += 24 + definition_size;
+        }
+
+        let template = match self.templates.get(&definition) {
+            Some(template) => template.clone(),
+            None => {
+                let body = definition as usize + 24;
+                let mut body_pos = body;
+                let nodes = Arc::new(self.parse_nodes(&mut body_pos, body + definition_size, false, depth + 1)?);
+                self.templates.insert(definition, nodes.clone());
+                nodes
+            }
+        };
+
+        let count = self.u32(*pos)? as usize;
+        *pos += 4;
+        let mut descriptors = Vec::with_capacity(count);
+        for _ in 0..count {
+            descriptors.push((self.u16(*pos)? as usize, self.byte(*pos + 2)?));
+            *pos += 4;
+        }
+
+        let mut values = Vec::with_capacity(count);
+        for (size, value_type) in descriptors {
+            let bytes = self
+                .chunk
+                .get(*pos..*pos + size)
+                .ok_or_else(|| Error::ParseError("Substitution value out of bounds".to_string()))?;
+            let value = if value_type == 0x21 {
+                let mut value_pos = *pos;
+                SubstitutionValue::Xml(self.parse_nodes(&mut value_pos, *pos + size, true, depth + 1)?)
+            } else if value_type == 0x00 || size == 0 {
+                SubstitutionValue::Null
+            } else {
+                SubstitutionValue::Text(render_value(value_type, bytes))
+            };
+            values.push(value);
+            *pos += size;
+        }
+
+        Ok(instantiate(&template, &values))
+    }
+
+    /// Read a name reference, advancing past inline names
+    fn read_name(&self, pos: &mut usize) -> Result<String> {
+        let offset = self.u32(*pos)? as usize;
+        *pos += 4;
+
+        let count = self.u16(offset + 6)? as usize;
+        let name = utf16_at(self.chunk, offset + 8, count)?;
+        if offset == *pos {
+            *pos += 8 + count * 2 + 2;
+        }
+        Ok(name)
+    }
+
+    /// Read a u16 character count followed by UTF-16 characters
+    fn read_prefixed_string(&self, pos: &mut usize) -> Result<String> {
+        let count = self.u16(*pos)? as usize;
+        let text = utf16_at(self.chunk, *pos + 2, count)?;
+        *pos += 2 + count * 2;
+        Ok(text)
+    }
+
+    fn byte(&self, pos: usize) -> Result<u8> {
+        self.chunk
+            .get(pos)
+            .copied()
+            .ok_or_else(|| Error::ParseError("BinXML data truncated".to_string()))
+    }
+
+    fn u16(&self, pos: usize) -> Result<u16> {
+        read_u16(self.chunk, pos).ok_or_else(|| Error::ParseError("BinXML data truncated".to_string()))
+    }
+
+    fn u32(&self, pos: usize) -> Result<u32> {
+        read_u32(self.chunk, pos).ok_or_else(|| Error::ParseError("BinXML data truncated".to_string()))
+    }
+}
+
+/// Decode `count` UTF-16 characters
+fn utf16_at(data: &[u8], offset: usize, count: usize) -> Result<String> {
+    let bytes = data
+        .get(offset..offset + count * 2)
+        .ok_or_else(|| Error::ParseError("BinXML string out of bounds".to_string()))?;
+    let units: Vec<u16> = bytes.chunks_exact(2).map(|c| u16::from_le_bytes([c[0], c[1]])).collect();
+    Ok(String::from_utf16_lossy(&units).trim_end_matches('\0').to_string())
+}
+
+/// Replace substitution placeholders with template instance values
+fn instantiate(nodes: &[XmlNode], values: &[SubstitutionValue]) -> Vec<XmlNode> {
+    let mut output = Vec::new();
+    for node in nodes {
+        match node {
+            // Null values render as nothing, whether or not the substitution is optional
+            XmlNode::Substitution { id, .. } => match values.get(*id as usize) {
+                Some(SubstitutionValue::Text(text)) => output.push(XmlNode::Text(text.clone())),
+                Some(SubstitutionValue::Xml(xml)) => output.extend(xml.iter().cloned()),
+                Some(SubstitutionValue::Null) | None => {}
+            },
+            XmlNode::Element { name, attributes, children } => {
+                let attributes = attributes
+                    .iter()
+                    .map(|(attribute, value)| (attribute.clone(), instantiate(value, values)))
+                    // Optional substitutions that resolve to nothing drop the attribute
+                    .filter(|(_, value)| !value.is_empty())
+                    .collect();
+                output.push(XmlNode::Element {
+                    name: name.clone(),
+                    attributes,
+                    children: instantiate(children, values),
+                });
+            }
+            XmlNode::Text(text) => output.push(XmlNode::Text(text.clone())),
+        }
+    }
+    output
+}
+
+/// Render a typed substitution value as text
+fn render_value(value_type: u8, bytes: &[u8]) -> String {
+    if value_type & 0x80 != 0 {
+        return render_array(value_type & 0x7F, bytes);
+    }
+
+    match value_type {
+        0x01 => {
+            let units: Vec<u16> = bytes.chunks_exact(2).map(|c| u16::from_le_bytes([c[0], c[1]])).collect();
+            String::from_utf16_lossy(&units).trim_end_matches('\0').to_string()
+        }
+        0x02 => String::from_utf8_lossy(bytes).trim_end_matches('\0').to_string(),
+        0x03 => (bytes[0] as i8).to_string(),
+        0x04 => bytes[0].to_string(),
+        0x05 => read_u16(bytes, 0).map(|v| (v as i16).to_string()).unwrap_or_default(),
+        0x06 => read_u16(bytes, 0).map(|v| v.to_string()).unwrap_or_default(),
+        0x07 => read_u32(bytes, 0).map(|v| (v as i32).to_string()).unwrap_or_default(),
+        0x08 => read_u32(bytes, 0).map(|v| v.to_string()).unwrap_or_default(),
+        0x09 => read_u64(bytes, 0).map(|v| (v as i64).to_string()).unwrap_or_default(),
+        0x0A => read_u64(bytes, 0).map(|v| v.to_string()).unwrap_or_default(),
+        0x0B => read_u32(bytes, 0).map(|v| f32::from_bits(v).to_string()).unwrap_or_default(),
+        0x0C => read_u64(bytes, 0).map(|v| f64::from_bits(v).to_string()).unwrap_or_default(),
+        0x0D => read_u32(bytes, 0).map(|v| (v != 0).to_string()).unwrap_or_default(),
+        0x0F => format_guid(bytes),
+        0x10 | 0x14 | 0x15 => match bytes.len() {
+            4 => format!("{:#x}", read_u32(bytes, 0).unwrap_or(0)),
+            _ => format!("{:#x}", read_u64(bytes, 0).unwrap_or(0)),
+        },
+        0x11 => read_u64(bytes, 0)
+            .and_then(filetime_to_datetime)
+            .map(|dt| dt.to_rfc3339())
+            .unwrap_or_default(),
+        0x12 => systemtime(bytes).map(|dt| dt.to_rfc3339()).unwrap_or_default(),
+        0x13 => format_sid(bytes),
+        _ => bytes.iter().map(|b| format!("{:02X}", b)).collect(),
+    }
+}
+
+/// Render an array value (strings are null separated, other types fixed size)
+fn render_array(value_type: u8, bytes: &[u8]) -> String {
+    let element_size = match value_type {
+        0x01 => {
+            let units: Vec<u16> = bytes.chunks_exact(2).map(|c| u16::from_le_bytes([c[0], c[1]])).collect();
+            return units
+                .split(|&c| c == 0)
+                .filter(|s| !s.is_empty())
+                .map(String::from_utf16_lossy)
+                .collect::<Vec<_>>()
+                .join(", ");
+        }
+        _ => match fixed_value_size(value_type) {
+            Some(size) => size,
+            None => return bytes.iter().map(|b| format!("{:02X}", b)).collect(),
+        },
+    };
+    bytes
+        .chunks_exact(element_size)
+        .map(|element| render_value(value_type, element))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Size of a fixed-size value type (None for strings, binary, SIDs and size_t)
+fn fixed_value_size(value_type: u8) -> Option<usize> {
+    match value_type {
+        0x03 | 0x04 => Some(1),
+        0x05 | 0x06 => Some(2),
+        0x07 | 0x08 | 0x0B | 0x0D | 0x14 => Some(4),
+        0x09 | 0x0A | 0x0C | 0x11 | 0x15 => Some(8),
+        0x0F | 0x12 => Some(16),
+        _ => None,
+    }
+}
+
+/// Map the decoded Event element to structured fields
+fn record_from_xml(record_id: u64, written: Option<DateTime<Utc>>, event: &XmlNode) -> EvtxRecord {
+    let system = event.child("System");
+    let field = |name: &str| system.and_then(|s| s.child(name)).map(|n| n.text()).filter(|t| !t.is_empty());
+    let attribute = |name: &str, attribute: &str| system.and_then(|s| s.child(name)).and_then(|n| n.attribute(attribute));
+
+    let timestamp = attribute("TimeCreated", "SystemTime")
+        .and_then(|t| DateTime::parse_from_rfc3339(&t).ok())
+        .map(|t| t.with_timezone(&Utc))
+        .or(written);
+
+    let mut data = Vec::new();
+    if let Some(event_data) = event.child("EventData") {
+        for (index, item) in event_data.elements().enumerate() {
+            let name = item.attribute("Name").unwrap_or_else(|| format!("Data{}", index));
+            data.push((name, item.text()));
+        }
+    } else if let Some(user_data) = event.child("UserData") {
+        // UserData holds one provider-specific element whose children are the fields
+        for container in user_data.elements() {
+            for item in container.elements() {
+                data.push((item.name().to_string(), item.text()));
+            }
+        }
+    }
+
+    EvtxRecord {
+        record_id: field("EventRecordID").and_then(|v| v.parse().ok()).unwrap_or(record_id),
+        timestamp,
+        provider: attribute("Provider", "Name").unwrap_or_default(),
+        event_id: field("EventID").and_then(|v| v.parse().ok()).unwrap_or(0),
+        level: field("Level").and_then(|v| v.parse().ok()),
+        task: field("Task").and_then(|v| v.parse().ok()),
+        opcode: field("Opcode").and_then(|v| v.parse().ok()),
+        keywords: field("Keywords"),
+        channel: field("Channel").unwrap_or_default(),
+        computer: field("Computer").unwrap_or_default(),
+        user_id: attribute("Security", "UserID"),
+        process_id: attribute("Execution", "ProcessID").and_then(|v| v.parse().ok()),
+        thread_id: attribute("Execution", "ThreadID").and_then(|v| v.parse().ok()),
+        data,
+    }
+}
+
+impl EvtxRecord {
+    /// Concise description: channel, event ID, provider and the leading data fields
+    pub fn description(&self) -> String {
+        let fields: Vec<String> = self
+            .data
+            .iter()
+            .filter(|(_, value)| !value.is_empty() && value != "-")
+            .take(3)
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect();
+
+        let mut description = format!("{} {} ({})", self.channel, self.event_id, self.provider);
+        if !fields.is_empty() {
+            description.push_str(": ");
+            description.push_str(&fields.join(", "));
+        }
+        description
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::test_hive::utf16;
+
+    /// Append an inline name structure
+    fn inline_name(buf: &mut Vec<u8>, base: usize, name: &str) {
+        let offset = (base + buf.len() + 4) as u32;
+        buf.extend_from_slice(&offset.to_le_bytes());
+        buf.extend_from_slice(&0u32.to_le_bytes());
+        buf.extend_from_slice(&0u16.to_le_bytes());
+        buf.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        buf.extend(utf16(name));
+        buf.extend_from_slice(&0u16.to_le_bytes());
+    }
+
+    /// Element with one substitution as content: <name>%id%</name>
+    fn element_with_substitution(buf: &mut Vec<u8>, base: usize, name: &str, id: u16) {
+        buf.extend_from_slice(&[0x01, 0, 0, 0, 0, 0, 0]);
+        inline_name(buf, base, name);
+        buf.push(0x02);
+        buf.push(0x0E);
+        buf.extend_from_slice(&id.to_le_bytes());
+        buf.push(0x08);
+        buf.push(0x04);
+    }
+
+    /// Build a chunk-sized buffer holding one template-based record
+    fn build_chunk() -> Vec<u8> {
+        let base = CHUNK_HEADER_SIZE + 24;
+        let mut xml = vec![0x0F, 1, 1, 0];
+
+        // Template instance with an inline definition
+        xml.extend_from_slice(&[0x0C, 0x01]);
+        xml.extend_from_slice(&0u32.to_le_bytes());
+        let definition = base + xml.len() + 4;
+        xml.extend_from_slice(&(definition as u32).to_le_bytes());
+
+        let mut body = vec![0x0F, 1, 1, 0];
+        let body_base = definition + 24;
+        body.extend_from_slice(&[0x01, 0, 0, 0, 0, 0, 0]);
+        inline_name(&mut body, body_base, "Event");
+        body.push(0x02);
+        body.extend_from_slice(&[0x01, 0, 0, 0, 0, 0, 0]);
+        inline_name(&mut body, body_base, "System");
+        body.push(0x02);
+        element_with_substitution(&mut body, body_base, "EventID", 0);
+        element_with_substitution(&mut body, body_base, "Channel", 1);
+        body.push(0x04);
+        body.push(0x04);
+        body.push(0x00);
+
+        xml.extend_from_slice(&0u32.to_le_bytes());
+        xml.extend_from_slice(&[0u8; 16]);
+        xml.extend_from_slice(&(body.len() as u32).to_le_bytes());
+        xml.extend(body);
+
+        let channel = utf16("Security");
+        xml.extend_from_slice(&2u32.to_le_bytes());
+        xml.extend_from_slice(&[2, 0, 0x06, 0]);
+        xml.extend_from_slice(&(channel.len() as u16).to_le_bytes());
+        xml.extend_from_slice(&[0x01, 0]);
+        xml.extend_from_slice(&4624u16.to_le_bytes());
+        xml.extend(channel);
+        xml.push(0x00);
+
+        let mut chunk = vec![0u8; CHUNK_SIZE];
+        chunk[0..8].copy_from_slice(CHUNK_SIGNATURE);
+        let size = 24 + xml.len() + 4;
+        let record = CHUNK_HEADER_SIZE;
+        chunk[record..record + 4].copy_from_slice(&RECORD_SIGNATURE.to_le_bytes());
+        chunk[record + 4..record + 8].copy_from_slice(&(size as u32).to_le_bytes());
+        chunk[record + 8..record + 16].copy_from_slice(&42u64.to_le_bytes());
+        chunk[record + 16..record + 24].copy_from_slice(&132223104000000000u64.to_le_bytes());
+        chunk[record + 24..record + 24 + xml.len()].copy_from_slice(&xml);
+        chunk[48..52].copy_from_slice(&((record + size) as u32).to_le_bytes());
+        let records_crc = crc32fast::hash(&chunk[CHUNK_HEADER_SIZE..record + size]);
+        chunk[52..56].copy_from_slice(&records_crc.to_le_bytes());
+
+        let mut hasher = crc32fast::Hasher::new();
+        hasher.update(&chunk[0..120]);
+        hasher.update(&chunk[128..CHUNK_HEADER_SIZE]);
+        chunk[124..128].copy_from_slice(&hasher.finalize().to_le_bytes());
+        chunk
+    }
+
+    #[test]
+    fn test_template_substitution() {
+        let records = parse_chunk(&build_chunk()).unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].record_id, 42);
+        assert_eq!(records[0].event_id, 4624);
+        assert_eq!(records[0].channel, "Security");
+        assert!(records[0].timestamp.is_some());
+    }
+
+    #[test]
+    fn test_chunk_checksum_mismatch() {
+        let mut chunk = build_chunk();
+        chunk[100] ^= 0xFF;
+        assert!(parse_chunk(&chunk).is_err());
+    }
+
+    /// Decode a standalone fragment: <name>value</name> for each (name, value token bytes)
+    fn decode_values(values: &[(&str, Vec<u8>)]) -> Result<Vec<XmlNode>> {
+        let mut fragment = Vec::new();
+        for (name, value) in values {
+            fragment.extend_from_slice(&[0x01, 0, 0, 0, 0, 0, 0]);
+            inline_name(&mut fragment, 0, name);
+            fragment.push(0x02);
+            fragment.extend_from_slice(value);
+            fragment.push(0x04);
+        }
+        fragment.push(0x00);
+        let mut decoder = BinXmlDecoder { chunk: &fragment, templates: HashMap::new() };
+        decoder.parse_fragment(0, fragment.len())
+    }
+
+    #[test]
+    fn test_value_token_types() {
+        let mut text = vec![0x05, 0x01, 3, 0];
+        text.extend(utf16("abc"));
+        let mut level = vec![0x05, 0x08];
+        level.extend_from_slice(&4u32.to_le_bytes());
+        let mut time = vec![0x05, 0x11];
+        time.extend_from_slice(&132223104000000000u64.to_le_bytes());
+
+        let nodes = decode_values(&[("Data", text), ("Level", level), ("Time", time)]).unwrap();
+        let texts: Vec<String> = nodes.iter().map(|n| n.text()).collect();
+        assert_eq!(texts, ["abc", "4", "2020-01-01T00:00:00+00:00"]);
+
+        // Variable-size types other than strings carry no length and cannot be skipped
+        assert!(decode_values(&[("Binary", vec![0x05, 0x0E, 0xAA, 0xBB])]).is_err());
+        // Truncated fixed-size values are rejected instead of read past the fragment
+        assert!(decode_values(&[("Level", vec![0x05, 0x08, 0x04])]).is_err());
+    }
+
+    #[test]
+    fn test_render_values() {
+        let sid = [1u8, 2, 0, 0, 0, 0, 0, 5, 32, 0, 0, 0, 0x20, 0x02, 0, 0];
+        assert_eq!(format_sid(&sid), "S-1-5-32-544");
+        assert_eq!(render_value(0x08, &7u32.to_le_bytes()), "7");
+        assert_eq!(render_value(0x14, &0x1Fu32.to_le_bytes()), "0x1f");
+        assert_eq!(render_value(0x81, &utf16("a\0b\0")), "a, b");
+    }
+}
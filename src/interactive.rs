@@ -374,10 +374,16 @@ impl InteractiveViewer {
             let event_index = self.filtered_events[current_selection];
             if event_index < self.events.len() {
                 let event = &self.events[event_index];
-                let record = self.records.iter().find(|r| r.record_number == event.mft_record_number);
+                let record = self.records.iter().find(|r| Self::record_matches_event(r, event));
                 record.map(|r| self.build_details_content(r, event))
             } else { None }
         } else { None };
+
+        // 12 lines fit basic info, timestamps and ADS; metadata grows the pane up to half the screen
+        let max_details_height = self.terminal.size().map(|size| size.height / 2).unwrap_or(12).max(12);
+        let details_height = details_content
+            .as_ref()
+            .map_or(12, |content| (content.lines().count() as u16 + 2).clamp(12, max_details_height));
         
 self.terminal.draw(|f| {
             let full_area = f.area();
@@ -391,7 +397,7 @@ self.terminal.draw(|f| {
                 .constraints([
                     Constraint::Length(1), // Header
                     Constraint::Min(1),     // Table (takes most space)
-                    Constraint::Length(12), // Details pane (12 lines for basic info + timestamps + ADS info)
+                    Constraint::Length(details_height), // Details pane (basic info + timestamps + ADS info + metadata)
                     Constraint::Length(1),  // Footer
                 ])
                 .split(full_area);
@@ -620,6 +626,23 @@ self.terminal.draw(|f| {
     }
     
     
+    /// Whether a record produced a timeline event (record numbers alone are 0 for non-MFT sources)
+    fn record_matches_event(record: &crate::types::Event, event: &TimelineEvent) -> bool {
+        if record.record_number != event.mft_record_number {
+            return false;
+        }
+        if event.mft_record_number != 0 {
+            return true;
+        }
+        let timestamps = [&record.timestamps, &record.fn_timestamps];
+        record.filename.as_deref().unwrap_or("N/A") == event.filename
+            && record.location.as_deref().unwrap_or("\\") == event.location
+            && record.event_source == event.event_source
+            && timestamps.iter().any(|ts| {
+                [ts.created, ts.modified, ts.mft_modified, ts.accessed].contains(&Some(event.timestamp))
+            })
+    }
+
     /// Build the content string for the details popup
     fn build_details_content(&self, record: &crate::types::Event, _event: &TimelineEvent) -> String {
         let mut content = String::new();
@@ -696,6 +719,14 @@ record.file_size.map_or("Unknown".to_string(), |s| format!("{} bytes", s))
         } else {
             content.push_str("Alternative Data Streams: None");
         }
+
+        // Source-specific fields (LNK extra data, event log fields, registry values, ...)
+        if !record.metadata.is_empty() {
+            content.push_str("\n\nDetails:\n");
+            for (label, value) in &record.metadata {
+                content.push_str(&format!("{:>14}: {}\n", label, value));
+            }
+        }
         
         // Details are always visible in the bottom pane
         
@@ -6,8 +6,11 @@
 //! - .customDestinations-ms files
 //! - Registry MRU locations from NTUSER.DAT
 
+use crate::droid::ObjectId;
 use crate::error::{Error, Result};
 use crate::ole::OleCompoundDocument;
+use crate::lnk_parser::{link_property_timestamps, ExtraDataType};
+use crate::registry::format_guid;
 use crate::shell_item::ItemIdList;
 use crate::types::EventTimestamps;
 use byteorder::{LittleEndian, ReadBytesExt};
@@ -31,6 +34,10 @@ pub struct JumplistEntry {
     pub access_count: Option<u32>,
     /// Timestamps
     pub timestamps: EventTimestamps,
+    /// Matching DestList entry (automatic destinations only)
+    pub dest_list: Option<DestListEntry>,
+    /// Timestamp properties from the link's property stores (e.g. System.Photo.DateTaken)
+    pub property_timestamps: Vec<(String, DateTime<Utc>)>,
 }
 
 /// Jumplist parser for automatic and custom destinations files
@@ -50,35 +57,62 @@ impl JumplistParser {
                 let mut entries = Vec::new();
                 
                 // Parse DestList stream for metadata
-                let mut dest_list_entries = Vec::new();
-                if let Some(destlist_data) = ole_doc.get_stream("DestList") {
-                    dest_list_entries = self.parse_dest_list_stream(destlist_data)?;
-                }
+                let dest_list = match ole_doc.get_stream("DestList") {
+                    Some(destlist_data) => DestList::parse(destlist_data).unwrap_or_else(|e| {
+                        eprintln!("⚠️  Failed to parse DestList stream: {}", e);
+                        DestList::default()
+                    }),
+                    None => DestList::default(),
+                };
                 
-                // Extract and parse LNK files from numbered streams
+                // Extract and parse LNK files from streams named by entry number (hex)
+                let mut joined = Vec::new();
                 for stream_name in ole_doc.list_streams() {
-                    // LNK streams are typically numbered (1, 2, 3, etc.)
-                    if stream_name.chars().all(|c| c.is_ascii_digit()) {
-                        if let Some(lnk_data) = ole_doc.get_stream(&stream_name) {
-                            if let Ok(shell_link) = ShellLink::parse(lnk_data) {
-                                // Find corresponding DestList entry by stream number
-                                let stream_num: usize = stream_name.parse().unwrap_or(0);
-                                let dest_entry = dest_list_entries.get(stream_num.saturating_sub(1));
-                                
-                                let entry = JumplistEntry {
-                                    target_path: shell_link.target_path.clone(),
-                                    file_size: Some(shell_link.header.file_size as u64),
-                                    file_attributes: Some(shell_link.header.file_attributes),
-                                    app_id: None, // Will be set by caller
-                                    access_count: dest_entry.map(|d| d.access_count),
-                                    timestamps: shell_link.timestamps.clone(),
-                                };
-                                entries.push(entry);
+                    if !is_lnk_stream_name(&stream_name) {
+                        continue;
+                    }
+                    if let Some(lnk_data) = ole_doc.get_stream(&stream_name) {
+                        if let Ok(shell_link) = ShellLink::parse(lnk_data) {
+                            let dest_entry = dest_list.entry_for_stream(&stream_name).cloned();
+                            if let Some(dest_entry) = &dest_entry {
+                                joined.push(dest_entry.entry_number);
                             }
+                            
+                            let entry = JumplistEntry {
+                                target_path: shell_link
+                                    .target_path
+                                    .clone()
+                                    .or_else(|| dest_entry.as_ref().map(|d| d.path.clone())),
+                                file_size: Some(shell_link.header.file_size as u64),
+                                file_attributes: Some(shell_link.header.file_attributes),
+                                app_id: None, // Will be set by caller
+                                access_count: dest_entry.as_ref().and_then(|d| d.access_count),
+                                timestamps: shell_link.timestamps.clone(),
+                                dest_list: dest_entry,
+                                property_timestamps: shell_link.property_timestamps(),
+                            };
+                            entries.push(entry);
                         }
                     }
                 }
                 
+                // DestList entries whose LNK stream is missing still record a path and last use
+                for dest_entry in dest_list.entries.iter().filter(|d| !joined.contains(&d.entry_number)) {
+                    entries.push(JumplistEntry {
+                        target_path: Some(dest_entry.path.clone()),
+                        file_size: None,
+                        file_attributes: None,
+                        app_id: None,
+                        access_count: dest_entry.access_count,
+                        timestamps: EventTimestamps {
+                            accessed: dest_entry.last_modified,
+                            ..EventTimestamps::default()
+                        },
+                        dest_list: Some(dest_entry.clone()),
+                        property_timestamps: Vec::new(),
+                    });
+                }
+                
                 Ok(entries)
             },
             Err(_) => {
@@ -88,12 +122,14 @@ impl JumplistParser {
                     match ShellLink::parse(data) {
                         Ok(shell_link) => {
                             let entry = JumplistEntry {
+                                property_timestamps: shell_link.property_timestamps(),
                                 target_path: shell_link.target_path,
                                 file_size: Some(shell_link.header.file_size as u64),
                                 file_attributes: Some(shell_link.header.file_attributes),
                                 app_id: None,
                                 access_count: None,
                                 timestamps: shell_link.timestamps,
+                                dest_list: None,
                             };
                             Ok(vec![entry])
                         },
@@ -108,6 +144,29 @@ impl JumplistParser {
 
     /// Parse custom destinations file (.customDestinations-ms)
     pub fn parse_custom_destinations(&self, data: &[u8]) -> Result<Vec<JumplistEntry>> {
+        let custom = match CustomDestinations::parse(data, "") {
+            Ok(custom) => custom,
+            // Damaged or unknown layouts: fall back to scanning for LNK signatures
+            Err(_) => return self.scan_custom_destinations(data),
+        };
+
+        Ok(custom
+            .links()
+            .map(|(_, link)| JumplistEntry {
+                target_path: link.target_path.clone(),
+                file_size: Some(link.header.file_size as u64),
+                file_attributes: Some(link.header.file_attributes),
+                app_id: None, // Will be set by caller
+                access_count: None, // Custom destinations don't have access counts
+                timestamps: link.timestamps.clone(),
+                dest_list: None,
+                property_timestamps: link.property_timestamps(),
+            })
+            .collect())
+    }
+
+    /// Scan custom destinations data for embedded LNK signatures
+    fn scan_custom_destinations(&self, data: &[u8]) -> Result<Vec<JumplistEntry>> {
         // Custom destinations are binary files with embedded LNK data
         let mut entries = Vec::new();
         let _cursor = Cursor::new(data);
@@ -143,12 +202,14 @@ impl JumplistParser {
                                 // Parse the LNK file
                                 if let Ok(shell_link) = ShellLink::parse(lnk_data) {
                                     let entry = JumplistEntry {
+                                        property_timestamps: shell_link.property_timestamps(),
                                         target_path: shell_link.target_path,
                                         file_size: Some(shell_link.header.file_size as u64),
                                         file_attributes: Some(shell_link.header.file_attributes),
                                         app_id: None, // Will be set by caller
                                         access_count: None, // Custom destinations don't have access counts
                                         timestamps: shell_link.timestamps,
+                                        dest_list: None,
                                     };
                                     entries.push(entry);
                                 }
@@ -179,6 +240,8 @@ impl JumplistParser {
                 app_id: None,
                 access_count: None,
                 timestamps: EventTimestamps::default(),
+                dest_list: None,
+                property_timestamps: Vec::new(),
             };
             entries.push(entry);
         }
@@ -186,93 +249,6 @@ impl JumplistParser {
         Ok(entries)
     }
 
-    /// Parse DestList stream from automatic destinations
-    fn parse_dest_list_stream(&self, data: &[u8]) -> Result<Vec<DestListEntry>> {
-        let mut entries = Vec::new();
-        let mut cursor = Cursor::new(data);
-        
-        // Skip header (32 bytes) if present
-        if data.len() > 32 {
-            cursor.seek(SeekFrom::Start(32))?;
-        }
-        
-        // Parse entries until we reach the end
-        while cursor.position() + 24 < data.len() as u64 {
-            match self.parse_dest_list_entry(&mut cursor) {
-                Ok(entry) => entries.push(entry),
-                Err(_) => break, // Stop on parsing errors
-            }
-        }
-        
-        Ok(entries)
-    }
-
-    /// Parse individual DestList entry
-    fn parse_dest_list_entry(&self, cursor: &mut Cursor<&[u8]>) -> Result<DestListEntry> {
-        let size = cursor.read_u32::<LittleEndian>()?;
-        let entry_type = cursor.read_u32::<LittleEndian>()?;
-        let pin_status = cursor.read_u32::<LittleEndian>()?;
-        let access_count = cursor.read_u32::<LittleEndian>()?;
-        let last_access_time = cursor.read_u64::<LittleEndian>()?;
-        
-        // Read remaining entry data (variable length)
-        let remaining_size = size.saturating_sub(24) as usize;
-        let mut entry_id = vec![0u8; remaining_size];
-        if remaining_size > 0 {
-            cursor.read_exact(&mut entry_id)?;
-        }
-        
-        // Try to extract target path from entry ID
-        let target_path = self.extract_target_path_from_entry_id(&entry_id);
-        
-        Ok(DestListEntry {
-            size,
-            entry_type,
-            pin_status,
-            access_count,
-            last_access_time,
-            entry_id,
-            target_path,
-        })
-    }
-
-    /// Extract target path from DestList entry ID data
-    fn extract_target_path_from_entry_id(&self, entry_id: &[u8]) -> Option<String> {
-        // Try parsing as ItemIdList first
-        if let Ok(idlist) = ItemIdList::parse(entry_id) {
-            if let Some(path) = idlist.full_path {
-                return Some(path);
-            }
-        }
-        
-        // Fallback: look for readable strings in the data
-        if let Ok(text) = String::from_utf8(entry_id.to_vec()) {
-            let cleaned = text.trim().trim_matches('\0');
-            if !cleaned.is_empty() && cleaned.len() > 3 {
-                return Some(cleaned.to_string());
-            }
-        }
-        
-        // Try UTF-16 parsing
-        if entry_id.len() >= 4 && entry_id.len() % 2 == 0 {
-            let utf16_data: Vec<u16> = entry_id
-                .chunks_exact(2)
-                .map(|chunk| u16::from_le_bytes([chunk[0], chunk[1]]))
-                .take_while(|&c| c != 0)
-                .collect();
-            
-            if !utf16_data.is_empty() {
-                let text = String::from_utf16_lossy(&utf16_data);
-                let cleaned = text.trim();
-                if !cleaned.is_empty() && cleaned.len() > 3 {
-                    return Some(cleaned.to_string());
-                }
-            }
-        }
-        
-        None
-    }
-
     /// Estimate the size of a LNK file from its header and structure
     fn estimate_lnk_size(&self, data: &[u8]) -> Option<usize> {
         if data.len() < 76 {
@@ -502,12 +478,12 @@ pub struct AutomaticDestinations {
 pub struct CustomDestinations {
     /// File header
     pub header: DestinationsHeader,
-    /// Custom categories
+    /// Categories with their embedded LNK files
     pub categories: Vec<CustomCategory>,
-    /// Embedded LNK files
-    pub lnk_files: Vec<ShellLink>,
     /// Application identifier (CRC64 hash)
     pub app_id: String,
+    /// Whether the file ended with the footer signature
+    pub footer_valid: bool,
 }
 
 /// Destinations file header
@@ -521,32 +497,81 @@ pub struct DestinationsHeader {
     pub pin_count: u32,
 }
 
+/// DestList stream header
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DestListHeader {
+    /// Format version (1 = Windows 7/8, 3 and 4 = Windows 10+)
+    pub version: u32,
+    /// Number of entries
+    pub entry_count: u32,
+    /// Number of pinned entries
+    pub pinned_count: u32,
+    /// Last issued entry number
+    pub last_entry_number: u32,
+}
+
+/// DestList stream
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DestList {
+    /// Stream header
+    pub header: DestListHeader,
+    /// Entries in stream order
+    pub entries: Vec<DestListEntry>,
+}
+
 /// DestList stream entry
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DestListEntry {
-    /// Entry size
-    pub size: u32,
-    /// Entry type
-    pub entry_type: u32,
-    /// Pin status
-    pub pin_status: u32,
-    /// Access count
-    pub access_count: u32,
-    /// Last access time
-    pub last_access_time: u64,
-    /// Entry identifier
-    pub entry_id: Vec<u8>,
+    /// Entry checksum (CRC64)
+    pub checksum: u64,
+    /// Current volume droid GUID
+    pub volume_droid: String,
+    /// Current file droid GUID
+    pub file_droid: String,
+    /// Birth volume droid GUID
+    pub birth_volume_droid: String,
+    /// Birth file droid GUID
+    pub birth_file_droid: String,
+    /// Decoded current file droid (version 1 UUIDs only)
+    pub file_object_id: Option<ObjectId>,
+    /// Decoded birth file droid (version 1 UUIDs only)
+    pub birth_file_object_id: Option<ObjectId>,
+    /// NetBIOS name of the machine the target was on
+    pub hostname: String,
+    /// Entry number (the LNK stream name in hex)
+    pub entry_number: u32,
+    /// Last time the entry was used
+    pub last_modified: Option<DateTime<Utc>>,
+    /// Pin position (-1 when not pinned)
+    pub pin_status: i32,
+    /// Access count (Windows 10+ only)
+    pub access_count: Option<u32>,
+    /// MRU position (0 = most recently used)
+    pub mru_position: usize,
     /// Target path
-    pub target_path: Option<String>,
+    pub path: String,
+}
+
+/// Category type in customDestinations
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CustomCategoryType {
+    /// Application-defined category with a name
+    Custom,
+    /// Known category (Frequent or Recent), which holds no entries
+    Known,
+    /// Custom tasks
+    Tasks,
 }
 
 /// Custom category in customDestinations
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CustomCategory {
-    /// Category name
+    /// Category name ("Frequent", "Recent" and "Tasks" for non-custom categories)
     pub name: String,
-    /// Category entries
-    pub entries: Vec<String>,
+    /// Category type
+    pub category_type: CustomCategoryType,
+    /// Embedded LNK files
+    pub entries: Vec<crate::lnk_parser::ShellLink>,
 }
 
 /// Registry MRU entry
@@ -657,6 +682,8 @@ pub fn get_mru_locations() -> Vec<&'static str> {
     vec![
         "Software\\Microsoft\\Windows\\Shell\\BagMRU",
         "Software\\Classes\\Local Settings\\Software\\Microsoft\\Windows\\Shell\\BagMRU",
+        // UsrClass.dat, where the Local Settings key is the hive root
+        "Local Settings\\Software\\Microsoft\\Windows\\Shell\\BagMRU",
         "Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\RecentDocs",
         "Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\ComDlg32\\OpenSavePidlMRU",
         "Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\ComDlg32\\LastVisitedPidlMRU",
@@ -665,6 +692,9 @@ pub fn get_mru_locations() -> Vec<&'static str> {
         "Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\Streams",
         "Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\StreamMRU",
         "Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\WordWheelQuery",
+        "Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\RunMRU",
+        "Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\TypedPaths",
+        "Software\\Microsoft\\Internet Explorer\\TypedURLs",
         "Software\\Microsoft\\Windows\\CurrentVersion\\Search\\JumplistData",
         "Software\\Microsoft\\Windows\\CurrentVersion\\Search\\RecentApps",
         "Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\Taskband\\Favorites",
@@ -766,6 +796,16 @@ impl ShellLink {
         
         Ok(lnk)
     }
+    
+    /// Timestamp properties from the ExtraData property stores and the shell items of the link
+    pub fn property_timestamps(&self) -> Vec<(String, DateTime<Utc>)> {
+        let extra_data: Vec<ExtraDataType> = self
+            .extra_data
+            .iter()
+            .map(|block| ExtraDataType::parse(block.signature, &block.data))
+            .collect();
+        link_property_timestamps(&extra_data, self.id_list.as_ref())
+    }
 }
 
 impl ShellLinkHeader {
@@ -922,16 +962,15 @@ impl AutomaticDestinations {
         let ole_doc = OleCompoundDocument::parse(data)?;
         
         // Find DestList stream
-        let mut dest_list = Vec::new();
-        if let Some(destlist_data) = ole_doc.get_stream("DestList") {
-            dest_list = Self::parse_dest_list(destlist_data)?;
-        }
+        let dest_list = match ole_doc.get_stream("DestList") {
+            Some(destlist_data) => DestList::parse(destlist_data)?,
+            None => DestList::default(),
+        };
         
-        // Parse embedded LNK files
+        // Parse embedded LNK files (streams are named by the entry number in hex)
         let mut lnk_files = Vec::new();
         for stream_name in ole_doc.list_streams() {
-            // LNK streams are typically numbered (1, 2, 3, etc.)
-            if stream_name.chars().all(|c| c.is_ascii_digit()) {
+            if is_lnk_stream_name(&stream_name) {
                 if let Some(lnk_data) = ole_doc.get_stream(&stream_name) {
                     if let Ok(lnk) = ShellLink::parse(lnk_data) {
                         lnk_files.push(lnk);
@@ -942,117 +981,291 @@ impl AutomaticDestinations {
         
         Ok(AutomaticDestinations {
             header: DestinationsHeader {
-                version: 1,
-                entry_count: dest_list.len() as u32,
-                pin_count: dest_list.iter().filter(|e| e.pin_status > 0).count() as u32,
+                version: dest_list.header.version,
+                entry_count: dest_list.header.entry_count,
+                pin_count: dest_list.header.pinned_count,
             },
-            dest_list,
+            dest_list: dest_list.entries,
             lnk_files,
             app_id,
         })
     }
-    
-    /// Parse DestList stream
-    fn parse_dest_list(data: &[u8]) -> Result<Vec<DestListEntry>> {
-        let mut entries = Vec::new();
+}
+
+/// Size of the DestList stream header
+const DEST_LIST_HEADER_SIZE: usize = 32;
+
+impl DestList {
+    /// Parse a DestList stream (version 1 for Windows 7/8, version 3/4 for Windows 10+)
+    pub fn parse(data: &[u8]) -> Result<Self> {
         let mut cursor = Cursor::new(data);
-        
-        // Skip header if present (varies by version)
-        if data.len() > 32 {
-            cursor.seek(SeekFrom::Start(32))?;
-        }
-        
-        // Parse entries
-        while cursor.position() + 32 < data.len() as u64 {
-            if let Ok(entry) = Self::parse_dest_list_entry(&mut cursor) {
-                entries.push(entry);
-            } else {
+        let version = cursor.read_u32::<LittleEndian>()?;
+        let entry_count = cursor.read_u32::<LittleEndian>()?;
+        let pinned_count = cursor.read_u32::<LittleEndian>()?;
+        let _counter = cursor.read_f32::<LittleEndian>()?;
+        let last_entry_number = cursor.read_u32::<LittleEndian>()?;
+
+        // (offset of the path length, trailing bytes after the path)
+        let (path_offset, trailer) = match version {
+            1 => (112, 0),
+            3 | 4 => (128, 4),
+            _ => return Err(Error::ParseError(format!("Unsupported DestList version {}", version))),
+        };
+
+        let mut entries = Vec::new();
+        let mut offset = DEST_LIST_HEADER_SIZE;
+        while entries.len() < entry_count as usize {
+            let Some(entry) = data.get(offset..) else {
                 break;
-            }
+            };
+            let Some(path_chars) = entry.get(path_offset..path_offset + 2).map(|b| u16::from_le_bytes([b[0], b[1]]) as usize) else {
+                break;
+            };
+            let path_end = path_offset + 2 + path_chars * 2;
+            let Some(path) = entry.get(path_offset + 2..path_end) else {
+                break;
+            };
+
+            let field = |at: usize| u32::from_le_bytes([entry[at], entry[at + 1], entry[at + 2], entry[at + 3]]);
+            entries.push(DestListEntry {
+                checksum: u64::from_le_bytes(entry[0..8].try_into().unwrap_or_default()),
+                volume_droid: format_guid(&entry[8..24]),
+                file_droid: format_guid(&entry[24..40]),
+                birth_volume_droid: format_guid(&entry[40..56]),
+                birth_file_droid: format_guid(&entry[56..72]),
+                file_object_id: ObjectId::parse(&entry[24..40]),
+                birth_file_object_id: ObjectId::parse(&entry[56..72]),
+                hostname: String::from_utf8_lossy(&entry[72..88]).trim_end_matches('\0').to_string(),
+                entry_number: field(88),
+                last_modified: filetime_to_datetime(u64::from_le_bytes(entry[100..108].try_into().unwrap_or_default())),
+                pin_status: field(108) as i32,
+                access_count: (version >= 3).then(|| field(116)),
+                mru_position: 0,
+                path: String::from_utf16_lossy(
+                    &path.chunks_exact(2).map(|c| u16::from_le_bytes([c[0], c[1]])).collect::<Vec<_>>(),
+                ),
+            });
+            offset += path_end + trailer;
         }
-        
-        Ok(entries)
-    }
-    
-    /// Parse individual DestList entry
-    fn parse_dest_list_entry(cursor: &mut Cursor<&[u8]>) -> Result<DestListEntry> {
-        let size = cursor.read_u32::<LittleEndian>()?;
-        let entry_type = cursor.read_u32::<LittleEndian>()?;
-        let pin_status = cursor.read_u32::<LittleEndian>()?;
-        let access_count = cursor.read_u32::<LittleEndian>()?;
-        let last_access_time = cursor.read_u64::<LittleEndian>()?;
-        
-        // Read entry ID (variable length)
-        let remaining_size = size.saturating_sub(24) as usize;
-        let mut entry_id = vec![0u8; remaining_size];
-        cursor.read_exact(&mut entry_id)?;
-        
-        // Try to extract target path from entry ID (simplified)
-        let target_path = Self::extract_target_path(&entry_id);
-        
-        Ok(DestListEntry {
-            size,
-            entry_type,
-            pin_status,
-            access_count,
-            last_access_time,
-            entry_id,
-            target_path,
+
+        // Rank entries by last use (0 = most recent)
+        let mut order: Vec<usize> = (0..entries.len()).collect();
+        order.sort_by_key(|&i| std::cmp::Reverse(entries[i].last_modified));
+        for (position, index) in order.into_iter().enumerate() {
+            entries[index].mru_position = position;
+        }
+
+        Ok(DestList {
+            header: DestListHeader {
+                version,
+                entry_count,
+                pinned_count,
+                last_entry_number,
+            },
+            entries,
         })
     }
-    
-    /// Extract target path from entry ID data
-    fn extract_target_path(entry_id: &[u8]) -> Option<String> {
-        // Entry ID often contains shell item data
-        if let Ok(idlist) = ItemIdList::parse(entry_id) {
-            return idlist.full_path;
-        }
-        
-        // Fallback: try to find readable strings
-        if let Ok(text) = String::from_utf8(entry_id.to_vec()) {
-            if !text.trim().is_empty() {
-                return Some(text.trim().to_string());
-            }
-        }
-        
-        None
+
+    /// Entry whose number matches an LNK stream name
+    pub fn entry_for_stream(&self, stream_name: &str) -> Option<&DestListEntry> {
+        let number = u32::from_str_radix(stream_name, 16).ok()?;
+        self.entries.iter().find(|e| e.entry_number == number)
     }
 }
 
+impl DestListEntry {
+    /// Whether the entry is pinned
+    pub fn is_pinned(&self) -> bool {
+        self.pin_status >= 0
+    }
+}
+
+/// Whether an OLE stream name is an LNK stream (entry number in hex)
+fn is_lnk_stream_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Category and file footer signature in customDestinations
+const CUSTOM_DESTINATIONS_FOOTER: u32 = 0xBABF_FBAB;
+
+/// Shell link CLSID {00021401-0000-0000-C000-000000000046} preceding each embedded LNK
+const SHELL_LINK_CLSID: [u8; 16] = [
+    0x01, 0x14, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0xC0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x46,
+];
+
 impl CustomDestinations {
     /// Parse .customDestinations-ms file
-    pub fn parse(_data: &[u8], filename: &str) -> Result<Self> {
+    pub fn parse(data: &[u8], filename: &str) -> Result<Self> {
         // Extract app ID from filename
-        let app_id = filename.replace(".customDestinations-ms", "").to_uppercase();
-        
-        // This is a simplified implementation
+        let name = filename.rsplit(['/', '\\']).next().unwrap_or(filename);
+        let app_id = name.replace(".customDestinations-ms", "").to_uppercase();
+
+        let mut cursor = Cursor::new(data);
+        let version = cursor.read_u32::<LittleEndian>()?;
+        let category_count = cursor.read_u32::<LittleEndian>()?;
+        let _unknown = cursor.read_u32::<LittleEndian>()?;
+        if version != 2 {
+            return Err(Error::ParseError(format!("Unsupported customDestinations version {}", version)));
+        }
+
+        let lnk_parser = crate::lnk_parser::LnkParser::new();
+        let mut categories = Vec::new();
+        for _ in 0..category_count {
+            let category = match cursor.read_u32::<LittleEndian>()? {
+                0 => {
+                    let chars = cursor.read_u16::<LittleEndian>()? as usize;
+                    let mut name = vec![0u16; chars];
+                    cursor.read_u16_into::<LittleEndian>(&mut name)?;
+                    let count = cursor.read_u32::<LittleEndian>()?;
+                    CustomCategory {
+                        name: String::from_utf16_lossy(&name),
+                        category_type: CustomCategoryType::Custom,
+                        entries: Self::parse_entries(&mut cursor, count, &lnk_parser)?,
+                    }
+                }
+                1 => {
+                    let name = match cursor.read_u32::<LittleEndian>()? {
+                        1 => "Frequent".to_string(),
+                        2 => "Recent".to_string(),
+                        other => format!("Known category {}", other),
+                    };
+                    CustomCategory { name, category_type: CustomCategoryType::Known, entries: Vec::new() }
+                }
+                2 => {
+                    let count = cursor.read_u32::<LittleEndian>()?;
+                    CustomCategory {
+                        name: "Tasks".to_string(),
+                        category_type: CustomCategoryType::Tasks,
+                        entries: Self::parse_entries(&mut cursor, count, &lnk_parser)?,
+                    }
+                }
+                other => return Err(Error::ParseError(format!("Unknown customDestinations category type {}", other))),
+            };
+            categories.push(category);
+
+            // Each category is terminated by the footer signature
+            if Self::peek_footer(&cursor) {
+                cursor.seek(SeekFrom::Current(4))?;
+            }
+        }
+
+        // The last category's footer doubles as the file footer
+        let footer_valid = cursor.position() as usize == data.len()
+            && data.len() >= 4
+            && data[data.len() - 4..] == CUSTOM_DESTINATIONS_FOOTER.to_le_bytes();
+        let entry_count = categories.iter().map(|c| c.entries.len() as u32).sum();
+
         Ok(CustomDestinations {
             header: DestinationsHeader {
-                version: 1,
-                entry_count: 0,
+                version,
+                entry_count,
                 pin_count: 0,
             },
-            categories: Vec::new(),
-            lnk_files: Vec::new(),
+            categories,
             app_id,
+            footer_valid,
         })
     }
+
+    /// Parse `count` CLSID-prefixed shell links
+    fn parse_entries(
+        cursor: &mut Cursor<&[u8]>,
+        count: u32,
+        lnk_parser: &crate::lnk_parser::LnkParser,
+    ) -> Result<Vec<crate::lnk_parser::ShellLink>> {
+        let mut entries = Vec::new();
+        for _ in 0..count {
+            let mut clsid = [0u8; 16];
+            cursor.read_exact(&mut clsid)?;
+            if clsid != SHELL_LINK_CLSID {
+                return Err(Error::ParseError("Unsupported customDestinations entry type".to_string()));
+            }
+
+            let data = *cursor.get_ref();
+            let start = cursor.position() as usize;
+            let (link, size) = lnk_parser.parse_embedded(&data[start..])?;
+            cursor.seek(SeekFrom::Start((start + size) as u64))?;
+            entries.push(link);
+        }
+        Ok(entries)
+    }
+
+    /// Whether the footer signature is next
+    fn peek_footer(cursor: &Cursor<&[u8]>) -> bool {
+        let position = cursor.position() as usize;
+        cursor
+            .get_ref()
+            .get(position..position + 4)
+            .is_some_and(|bytes| bytes == CUSTOM_DESTINATIONS_FOOTER.to_le_bytes())
+    }
+
+    /// Embedded links with their category
+    pub fn links(&self) -> impl Iterator<Item = (&CustomCategory, &crate::lnk_parser::ShellLink)> {
+        self.categories
+            .iter()
+            .flat_map(|category| category.entries.iter().map(move |link| (category, link)))
+    }
 }
 
 /// Parse registry MRU data
 pub fn parse_registry_mru(key_path: &str, value_name: &str, data: &[u8]) -> RegistryMruEntry {
+    let key_lower = key_path.to_lowercase();
+    
+    let parsed_path = if key_lower.contains("recentdocs") {
+        // UTF-16 file name followed by a shell item for the shortcut in Recent
+        read_mru_string(data).map(|(name, _)| name)
+    } else if key_lower.contains("opensavepidlmru") {
+        ItemIdList::parse_pidl(data).ok().and_then(|idlist| idlist.full_path)
+    } else if key_lower.contains("lastvisitedpidlmru") {
+        // UTF-16 executable name followed by the PIDL of the last folder it used
+        read_mru_string(data).map(|(exe, consumed)| {
+            match ItemIdList::parse_pidl(&data[consumed..]).ok().and_then(|idlist| idlist.full_path) {
+                Some(folder) => format!("{} ({})", folder, exe),
+                None => exe,
+            }
+        })
+    } else if key_lower.contains("bagmru") {
+        // Each BagMRU value is a single shell item naming one level of the folder hierarchy
+        ItemIdList::parse_pidl(data).ok().and_then(|idlist| idlist.full_path)
+    } else if key_lower.contains("runmru") {
+        // Commands are stored with a trailing "\1"
+        read_mru_string(data).map(|(command, _)| command.trim_end_matches("\\1").to_string())
+    } else if key_lower.contains("wordwheelquery") || key_lower.contains("typedpaths") || key_lower.contains("typedurls") {
+        // Search terms, typed paths and typed URLs are plain UTF-16 strings
+        read_mru_string(data).map(|(text, _)| text)
+    } else {
+        None
+    };
+    
     RegistryMruEntry {
         key_path: key_path.to_string(),
         value_name: value_name.to_string(),
         data: data.to_vec(),
-        parsed_path: None, // TODO: Parse based on MRU type
+        parsed_path,
         last_modified: None,
     }
 }
 
+/// Read a null-terminated UTF-16 string, returning it with the number of bytes consumed
+fn read_mru_string(data: &[u8]) -> Option<(String, usize)> {
+    let chars: Vec<u16> = data
+        .chunks_exact(2)
+        .map(|chunk| u16::from_le_bytes([chunk[0], chunk[1]]))
+        .take_while(|&c| c != 0)
+        .collect();
+    
+    if chars.is_empty() {
+        return None;
+    }
+    
+    let consumed = ((chars.len() + 1) * 2).min(data.len());
+    Some((String::from_utf16_lossy(&chars), consumed))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::registry::test_hive::{utf16, utf16z};
     
     #[test]
     fn test_filetime_conversion() {
@@ -1066,6 +1279,143 @@ mod tests {
         assert!(dt.is_none());
     }
     
+    #[test]
+    fn test_parse_run_mru() {
+        let data = utf16z("cmd\\1");
+        let entry = parse_registry_mru("Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\RunMRU", "a", &data);
+        assert_eq!(entry.parsed_path, Some("cmd".to_string()));
+    }
+
+    #[test]
+    fn test_parse_word_wheel_query() {
+        let data = utf16z("invoice.pdf");
+        let entry = parse_registry_mru("Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\WordWheelQuery", "0", &data);
+        assert_eq!(entry.parsed_path, Some("invoice.pdf".to_string()));
+    }
+    
+    /// Minimal shell link with a Unicode name string and an empty extra data section
+    fn build_lnk(name: &str) -> Vec<u8> {
+        let mut lnk = vec![0u8; 76];
+        lnk[0..4].copy_from_slice(&0x4Cu32.to_le_bytes());
+        lnk[4..20].copy_from_slice(&SHELL_LINK_CLSID);
+        lnk[20..24].copy_from_slice(&0x84u32.to_le_bytes()); // HasName | IsUnicode
+        lnk[28..36].copy_from_slice(&132223104000000000u64.to_le_bytes());
+        lnk.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        lnk.extend(utf16(name));
+        lnk.extend_from_slice(&0u32.to_le_bytes());
+        lnk
+    }
+
+    #[test]
+    fn test_parse_custom_destinations() {
+        let mut data = Vec::new();
+        for value in [2u32, 3, 0] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        // Custom category "Pinned" with one link
+        data.extend_from_slice(&0u32.to_le_bytes());
+        data.extend_from_slice(&6u16.to_le_bytes());
+        data.extend(utf16("Pinned"));
+        data.extend_from_slice(&1u32.to_le_bytes());
+        data.extend_from_slice(&SHELL_LINK_CLSID);
+        data.extend(build_lnk("report.docx"));
+        data.extend_from_slice(&CUSTOM_DESTINATIONS_FOOTER.to_le_bytes());
+        // Known category (Recent)
+        data.extend_from_slice(&1u32.to_le_bytes());
+        data.extend_from_slice(&2u32.to_le_bytes());
+        data.extend_from_slice(&CUSTOM_DESTINATIONS_FOOTER.to_le_bytes());
+        // Tasks with one link
+        data.extend_from_slice(&2u32.to_le_bytes());
+        data.extend_from_slice(&1u32.to_le_bytes());
+        data.extend_from_slice(&SHELL_LINK_CLSID);
+        data.extend(build_lnk("New window"));
+        data.extend_from_slice(&CUSTOM_DESTINATIONS_FOOTER.to_le_bytes());
+
+        let custom = CustomDestinations::parse(&data, "Recent/7e4dca80246863e3.customDestinations-ms").unwrap();
+        assert_eq!(custom.app_id, "7E4DCA80246863E3");
+        assert!(custom.footer_valid);
+        assert_eq!(custom.header.entry_count, 2);
+        let names: Vec<&str> = custom.categories.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["Pinned", "Recent", "Tasks"]);
+
+        let links: Vec<_> = custom.links().collect();
+        assert_eq!(links[0].1.target_path.as_deref(), Some("report.docx"));
+        assert_eq!(links[1].0.category_type, CustomCategoryType::Tasks);
+        assert!(links[1].1.timestamps.created.is_some());
+    }
+
+    /// DestList entry with the given number, FILETIME, pin status and path
+    fn build_dest_list_entry(version: u32, number: u32, filetime: u64, pin: i32, path: &str) -> Vec<u8> {
+        let path_offset = if version == 1 { 112 } else { 128 };
+        let mut entry = vec![0u8; path_offset];
+        entry[8] = 0x11; // volume droid first byte
+        entry[56..72].copy_from_slice(&[
+            0xB4, 0xC8, 0xF9, 0xE2, 0x3A, 0x5D, 0xEA, 0x11, 0x8A, 0x6C, 0x08, 0x00, 0x27, 0x5C, 0x9F, 0x1E,
+        ]); // version 1 birth file droid
+        entry[72..78].copy_from_slice(b"host01");
+        entry[88..92].copy_from_slice(&number.to_le_bytes());
+        entry[100..108].copy_from_slice(&filetime.to_le_bytes());
+        entry[108..112].copy_from_slice(&pin.to_le_bytes());
+        if version >= 3 {
+            entry[116..120].copy_from_slice(&5u32.to_le_bytes());
+        }
+        entry.extend_from_slice(&(path.len() as u16).to_le_bytes());
+        entry.extend(utf16(path));
+        if version >= 3 {
+            entry.extend_from_slice(&0u32.to_le_bytes());
+        }
+        entry
+    }
+
+    fn build_dest_list(version: u32, entries: &[Vec<u8>]) -> Vec<u8> {
+        let mut data = vec![0u8; DEST_LIST_HEADER_SIZE];
+        data[0..4].copy_from_slice(&version.to_le_bytes());
+        data[4..8].copy_from_slice(&(entries.len() as u32).to_le_bytes());
+        data[8..12].copy_from_slice(&1u32.to_le_bytes());
+        data.extend(entries.concat());
+        data
+    }
+
+    #[test]
+    fn test_parse_dest_list_v4() {
+        let data = build_dest_list(
+            4,
+            &[
+                build_dest_list_entry(4, 10, 132223104000000000, -1, "C:\\old.txt"),
+                build_dest_list_entry(4, 11, 132539328000000000, 0, "C:\\new.txt"),
+            ],
+        );
+        let dest_list = DestList::parse(&data).unwrap();
+        assert_eq!(dest_list.header.version, 4);
+        assert_eq!(dest_list.entries.len(), 2);
+
+        let old = dest_list.entry_for_stream("a").unwrap();
+        assert_eq!(old.path, "C:\\old.txt");
+        assert_eq!(old.hostname, "host01");
+        assert_eq!(old.access_count, Some(5));
+        assert_eq!(old.mru_position, 1);
+        assert!(!old.is_pinned());
+        assert!(old.volume_droid.starts_with("{00000011-"));
+        assert!(old.file_object_id.is_none());
+        let birth = old.birth_file_object_id.as_ref().unwrap();
+        assert_eq!(birth.mac_address, "08:00:27:5c:9f:1e");
+        assert!(birth.created.is_some());
+
+        let new = dest_list.entry_for_stream("b").unwrap();
+        assert_eq!(new.mru_position, 0);
+        assert!(new.is_pinned());
+    }
+
+    #[test]
+    fn test_parse_dest_list_v1() {
+        let data = build_dest_list(1, &[build_dest_list_entry(1, 1, 132223104000000000, -1, "D:\\a.docx")]);
+        let dest_list = DestList::parse(&data).unwrap();
+        assert_eq!(dest_list.entries[0].path, "D:\\a.docx");
+        assert_eq!(dest_list.entries[0].access_count, None);
+        assert!(dest_list.entries[0].last_modified.is_some());
+        assert!(DestList::parse(&build_dest_list(2, &[])).is_err());
+    }
+
     #[test]
     fn test_app_ids() {
         let app_ids = get_known_app_ids();
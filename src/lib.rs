@@ -17,25 +17,48 @@
 //!
 //! Albert Hui <albert@securityronin.com>
 
+pub mod activities_cache;
+pub mod amcache;
 pub mod app;
+pub mod bam;
 pub mod benchmark;
+pub mod browser;
 pub mod cli;
 pub mod container;
 pub mod datetime;
+pub mod droid;
 pub mod error;
+pub mod ese;
+pub mod event_catalog;
+pub mod evtx;
 pub mod formatter;
 pub mod interactive;
 pub mod jumplist;
 pub mod live_registry;
 pub mod lnk_parser;
 pub mod mft;
+pub mod network;
+pub mod office;
 pub mod ole;
 pub mod output;
+pub mod persistence;
+pub mod prefetch;
+pub mod recycle_bin;
+pub mod property_schema;
 pub mod property_store;
 pub mod registry;
+pub mod sam;
+pub mod scheduled_task;
 pub mod shell_item;
+pub mod shimcache;
 pub mod simd_optimize;
+pub mod sqlite;
+pub mod srum;
+pub mod system_context;
 pub mod types;
+pub mod usb;
+pub mod xml;
+pub mod xpress;
 
 #[cfg(windows)]
 pub mod windows;
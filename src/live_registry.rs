@@ -245,6 +245,11 @@ impl LiveRegistryAccess {
 
     /// Parse MRU data based on key type
     fn parse_mru_data(&self, data: &[u8], key_path: &str) -> Option<String> {
+        // Formats shared with offline hive parsing (RecentDocs, *PidlMRU, RunMRU)
+        if let Some(parsed) = crate::jumplist::parse_registry_mru(key_path, "", data).parsed_path {
+            return Some(parsed);
+        }
+
         let key_lower = key_path.to_lowercase();
 
         if key_lower.contains("recentdocs") {
@@ -435,7 +440,7 @@ impl Default for LiveRegistryAccess {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, windows))]
 mod tests {
     use super::*;
 
@@ -4,8 +4,11 @@
 //! Shell links contain creation, access, and modification timestamps along with target
 //! file information, making them valuable forensic artifacts.
 
+use crate::droid::ObjectId;
 use crate::error::{Error, Result};
-use crate::shell_item::ItemIdList;
+use crate::property_store::PropertyStore;
+use crate::registry::{format_guid, read_u16, read_u32, utf16_string};
+use crate::shell_item::{known_folder_name, ItemIdList};
 use crate::types::EventTimestamps;
 use byteorder::{LittleEndian, ReadBytesExt};
 use chrono::{DateTime, Utc};
@@ -37,6 +40,32 @@ pub struct ShellLink {
     pub timestamps: EventTimestamps,
 }
 
+impl ShellLink {
+    /// Timestamp properties from the ExtraData property stores and the shell items of the link
+    pub fn property_timestamps(&self) -> Vec<(String, DateTime<Utc>)> {
+        link_property_timestamps(self.extra_data.iter().map(|block| &block.parsed_data), self.id_list.as_ref())
+    }
+}
+
+/// Timestamp properties from decoded ExtraData blocks and the link's shell items, sorted and deduplicated
+pub(crate) fn link_property_timestamps<'a>(
+    extra_data: impl IntoIterator<Item = &'a ExtraDataType>,
+    id_list: Option<&ItemIdList>,
+) -> Vec<(String, DateTime<Utc>)> {
+    let mut timestamps: Vec<(String, DateTime<Utc>)> = extra_data
+        .into_iter()
+        .flat_map(|block| match block {
+            ExtraDataType::PropertyStoreProps(stores) => stores.iter().flat_map(|store| store.timestamps()).collect(),
+            ExtraDataType::VistaAndAboveIDListProps(id_list) => id_list.property_timestamps(),
+            _ => Vec::new(),
+        })
+        .chain(id_list.into_iter().flat_map(|id_list| id_list.property_timestamps()))
+        .collect();
+    timestamps.sort();
+    timestamps.dedup();
+    timestamps
+}
+
 /// Shell Link Header structure (76 bytes)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ShellLinkHeader {
@@ -140,33 +169,241 @@ pub struct ExtraDataBlock {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ExtraDataType {
     /// Console properties
-    ConsoleProps,
+    ConsoleProps(ConsoleProps),
     /// Console codepage
-    ConsoleCodepage,
-    /// Darwin properties
-    DarwinProps,
+    ConsoleCodepage { code_page: u32 },
+    /// Darwin properties (Windows Installer descriptor)
+    DarwinProps { darwin_data: String },
     /// Environment variable data block
-    EnvironmentProps,
+    EnvironmentProps { target: String },
     /// Icon environment data block
-    IconEnvironmentProps,
+    IconEnvironmentProps { target: String },
     /// Known folder data block
-    KnownFolderProps,
+    KnownFolderProps { known_folder_id: String, offset: u32 },
     /// Property store data block
-    PropertyStoreProps,
+    PropertyStoreProps(Vec<PropertyStore>),
     /// Shim data block
-    ShimProps,
+    ShimProps { layer_name: String },
     /// Special folder data block
-    SpecialFolderProps,
+    SpecialFolderProps { special_folder_id: u32, offset: u32 },
     /// Tracker data block
-    TrackerProps,
+    TrackerProps(TrackerData),
     /// VistaAndAboveIDList data block
-    VistaAndAboveIDListProps,
+    VistaAndAboveIDListProps(ItemIdList),
     /// Volume ID data block
     VolumeIDProps,
     /// Unknown block type
     Unknown(Vec<u8>),
 }
 
+/// Console window settings (ConsoleDataBlock)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConsoleProps {
+    /// Foreground and background text colours
+    pub fill_attributes: u16,
+    /// Popup window colours
+    pub popup_fill_attributes: u16,
+    /// Screen buffer width and height in characters
+    pub screen_buffer_size: (u16, u16),
+    /// Window width and height in characters
+    pub window_size: (u16, u16),
+    /// Window origin in pixels
+    pub window_origin: (u16, u16),
+    /// Font size
+    pub font_size: u32,
+    /// Font family
+    pub font_family: u32,
+    /// Font weight
+    pub font_weight: u32,
+    /// Font face name
+    pub face_name: String,
+    /// Cursor size as a percentage of the character cell
+    pub cursor_size: u32,
+    /// Open in full screen mode
+    pub full_screen: bool,
+    /// QuickEdit mode enabled
+    pub quick_edit: bool,
+    /// Insert mode enabled
+    pub insert_mode: bool,
+    /// Window positioned by the system
+    pub auto_position: bool,
+    /// Command history buffer size
+    pub history_buffer_size: u32,
+    /// Number of history buffers
+    pub number_of_history_buffers: u32,
+    /// Duplicate history entries removed
+    pub history_no_dup: bool,
+    /// Sixteen RGB colour table entries
+    pub color_table: Vec<u32>,
+}
+
+/// Distributed link tracking data (TrackerDataBlock)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TrackerData {
+    /// NetBIOS name of the machine the target was last seen on
+    pub machine_id: String,
+    /// Current volume droid
+    pub volume_droid: String,
+    /// Current file droid
+    pub file_droid: String,
+    /// Birth volume droid
+    pub birth_volume_droid: String,
+    /// Birth file droid
+    pub birth_file_droid: String,
+    /// Decoded current file droid (version 1 UUIDs only)
+    pub file_object_id: Option<ObjectId>,
+    /// Decoded birth file droid (version 1 UUIDs only)
+    pub birth_file_object_id: Option<ObjectId>,
+}
+
+impl ExtraDataType {
+    /// Decode the contents of an extra data block by signature
+    pub(crate) fn parse(signature: u32, data: &[u8]) -> Self {
+        match signature {
+            0xA0000001 => ExtraDataType::EnvironmentProps { target: ansi_or_unicode(data) },
+            0xA0000002 => ExtraDataType::ConsoleProps(ConsoleProps::parse(data)),
+            0xA0000003 if data.len() >= 88 => ExtraDataType::TrackerProps(TrackerData {
+                machine_id: ansi_string(&data[8..24]),
+                volume_droid: format_guid(&data[24..40]),
+                file_droid: format_guid(&data[40..56]),
+                birth_volume_droid: format_guid(&data[56..72]),
+                birth_file_droid: format_guid(&data[72..88]),
+                file_object_id: ObjectId::parse(&data[40..56]),
+                birth_file_object_id: ObjectId::parse(&data[72..88]),
+            }),
+            0xA0000004 => ExtraDataType::ConsoleCodepage { code_page: read_u32(data, 0).unwrap_or(0) },
+            0xA0000005 => ExtraDataType::SpecialFolderProps {
+                special_folder_id: read_u32(data, 0).unwrap_or(0),
+                offset: read_u32(data, 4).unwrap_or(0),
+            },
+            0xA0000006 => ExtraDataType::DarwinProps { darwin_data: ansi_or_unicode(data) },
+            0xA0000007 => ExtraDataType::IconEnvironmentProps { target: ansi_or_unicode(data) },
+            0xA0000008 => ExtraDataType::ShimProps { layer_name: utf16_string(data) },
+            0xA0000009 => ExtraDataType::PropertyStoreProps(PropertyStore::parse_all(data)),
+            0xA000000B if data.len() >= 20 => ExtraDataType::KnownFolderProps {
+                known_folder_id: format_guid(&data[0..16]),
+                offset: read_u32(data, 16).unwrap_or(0),
+            },
+            0xA000000C => match ItemIdList::parse_pidl(data) {
+                Ok(id_list) => ExtraDataType::VistaAndAboveIDListProps(id_list),
+                Err(_) => ExtraDataType::Unknown(data.to_vec()),
+            },
+            0xA0000000 => ExtraDataType::VolumeIDProps,
+            _ => ExtraDataType::Unknown(data.to_vec()),
+        }
+    }
+
+    /// Labelled values for display and event metadata
+    pub fn fields(&self) -> Vec<(String, String)> {
+        let field = |label: &str, value: String| (label.to_string(), value);
+        match self {
+            ExtraDataType::ConsoleProps(console) => vec![
+                field("Console Font", format!("{} {}px, weight {}", console.face_name, console.font_size >> 16, console.font_weight)),
+                field("Console Screen Buffer", format!("{}x{}", console.screen_buffer_size.0, console.screen_buffer_size.1)),
+                field("Console Window", format!("{}x{} at {},{}", console.window_size.0, console.window_size.1, console.window_origin.0, console.window_origin.1)),
+                field("Console Colours", format!("0x{:02X} / popup 0x{:02X}", console.fill_attributes, console.popup_fill_attributes)),
+                field("Console History", format!("{} x {}{}", console.number_of_history_buffers, console.history_buffer_size, if console.history_no_dup { " (no duplicates)" } else { "" })),
+                field("Console Modes", format!(
+                    "full screen {}, quick edit {}, insert {}, auto position {}",
+                    console.full_screen, console.quick_edit, console.insert_mode, console.auto_position
+                )),
+            ],
+            ExtraDataType::ConsoleCodepage { code_page } => vec![field("Console Code Page", code_page.to_string())],
+            ExtraDataType::DarwinProps { darwin_data } => vec![field("Darwin ID", darwin_data.clone())],
+            ExtraDataType::EnvironmentProps { target } => vec![field("Environment Target", target.clone())],
+            ExtraDataType::IconEnvironmentProps { target } => vec![field("Icon Environment", target.clone())],
+            ExtraDataType::KnownFolderProps { known_folder_id, .. } => {
+                let folder = match known_folder_name(known_folder_id) {
+                    Some(name) => format!("{} {}", name, known_folder_id),
+                    None => known_folder_id.clone(),
+                };
+                vec![field("Known Folder", folder)]
+            }
+            ExtraDataType::PropertyStoreProps(stores) => {
+                let mut properties: Vec<(String, String)> = stores
+                    .iter()
+                    .flat_map(|store| store.properties.iter())
+                    .map(|(key, value)| (value.description.clone().unwrap_or_else(|| key.clone()), value.display_value.clone()))
+                    .filter(|(_, value)| !value.is_empty())
+                    .collect();
+                properties.sort();
+                properties.extend(
+                    stores
+                        .iter()
+                        .find_map(|store| store.gps_position())
+                        .map(|(latitude, longitude)| field("GPS Position", format!("{:.6}, {:.6}", latitude, longitude))),
+                );
+                properties
+            }
+            ExtraDataType::ShimProps { layer_name } => vec![field("Shim Layer", layer_name.clone())],
+            ExtraDataType::SpecialFolderProps { special_folder_id, .. } => vec![field("Special Folder (CSIDL)", special_folder_id.to_string())],
+            ExtraDataType::TrackerProps(tracker) => {
+                let mut fields = vec![
+                    field("Machine ID", tracker.machine_id.clone()),
+                    field("Volume Droid", tracker.volume_droid.clone()),
+                    field("File Droid", tracker.file_droid.clone()),
+                    field("Birth Volume Droid", tracker.birth_volume_droid.clone()),
+                    field("Birth File Droid", tracker.birth_file_droid.clone()),
+                ];
+                if let Some(object_id) = tracker.birth_file_object_id.as_ref().or(tracker.file_object_id.as_ref()) {
+                    fields.extend(object_id.fields());
+                }
+                fields
+            }
+            ExtraDataType::VistaAndAboveIDListProps(id_list) => id_list
+                .full_path
+                .iter()
+                .map(|path| field("Vista IDList Path", path.clone()))
+                .collect(),
+            ExtraDataType::VolumeIDProps | ExtraDataType::Unknown(_) => Vec::new(),
+        }
+    }
+}
+
+impl ConsoleProps {
+    /// Parse a ConsoleDataBlock body (after size and signature)
+    fn parse(data: &[u8]) -> Self {
+        let u16_at = |offset| read_u16(data, offset).unwrap_or(0);
+        let u32_at = |offset| read_u32(data, offset).unwrap_or(0);
+        ConsoleProps {
+            fill_attributes: u16_at(0),
+            popup_fill_attributes: u16_at(2),
+            screen_buffer_size: (u16_at(4), u16_at(6)),
+            window_size: (u16_at(8), u16_at(10)),
+            window_origin: (u16_at(12), u16_at(14)),
+            font_size: u32_at(24),
+            font_family: u32_at(28),
+            font_weight: u32_at(32),
+            face_name: data.get(36..100).map(utf16_string).unwrap_or_default(),
+            cursor_size: u32_at(100),
+            full_screen: u32_at(104) != 0,
+            quick_edit: u32_at(108) != 0,
+            insert_mode: u32_at(112) != 0,
+            auto_position: u32_at(116) != 0,
+            history_buffer_size: u32_at(120),
+            number_of_history_buffers: u32_at(124),
+            history_no_dup: u32_at(128) != 0,
+            color_table: (0..16).filter_map(|i| read_u32(data, 132 + i * 4)).collect(),
+        }
+    }
+}
+
+/// Decode a NUL-terminated ANSI string
+fn ansi_string(data: &[u8]) -> String {
+    let end = data.iter().position(|&b| b == 0).unwrap_or(data.len());
+    String::from_utf8_lossy(&data[..end]).to_string()
+}
+
+/// Decode the 260-byte ANSI / 520-byte Unicode path pair used by environment and Darwin blocks
+fn ansi_or_unicode(data: &[u8]) -> String {
+    let unicode = data.get(260..780).map(utf16_string).unwrap_or_default();
+    if unicode.is_empty() {
+        ansi_string(data.get(..260).unwrap_or(data))
+    } else {
+        unicode
+    }
+}
+
 /// LNK file parser
 pub struct LnkParser {
     /// FILETIME epoch difference (1601-01-01 to 1970-01-01)
@@ -183,6 +420,11 @@ impl LnkParser {
 
     /// Parse LNK file data
     pub fn parse_lnk_data(&self, data: &[u8]) -> Result<ShellLink> {
+        self.parse_embedded(data).map(|(link, _)| link)
+    }
+
+    /// Parse a shell link at the start of `data`, returning it with the number of bytes it occupies
+    pub fn parse_embedded(&self, data: &[u8]) -> Result<(ShellLink, usize)> {
         if data.len() < 76 {
             return Err(Error::InvalidInput(format!("LNK file too small: {} bytes, need at least 76", data.len())));
         }
@@ -242,7 +484,7 @@ impl LnkParser {
             mft_modified: None, // Not applicable for LNK files
         };
 
-        Ok(ShellLink {
+        let link = ShellLink {
             header,
             id_list,
             link_info,
@@ -253,7 +495,8 @@ impl LnkParser {
             working_directory,
             icon_location,
             timestamps,
-        })
+        };
+        Ok((link, cursor.position() as usize))
     }
 
     /// Parse shell link header
@@ -432,8 +675,8 @@ impl LnkParser {
             }
 
             let size = cursor.read_u32::<LittleEndian>()?;
-            if size < 4 {
-                break; // End of extra data
+            if size < 8 {
+                break; // Terminal block (or a size too small to hold a signature)
             }
 
             // Check if we have enough bytes for signature and data
@@ -446,21 +689,7 @@ impl LnkParser {
             let mut data = vec![0u8; data_size as usize];
             cursor.read_exact(&mut data)?;
 
-            let parsed_data = match signature {
-                0xA0000002 => ExtraDataType::ConsoleProps,
-                0xA0000004 => ExtraDataType::ConsoleCodepage,
-                0xA0000006 => ExtraDataType::DarwinProps,
-                0xA0000001 => ExtraDataType::EnvironmentProps,
-                0xA0000007 => ExtraDataType::IconEnvironmentProps,
-                0xA000000B => ExtraDataType::KnownFolderProps,
-                0xA0000009 => ExtraDataType::PropertyStoreProps,
-                0xA0000008 => ExtraDataType::ShimProps,
-                0xA0000005 => ExtraDataType::SpecialFolderProps,
-                0xA0000003 => ExtraDataType::TrackerProps,
-                0xA000000C => ExtraDataType::VistaAndAboveIDListProps,
-                0xA0000000 => ExtraDataType::VolumeIDProps,
-                _ => ExtraDataType::Unknown(data.clone()),
-            };
+            let parsed_data = ExtraDataType::parse(signature, &data);
 
             extra_blocks.push(ExtraDataBlock {
                 size,
@@ -556,6 +785,7 @@ impl Default for LnkParser {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::registry::test_hive::{utf16, utf16z};
     use chrono::Datelike;
 
     #[test]
@@ -592,4 +822,115 @@ mod tests {
         let small_data = vec![0u8; 50];
         assert!(parser.parse_lnk_data(&small_data).is_err());
     }
-}
\ No newline at end of file
+
+    fn block(signature: u32, body: &[u8]) -> Vec<u8> {
+        let mut block = Vec::new();
+        block.extend_from_slice(&(body.len() as u32 + 8).to_le_bytes());
+        block.extend_from_slice(&signature.to_le_bytes());
+        block.extend_from_slice(body);
+        block
+    }
+
+    #[test]
+    fn test_extra_data_blocks() {
+        let mut data = vec![0u8; 76];
+        data[0] = 0x4C;
+        data[4..20].copy_from_slice(&[0x01, 0x14, 0x02, 0, 0, 0, 0, 0, 0xC0, 0, 0, 0, 0, 0, 0, 0x46]);
+
+        let mut tracker = vec![0u8; 88];
+        tracker[0] = 0x58;
+        tracker[8..15].copy_from_slice(b"desktop");
+        tracker[24..40].copy_from_slice(&[
+            0x78, 0x56, 0x34, 0x12, 0x34, 0x12, 0xCD, 0xAB, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08,
+        ]);
+        tracker[72..88].copy_from_slice(&[
+            0xB4, 0xC8, 0xF9, 0xE2, 0x3A, 0x5D, 0xEA, 0x11, 0x8A, 0x6C, 0x08, 0x00, 0x27, 0x5C, 0x9F, 0x1E,
+        ]);
+        data.extend(block(0xA0000003, &tracker));
+
+        let mut environment = vec![0u8; 780];
+        environment[..16].copy_from_slice(b"%windir%\\cmd.exe");
+        let unicode = utf16("%windir%\\system32\\cmd.exe");
+        environment[260..260 + unicode.len()].copy_from_slice(&unicode);
+        data.extend(block(0xA0000001, &environment));
+
+        let mut known_folder = vec![0u8; 20];
+        known_folder[..16].copy_from_slice(&[
+            0x88, 0xE9, 0x3A, 0xFD, 0x64, 0xDB, 0x4F, 0x4C, 0xA7, 0x6E, 0x6E, 0x2A, 0x30, 0x2A, 0x4B, 0x85,
+        ]);
+        data.extend(block(0xA000000B, &known_folder));
+
+        let mut shim = utf16("WinXPSp3");
+        shim.resize(128, 0);
+        data.extend(block(0xA0000008, &shim));
+        data.extend_from_slice(&0u32.to_le_bytes()); // Terminal block
+
+        let link = LnkParser::new().parse_lnk_data(&data).unwrap();
+        assert_eq!(link.extra_data.len(), 4);
+        match &link.extra_data[0].parsed_data {
+            ExtraDataType::TrackerProps(tracker) => {
+                assert_eq!(tracker.machine_id, "desktop");
+                assert_eq!(tracker.volume_droid, "{12345678-1234-ABCD-0102-030405060708}");
+                assert!(tracker.file_object_id.is_none());
+                assert_eq!(tracker.birth_file_object_id.as_ref().unwrap().clock_sequence, 0x0A6C);
+            }
+            other => panic!("unexpected block {:?}", other),
+        }
+        assert!(matches!(
+            &link.extra_data[1].parsed_data,
+            ExtraDataType::EnvironmentProps { target } if target == "%windir%\\system32\\cmd.exe"
+        ));
+        assert!(matches!(
+            &link.extra_data[2].parsed_data,
+            ExtraDataType::KnownFolderProps { known_folder_id, .. } if known_folder_id == "{FD3AE988-DB64-4C4F-A76E-6E2A302A4B85}"
+        ));
+
+        let fields: Vec<(String, String)> = link.extra_data.iter().flat_map(|b| b.parsed_data.fields()).collect();
+        assert!(fields.contains(&("Machine ID".to_string(), "desktop".to_string())));
+        assert!(fields.contains(&("MAC Address".to_string(), "08:00:27:5c:9f:1e".to_string())));
+        assert!(fields.contains(&("Shim Layer".to_string(), "WinXPSp3".to_string())));
+    }
+
+    #[test]
+    fn test_console_and_property_store_blocks() {
+        let mut console = vec![0u8; 196];
+        console[0] = 0x07;
+        console[4..8].copy_from_slice(&[120, 0, 0x2C, 0x01]); // 120 x 300 buffer
+        console[24..28].copy_from_slice(&(16u32 << 16).to_le_bytes());
+        let face = utf16("Consolas");
+        console[36..36 + face.len()].copy_from_slice(&face);
+        console[108] = 1; // QuickEdit
+        match ExtraDataType::parse(0xA0000002, &console) {
+            ExtraDataType::ConsoleProps(props) => {
+                assert_eq!(props.screen_buffer_size, (120, 300));
+                assert_eq!(props.face_name, "Consolas");
+                assert!(props.quick_edit);
+                assert_eq!(props.color_table.len(), 16);
+            }
+            other => panic!("unexpected block {:?}", other),
+        }
+
+        // System.ItemNameDisplay string property in the B725F130 storage property set
+        let mut value = Vec::new();
+        value.extend_from_slice(&10u32.to_le_bytes()); // Property ID (ItemNameDisplay)
+        value.push(0);
+        value.extend_from_slice(&31u16.to_le_bytes()); // VT_LPWSTR
+        value.extend_from_slice(&0u16.to_le_bytes());
+        let name = utf16z("report.pdf");
+        value.extend_from_slice(&((name.len() / 2) as u32).to_le_bytes());
+        value.extend(name);
+        let mut record = ((value.len() + 4) as u32).to_le_bytes().to_vec();
+        record.extend(value);
+
+        let mut store = Vec::new();
+        store.extend_from_slice(&((24 + record.len() + 4) as u32).to_le_bytes());
+        store.extend_from_slice(b"1SPS");
+        store.extend_from_slice(&[0x30, 0xF1, 0x25, 0xB7, 0xEF, 0x47, 0x1A, 0x10, 0xA5, 0xF1, 0x02, 0x60, 0x8C, 0x9E, 0xEB, 0xAC]);
+        store.extend(record);
+        store.extend_from_slice(&0u32.to_le_bytes()); // End of values
+        store.extend_from_slice(&0u32.to_le_bytes()); // End of stores
+
+        let parsed = ExtraDataType::parse(0xA0000009, &store);
+        assert_eq!(parsed.fields(), vec![("System.ItemNameDisplay".to_string(), "report.pdf".to_string())]);
+    }
+}
@@ -6,9 +6,19 @@ use crate::simd_optimize::{
     StringPool, scan_record_boundaries_simd,
     find_attributes_simd, convert_timestamps_simd, apply_fixups_simd
 };
+use crate::activities_cache::{parse_activities, Activity};
+use crate::browser::{Browser, BrowserDownload, BrowserHistory, BrowserVisit};
+use crate::scheduled_task::{parse_task, ScheduledTask, TaskCacheEntry};
+use crate::srum::{parse_srum, SrumRecord};
 use crate::cli::InputType;
-use crate::jumplist::JumplistParser;
+use crate::droid::ObjectId;
+use crate::jumplist::{get_known_app_ids, CustomDestinations, JumplistParser};
 use crate::lnk_parser::LnkParser;
+use crate::prefetch::PrefetchFile;
+use crate::office::OfficeMetadata;
+use crate::recycle_bin::RecycleBinEntry;
+use crate::event_catalog::EventCatalog;
+use crate::evtx::{EvtxParser, EvtxRecord};
 use crate::registry::RegistryParser;
 use std::io::{Cursor, Read};
 use std::path::Path;
@@ -154,6 +164,8 @@ pub struct MftParser {
     /// Processing configuration
     parallel_threads: usize,
     use_simd: bool,
+    /// Event-ID catalogue used to describe event log records
+    event_catalog: Arc<EventCatalog>,
 }
 
 impl MftParser {
@@ -165,6 +177,7 @@ impl MftParser {
             mmap_cache: Arc::new(RwLock::new(None)),
             parallel_threads: std::cmp::min(rayon::current_num_threads(), MAX_PARALLEL_THREADS),
             use_simd: Self::detect_simd_support(),
+            event_catalog: Arc::new(EventCatalog::builtin()),
         }
     }
     
@@ -179,6 +192,11 @@ impl MftParser {
         parser
     }
     
+    /// Replace the event-ID catalogue used for event log records
+    pub fn set_event_catalog(&mut self, catalog: EventCatalog) {
+        self.event_catalog = Arc::new(catalog);
+    }
+
     fn detect_simd_support() -> bool {
         #[cfg(target_arch = "x86_64")]
         {
@@ -198,6 +216,29 @@ impl MftParser {
             .to_lowercase();
         let filename = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
         
+        // $I files keep the original file's extension, so check the name first
+        if crate::recycle_bin::is_index_file(filename) {
+            return Ok(InputType::RecycleBin);
+        }
+        
+        if filename.eq_ignore_ascii_case("ActivitiesCache.db") {
+            return Ok(InputType::ActivitiesCache);
+        }
+        
+        if crate::browser::is_history_database_file(path) {
+            return Ok(InputType::BrowserHistory);
+        }
+        
+        if filename.eq_ignore_ascii_case("SRUDB.dat") {
+            return Ok(InputType::Srum);
+        }
+        
+        if crate::scheduled_task::is_task_file(&path.to_string_lossy())
+            || (extension == "xml" && crate::scheduled_task::is_task_xml_file(path))
+        {
+            return Ok(InputType::ScheduledTask);
+        }
+        
         // Check for jumplist files first (handle compound extensions)
         if filename.ends_with(".automaticDestinations-ms") {
             return Ok(InputType::AutomaticDestinations);
@@ -230,6 +271,10 @@ impl MftParser {
                     Err(Error::InvalidInput(format!("Unknown .ms file type: {}", filename)))
                 }
             },
+            "hve" => Ok(InputType::Registry), // Amcache.hve and other exported hives
+            "pf" => Ok(InputType::Prefetch),
+            "evtx" => Ok(InputType::Evtx),
+            _ if crate::office::is_office_document(filename) => Ok(InputType::OfficeDocument),
             "mft" | "bin" | "gz" => Ok(InputType::Mft),
             _ => {
                 let filename = path.file_name()
@@ -240,7 +285,7 @@ impl MftParser {
                     Ok(InputType::AutomaticDestinations)
                 } else if filename.contains("customDestinations") {
                     Ok(InputType::CustomDestinations)
-                } else if filename.contains("ntuser") {
+                } else if filename.contains("ntuser") || crate::cli::is_bare_hive_name(&filename) {
                     Ok(InputType::Registry)
                 } else if filename.contains("$mft") {
                     Ok(InputType::Mft)
@@ -263,6 +308,40 @@ impl MftParser {
                 self.parse_jumplist_file(path, input_type)
             },
             InputType::Registry => self.parse_registry_file(path),
+            InputType::Prefetch => {
+                let data = std::fs::read(path)?;
+                self.parse_prefetch_data(&data, path)
+            },
+            InputType::Evtx => {
+                let data = std::fs::read(path)?;
+                self.parse_evtx_data(&data, path)
+            },
+            InputType::RecycleBin => {
+                let data = std::fs::read(path)?;
+                self.parse_recycle_bin_data(&data, path, |content| Path::new(content).exists())
+            },
+            InputType::OfficeDocument => {
+                let data = std::fs::read(path)?;
+                self.parse_office_data(&data, path)
+            },
+            InputType::ActivitiesCache => {
+                let data = std::fs::read(path)?;
+                self.parse_activities_cache_data(&data, path)
+            },
+            InputType::BrowserHistory => {
+                let data = std::fs::read(path)?;
+                self.parse_browser_history_data(&data, path)
+            },
+            InputType::Srum => {
+                let data = std::fs::read(path)?;
+                self.parse_srum_data(&data, path)
+            },
+            InputType::ScheduledTask => {
+                let data = std::fs::read(path)?;
+                let mut records = self.parse_scheduled_task_data(&data, path)?;
+                Self::retain_dated_tasks(&mut records);
+                Ok(records)
+            },
             _ => {
                 // Default to MFT parsing for unknown types
                 self.parse_mft_file(path)
@@ -316,6 +395,8 @@ impl MftParser {
         let mut records = Vec::new();
         
         if let Some(record) = Self::shell_link_to_mft_record(&shell_link, path)? {
+            records.extend(Self::link_object_id_records(&record, &shell_link));
+            records.extend(Self::property_timestamp_records(&record, &shell_link.property_timestamps()));
             records.push(record);
         }
         
@@ -332,7 +413,7 @@ impl MftParser {
                 jumplist_parser.parse_automatic_destinations(&data)?
             },
             InputType::CustomDestinations => {
-                jumplist_parser.parse_custom_destinations(&data)?
+                return self.parse_custom_destinations_data(&data, path);
             },
             _ => return Err(Error::InvalidInput("Invalid jumplist type".to_string())),
         };
@@ -342,6 +423,11 @@ impl MftParser {
         
         for entry in jumplist_entries {
             if let Some(record) = Self::jumplist_entry_to_mft_record(&entry, path)? {
+                if let Some(dest) = &entry.dest_list {
+                    let object_ids = [dest.birth_file_object_id.as_ref(), dest.file_object_id.as_ref()];
+                    records.extend(Self::object_id_records(&record, &dest.hostname, object_ids));
+                }
+                records.extend(Self::property_timestamp_records(&record, &entry.property_timestamps));
                 records.push(record);
             }
         }
@@ -349,20 +435,132 @@ impl MftParser {
         Ok(records)
     }
 
+    /// Parse custom destinations data, emitting each embedded link like a standalone LNK file
+    pub fn parse_custom_destinations_data(&mut self, data: &[u8], path: &Path) -> Result<Vec<Event>> {
+        let custom = match CustomDestinations::parse(data, &path.to_string_lossy()) {
+            Ok(custom) => custom,
+            Err(e) => {
+                eprintln!("⚠️  Failed to parse custom destinations {}: {}; scanning for links", path.display(), e);
+                let entries = JumplistParser::new().parse_custom_destinations(data)?;
+                let mut records = Vec::new();
+                for entry in entries {
+                    records.extend(Self::jumplist_entry_to_mft_record(&entry, path)?);
+                }
+                return Ok(records);
+            }
+        };
+
+        let application = get_known_app_ids().get(&custom.app_id).cloned();
+        let mut records = Vec::new();
+        for (category, link) in custom.links() {
+            if let Some(mut record) = Self::shell_link_to_mft_record(link, path)? {
+                record.metadata.push(("Jumplist".to_string(), path.to_string_lossy().to_string()));
+                record.metadata.push(("App ID".to_string(), custom.app_id.clone()));
+                if let Some(application) = &application {
+                    record.metadata.push(("Application".to_string(), application.clone()));
+                }
+                record.metadata.push(("Category".to_string(), category.name.clone()));
+                record.metadata.push(("Category Type".to_string(), format!("{:?}", category.category_type)));
+                if let Some(arguments) = &link.arguments {
+                    record.metadata.push(("Arguments".to_string(), arguments.clone()));
+                }
+                records.extend(Self::link_object_id_records(&record, link));
+                records.extend(Self::property_timestamp_records(&record, &link.property_timestamps()));
+                records.push(record);
+            }
+        }
+        Ok(records)
+    }
+
+    /// Parse prefetch data and emit one event per recorded run time
+    pub fn parse_prefetch_data(&mut self, data: &[u8], path: &Path) -> Result<Vec<Event>> {
+        let prefetch = match PrefetchFile::parse(data) {
+            Ok(prefetch) => prefetch,
+            Err(e) => {
+                eprintln!("⚠️  Failed to parse prefetch file {}: {}", path.display(), e);
+                return Ok(Vec::new()); // Skip invalid prefetch files instead of failing
+            }
+        };
+
+        Ok(Self::prefetch_to_mft_records(&prefetch, path))
+    }
+
+    /// Parse event log data and emit one event per record
+    pub fn parse_evtx_data(&mut self, data: &[u8], path: &Path) -> Result<Vec<Event>> {
+        let records = match EvtxParser::parse(data) {
+            Ok(records) => records,
+            Err(e) => {
+                eprintln!("⚠️  Failed to parse event log {}: {}", path.display(), e);
+                return Ok(Vec::new()); // Skip invalid event logs instead of failing
+            }
+        };
+
+        Ok(records
+            .iter()
+            .map(|record| Self::evtx_record_to_mft_record(record, &self.event_catalog, path))
+            .collect())
+    }
+
+    /// Parse a Recycle Bin $I file; `content_exists` reports whether its $R file is available
+    pub fn parse_recycle_bin_data<F: Fn(&str) -> bool>(&mut self, data: &[u8], path: &Path, content_exists: F) -> Result<Vec<Event>> {
+        let entry = match RecycleBinEntry::parse(data, &path.to_string_lossy()) {
+            Ok(entry) => entry,
+            Err(e) => {
+                eprintln!("⚠️  Failed to parse Recycle Bin file {}: {}", path.display(), e);
+                return Ok(Vec::new()); // Skip invalid $I files instead of failing
+            }
+        };
+
+        let content_present = content_exists(&entry.content_path);
+        Ok(vec![Self::recycle_bin_to_mft_record(&entry, content_present)])
+    }
+
+    /// Parse the document properties of an Office file
+    pub fn parse_office_data(&mut self, data: &[u8], path: &Path) -> Result<Vec<Event>> {
+        let metadata = match OfficeMetadata::parse(data) {
+            Ok(metadata) => metadata,
+            Err(e) => {
+                eprintln!("⚠️  Failed to parse Office document {}: {}", path.display(), e);
+                return Ok(Vec::new()); // Skip documents without readable properties
+            }
+        };
+
+        if metadata.created.is_none() && metadata.last_saved.is_none() && metadata.last_printed.is_none() {
+            return Ok(Vec::new());
+        }
+        Ok(vec![Self::office_to_mft_record(&metadata, data.len() as u64, path)])
+    }
+
     /// Parse registry file and extract timeline events
     fn parse_registry_file(&mut self, path: &Path) -> Result<Vec<Event>> {
         let data = std::fs::read(path)?;
         let registry_parser = RegistryParser::new();
         let registry_hive = registry_parser.parse_registry_data(&data, path)?;
         
-        // Extract timeline events from registry
-        let registry_events = registry_parser.extract_timeline_events(&registry_hive)?;
+        let mut records = self.registry_hive_records(&registry_hive, path)?;
         
-        // Convert registry events to Event format
+        // A single hive can only contribute its own side of the USB device history
+        let usb_events = crate::usb::extract_timeline_events(&[&registry_hive]);
+        records.extend(Self::registry_events_to_records(&usb_events, path)?);
+        
+        Ok(records)
+    }
+
+    /// Extract timeline events from a parsed hive (excluding cross-hive USB history)
+    pub fn registry_hive_records(&self, hive: &crate::registry::RegistryHive, path: &Path) -> Result<Vec<Event>> {
+        let registry_events = RegistryParser::new().extract_timeline_events(hive)?;
+        Self::registry_events_to_records(&registry_events, path)
+    }
+
+    /// Convert registry timeline events to Event format
+    pub fn registry_events_to_records(
+        events: &[crate::registry::RegistryTimelineEvent],
+        path: &Path,
+    ) -> Result<Vec<Event>> {
         let mut records = Vec::new();
         
-        for event in registry_events {
-            if let Some(record) = Self::registry_event_to_mft_record(&event, path)? {
+        for event in events {
+            if let Some(record) = Self::registry_event_to_mft_record(event, path)? {
                 records.push(record);
             }
         }
@@ -1047,6 +1245,13 @@ impl MftParser {
         // Location should be the full target path for the "Full Path" column
         let location = target_path;
 
+        // Decoded ExtraData blocks (tracker, environment, console, property store, ...)
+        let metadata = shell_link
+            .extra_data
+            .iter()
+            .flat_map(|block| block.parsed_data.fields())
+            .collect();
+
         let record = Event {
             record_number: 0, // LNK files use 🔗 instead of record numbers
             sequence_number: 0,
@@ -1062,11 +1267,78 @@ impl MftParser {
             alternate_data_streams: Vec::new(),
             location: Some(location), // Use target path as location
             event_source: Some("LNK".to_string()),
+            metadata,
         };
 
         Ok(Some(record))
     }
 
+    /// "Object ID created" events for the tracker block of a shell link
+    fn link_object_id_records(record: &Event, shell_link: &crate::lnk_parser::ShellLink) -> Vec<Event> {
+        shell_link
+            .extra_data
+            .iter()
+            .filter_map(|block| match &block.parsed_data {
+                crate::lnk_parser::ExtraDataType::TrackerProps(tracker) => Some(tracker),
+                _ => None,
+            })
+            .flat_map(|tracker| {
+                let object_ids = [tracker.birth_file_object_id.as_ref(), tracker.file_object_id.as_ref()];
+                Self::object_id_records(record, &tracker.machine_id, object_ids)
+            })
+            .collect()
+    }
+
+    /// Emit an "Object ID created" event for each distinct version 1 file droid behind a link
+    fn object_id_records(record: &Event, machine_id: &str, object_ids: [Option<&ObjectId>; 2]) -> Vec<Event> {
+        let mut records = Vec::new();
+        let mut seen: Vec<&str> = Vec::new();
+        for (label, object_id) in ["Birth File Droid", "File Droid"].into_iter().zip(object_ids) {
+            let Some(object_id) = object_id else { continue };
+            if object_id.created.is_none() || seen.contains(&object_id.guid.as_str()) {
+                continue;
+            }
+            seen.push(&object_id.guid);
+
+            let mut metadata = vec![
+                ("Machine ID".to_string(), machine_id.to_string()),
+                (label.to_string(), object_id.guid.clone()),
+            ];
+            metadata.extend(object_id.fields());
+            metadata.push(("Link Source".to_string(), record.event_source.clone().unwrap_or_default()));
+
+            records.push(Event {
+                timestamps: EventTimestamps { created: object_id.created, ..EventTimestamps::default() },
+                fn_timestamps: EventTimestamps::default(),
+                event_source: Some("ObjectID".to_string()),
+                metadata,
+                ..record.clone()
+            });
+        }
+        records
+    }
+
+    /// Emit an event for each timestamp property (e.g. Photo.DateTaken) found in a link's property stores
+    fn property_timestamp_records(record: &Event, timestamps: &[(String, chrono::DateTime<chrono::Utc>)]) -> Vec<Event> {
+        timestamps
+            .iter()
+            .map(|(name, timestamp)| {
+                let short_name = name.strip_prefix("System.").unwrap_or(name);
+                Event {
+                    filename: record.filename.as_ref().map(|filename| format!("{} ({})", filename, short_name)),
+                    timestamps: EventTimestamps { created: Some(*timestamp), ..EventTimestamps::default() },
+                    fn_timestamps: EventTimestamps::default(),
+                    event_source: Some("PropertyStore".to_string()),
+                    metadata: vec![
+                        ("Property".to_string(), name.clone()),
+                        ("Link Source".to_string(), record.event_source.clone().unwrap_or_default()),
+                    ],
+                    ..record.clone()
+                }
+            })
+            .collect()
+    }
+
     /// Clean jumplist path to show proper Windows path structure
     fn clean_jumplist_path(path: &Path) -> String {
         let path_str = path.to_string_lossy();
@@ -1108,6 +1380,29 @@ impl MftParser {
             "CustomDest"
         };
 
+        let mut metadata = Vec::new();
+        if let Some(count) = entry.access_count {
+            metadata.push(("Access Count".to_string(), count.to_string()));
+        }
+        if let Some(dest) = &entry.dest_list {
+            metadata.push(("Entry Number".to_string(), format!("{} (stream {:x})", dest.entry_number, dest.entry_number)));
+            metadata.push(("MRU Position".to_string(), dest.mru_position.to_string()));
+            if let Some(last_used) = dest.last_modified {
+                metadata.push(("Last Used".to_string(), last_used.to_rfc3339()));
+            }
+            let pinned = if dest.is_pinned() { format!("Yes (position {})", dest.pin_status) } else { "No".to_string() };
+            metadata.push(("Pinned".to_string(), pinned));
+            metadata.push(("Hostname".to_string(), dest.hostname.clone()));
+            metadata.push(("DestList Path".to_string(), dest.path.clone()));
+            metadata.push(("Volume Droid".to_string(), dest.volume_droid.clone()));
+            metadata.push(("File Droid".to_string(), dest.file_droid.clone()));
+            metadata.push(("Birth Volume Droid".to_string(), dest.birth_volume_droid.clone()));
+            metadata.push(("Birth File Droid".to_string(), dest.birth_file_droid.clone()));
+            if let Some(object_id) = dest.birth_file_object_id.as_ref().or(dest.file_object_id.as_ref()) {
+                metadata.extend(object_id.fields());
+            }
+        }
+
         let record = Event {
             record_number: 0, // Jumplist entries don't have MFT record numbers
             sequence_number: 0,
@@ -1123,11 +1418,478 @@ impl MftParser {
             alternate_data_streams: Vec::new(),
             location: Some(format!("{} [Source: {}]", cleaned_path.rsplit(['/', '\\']).next().unwrap_or(&cleaned_path), cleaned_path)),
             event_source: Some("Jumplist".to_string()),
+            metadata,
         };
 
         Ok(Some(record))
     }
 
+    /// Convert a prefetch file to one Event per last run time
+    fn prefetch_to_mft_records(prefetch: &PrefetchFile, source_path: &Path) -> Vec<Event> {
+        let metadata = vec![
+            ("Executable".to_string(), prefetch.executable.clone()),
+            ("Run Count".to_string(), prefetch.run_count.to_string()),
+            ("Prefetch Hash".to_string(), prefetch.hash.clone()),
+            ("Version".to_string(), prefetch.version.to_string()),
+            ("Compressed".to_string(), prefetch.compressed.to_string()),
+            ("Volumes".to_string(), prefetch.volumes.len().to_string()),
+            ("Loaded Files".to_string(), prefetch.loaded_files.len().to_string()),
+        ];
+
+        // Volume and loaded file lists describe the whole prefetch file, so only the most
+        // recent run carries them; older runs point back to it
+        let mut details = Vec::new();
+        for (i, volume) in prefetch.volumes.iter().enumerate() {
+            let created = volume.created.map(|c| c.to_rfc3339()).unwrap_or_default();
+            details.push((
+                format!("Volume {}", i + 1),
+                format!("{} (serial {}, created {})", volume.device_path, volume.serial_number, created),
+            ));
+        }
+        for file in &prefetch.loaded_files {
+            details.push(("Loaded File".to_string(), file.clone()));
+        }
+
+        // The executable's full path is usually among the loaded files
+        let executable_path = prefetch
+            .loaded_files
+            .iter()
+            .find(|f| f.to_uppercase().ends_with(&format!("\\{}", prefetch.executable.to_uppercase())))
+            .cloned()
+            .unwrap_or_else(|| source_path.to_string_lossy().to_string());
+
+        prefetch
+            .last_run_times
+            .iter()
+            .enumerate()
+            .map(|(i, run_time)| {
+                let mut run_metadata = metadata.clone();
+                run_metadata.insert(0, ("Run Index".to_string(), (i + 1).to_string()));
+                if i == 0 {
+                    run_metadata.extend(details.iter().cloned());
+                } else if !details.is_empty() {
+                    run_metadata.push(("Details".to_string(), "Volumes and loaded files listed on Run Index 1".to_string()));
+                }
+
+                Event {
+                    record_number: 0, // Prefetch entries don't have MFT record numbers
+                    sequence_number: 0,
+                    filename: Some(prefetch.executable.clone()),
+                    file_size: None,
+                    allocated_size: None,
+                    is_directory: false,
+                    is_deleted: false,
+                    link_count: None,
+                    parent_directory: None,
+                    timestamps: EventTimestamps {
+                        created: None,
+                        modified: Some(*run_time),
+                        accessed: None,
+                        mft_modified: None,
+                    },
+                    fn_timestamps: EventTimestamps::default(),
+                    alternate_data_streams: Vec::new(),
+                    location: Some(format!("{} [Source: {}]", executable_path, source_path.to_string_lossy())),
+                    event_source: Some("Prefetch".to_string()),
+                    metadata: run_metadata,
+                }
+            })
+            .collect()
+    }
+
+    /// Convert a Recycle Bin entry to a deletion Event
+    fn recycle_bin_to_mft_record(entry: &RecycleBinEntry, content_present: bool) -> Event {
+        let mut metadata = vec![
+            ("Original Path".to_string(), entry.original_path.clone()),
+            ("Original Size".to_string(), entry.original_size.to_string()),
+            ("Index File".to_string(), entry.index_path.clone()),
+            ("Content File".to_string(), entry.content_path.clone()),
+            ("Version".to_string(), entry.version.to_string()),
+        ];
+        let status = if content_present { "Present" } else { "Missing (emptied or not collected)" };
+        metadata.push(("Content Status".to_string(), status.to_string()));
+        if let Some(sid) = &entry.sid {
+            metadata.push(("SID".to_string(), sid.clone()));
+        }
+
+        Event {
+            record_number: 0, // Recycle Bin entries don't have MFT record numbers
+            sequence_number: 0,
+            filename: Some(entry.original_name().to_string()),
+            file_size: Some(entry.original_size),
+            allocated_size: None,
+            is_directory: false,
+            is_deleted: true,
+            link_count: None,
+            parent_directory: None,
+            timestamps: EventTimestamps {
+                created: None,
+                modified: entry.deleted,
+                accessed: None,
+                mft_modified: None,
+            },
+            fn_timestamps: EventTimestamps::default(),
+            alternate_data_streams: Vec::new(),
+            location: Some(format!("{} [Source: {}]", entry.original_path, entry.index_path)),
+            event_source: Some("RecycleBin".to_string()),
+            metadata,
+        }
+    }
+
+    /// Parse a Windows Timeline database, emitting one event per activity
+    pub fn parse_activities_cache_data(&mut self, data: &[u8], path: &Path) -> Result<Vec<Event>> {
+        let activities = match parse_activities(data) {
+            Ok(activities) => activities,
+            Err(e) => {
+                eprintln!("⚠️  Failed to parse Windows Timeline database {}: {}", path.display(), e);
+                return Ok(Vec::new());
+            }
+        };
+        Ok(activities.iter().map(Self::activity_to_mft_record).collect())
+    }
+
+    /// Convert a Windows Timeline activity to an Event named after its content or application
+    fn activity_to_mft_record(activity: &Activity) -> Event {
+        let location = activity.content.clone().or_else(|| activity.application.clone());
+        let filename = activity
+            .display_text
+            .clone()
+            .or_else(|| location.as_ref().map(|l| l.rsplit('\\').next().unwrap_or(l).to_string()))
+            .or_else(|| activity.app_display_name.clone());
+
+        Event {
+            record_number: 0, // Timeline activities don't have MFT record numbers
+            sequence_number: 0,
+            filename,
+            file_size: None,
+            allocated_size: None,
+            is_directory: false,
+            is_deleted: activity.operation_type == Some(3),
+            link_count: None,
+            parent_directory: None,
+            timestamps: EventTimestamps {
+                created: activity.start_time,
+                modified: activity.last_modified,
+                accessed: activity.end_time,
+                mft_modified: None,
+            },
+            fn_timestamps: EventTimestamps::default(),
+            alternate_data_streams: Vec::new(),
+            location,
+            event_source: Some("WindowsTimeline".to_string()),
+            metadata: activity.fields(),
+        }
+    }
+
+    /// Parse a scheduled task XML or .job file. Tasks without dates are kept so TaskCache
+    /// correlation can date them; `retain_dated_tasks` drops those still undated afterwards.
+    pub fn parse_scheduled_task_data(&mut self, data: &[u8], path: &Path) -> Result<Vec<Event>> {
+        match parse_task(data, &path.to_string_lossy()) {
+            Ok(task) => Ok(vec![Self::task_to_mft_record(&task, path)]),
+            Err(e) => {
+                eprintln!("⚠️  Failed to parse scheduled task {}: {}", path.display(), e);
+                Ok(Vec::new())
+            }
+        }
+    }
+
+    /// Drop scheduled task events that have no timestamps
+    pub fn retain_dated_tasks(records: &mut Vec<Event>) {
+        records.retain(|record| {
+            let timestamps = &record.timestamps;
+            record.event_source.as_deref() != Some("ScheduledTask")
+                || timestamps.created.is_some()
+                || timestamps.modified.is_some()
+                || timestamps.accessed.is_some()
+                || timestamps.mft_modified.is_some()
+        });
+    }
+
+    /// Convert a scheduled task to an Event dated by its registration and last run
+    fn task_to_mft_record(task: &ScheduledTask, path: &Path) -> Event {
+        let mut metadata = task.fields();
+        metadata.push(("Task File".to_string(), path.to_string_lossy().to_string()));
+
+        Event {
+            record_number: 0, // Scheduled tasks don't have MFT record numbers
+            sequence_number: 0,
+            filename: Some(task.name().to_string()),
+            file_size: None,
+            allocated_size: None,
+            is_directory: false,
+            is_deleted: false,
+            link_count: None,
+            parent_directory: None,
+            timestamps: EventTimestamps {
+                created: task.registration_date,
+                accessed: task.last_run,
+                ..EventTimestamps::default()
+            },
+            fn_timestamps: EventTimestamps::default(),
+            alternate_data_streams: Vec::new(),
+            location: Some(task.path.clone()),
+            event_source: Some("ScheduledTask".to_string()),
+            metadata,
+        }
+    }
+
+    /// Convert a TaskCache registration without a collected task file to an Event
+    pub fn task_cache_to_mft_record(entry: &TaskCacheEntry, task_files_collected: bool) -> Event {
+        let mut metadata = vec![("Task Path".to_string(), entry.path.clone()), ("Source".to_string(), "TaskCache".to_string())];
+        if task_files_collected {
+            metadata.push(("Task File".to_string(), "Missing".to_string()));
+        }
+        metadata.extend(entry.fields());
+
+        Event {
+            record_number: 0, // TaskCache entries don't have MFT record numbers
+            sequence_number: 0,
+            filename: Some(entry.path.rsplit('\\').next().unwrap_or(&entry.path).to_string()),
+            file_size: None,
+            allocated_size: None,
+            is_directory: false,
+            is_deleted: false,
+            link_count: None,
+            parent_directory: None,
+            timestamps: EventTimestamps {
+                created: entry.created,
+                modified: entry.last_success,
+                accessed: entry.last_run,
+                mft_modified: None,
+            },
+            fn_timestamps: EventTimestamps::default(),
+            alternate_data_streams: Vec::new(),
+            location: Some(entry.path.clone()),
+            event_source: Some("ScheduledTask".to_string()),
+            metadata,
+        }
+    }
+
+    /// Parse a SRUM database, emitting one event per hourly usage record
+    pub fn parse_srum_data(&mut self, data: &[u8], path: &Path) -> Result<Vec<Event>> {
+        let records = match parse_srum(data) {
+            Ok(records) => records,
+            Err(e) => {
+                eprintln!("⚠️  Failed to parse SRUM database {}: {}", path.display(), e);
+                return Ok(Vec::new());
+            }
+        };
+        Ok(records.iter().map(Self::srum_to_mft_record).collect())
+    }
+
+    /// Convert a SRUM record to an Event named after its application
+    fn srum_to_mft_record(record: &SrumRecord) -> Event {
+        let filename = record
+            .application
+            .as_ref()
+            .map(|a| a.rsplit('\\').next().unwrap_or(a).to_string())
+            .or_else(|| Some(record.table.name().to_string()));
+
+        Event {
+            record_number: 0, // SRUM records don't have MFT record numbers
+            sequence_number: 0,
+            filename,
+            file_size: None,
+            allocated_size: None,
+            is_directory: false,
+            is_deleted: false,
+            link_count: None,
+            parent_directory: None,
+            timestamps: EventTimestamps {
+                created: record.connect_start,
+                modified: record.timestamp,
+                ..EventTimestamps::default()
+            },
+            fn_timestamps: EventTimestamps::default(),
+            alternate_data_streams: Vec::new(),
+            location: record.application.clone(),
+            event_source: Some("SRUM".to_string()),
+            metadata: record.fields(),
+        }
+    }
+
+    /// Parse a Chromium or Firefox history database, emitting visit and download events
+    pub fn parse_browser_history_data(&mut self, data: &[u8], path: &Path) -> Result<Vec<Event>> {
+        let history = match BrowserHistory::parse(data) {
+            Ok(history) => history,
+            Err(e) => {
+                eprintln!("⚠️  Failed to parse browser history {}: {}", path.display(), e);
+                return Ok(Vec::new());
+            }
+        };
+        let visits = history.visits.iter().map(|visit| Self::browser_visit_to_mft_record(visit, history.browser));
+        let downloads = history.downloads.iter().map(|download| Self::browser_download_to_mft_record(download, history.browser));
+        Ok(visits.chain(downloads).collect())
+    }
+
+    /// Convert a browser page visit to an Event dated by the visit time
+    fn browser_visit_to_mft_record(visit: &BrowserVisit, browser: Browser) -> Event {
+        let mut metadata = vec![
+            ("Browser".to_string(), format!("{:?}", browser)),
+            ("Event".to_string(), "Visit".to_string()),
+            ("URL".to_string(), visit.url.clone()),
+        ];
+        let optional = [
+            ("Title", visit.title.clone()),
+            ("Transition", visit.transition.clone()),
+            ("Visit Count", visit.visit_count.map(|c| c.to_string())),
+            ("Typed Count", visit.typed_count.map(|c| c.to_string())),
+            ("Duration", visit.duration.map(|d| format!("{:.1}s", d))),
+        ];
+        metadata.extend(optional.into_iter().filter_map(|(label, value)| Some((label.to_string(), value?))));
+
+        Event {
+            record_number: 0, // Browser history entries don't have MFT record numbers
+            sequence_number: 0,
+            filename: Some(visit.title.clone().unwrap_or_else(|| visit.url.clone())),
+            file_size: None,
+            allocated_size: None,
+            is_directory: false,
+            is_deleted: false,
+            link_count: None,
+            parent_directory: None,
+            timestamps: EventTimestamps {
+                accessed: visit.visit_time,
+                ..EventTimestamps::default()
+            },
+            fn_timestamps: EventTimestamps::default(),
+            alternate_data_streams: Vec::new(),
+            location: Some(visit.url.clone()),
+            event_source: Some("Browser".to_string()),
+            metadata,
+        }
+    }
+
+    /// Convert a browser download to an Event named after the saved file
+    fn browser_download_to_mft_record(download: &BrowserDownload, browser: Browser) -> Event {
+        let mut metadata = vec![
+            ("Browser".to_string(), format!("{:?}", browser)),
+            ("Event".to_string(), "Download".to_string()),
+            ("Target Path".to_string(), download.target_path.clone()),
+        ];
+        let optional = [
+            ("URL", download.url.clone()),
+            ("Referrer", download.referrer.clone()),
+            ("State", download.state.clone()),
+            ("Danger Type", download.danger_type.clone()),
+            ("MIME Type", download.mime_type.clone()),
+            ("Received Bytes", download.received_bytes.map(|b| b.to_string())),
+        ];
+        metadata.extend(optional.into_iter().filter_map(|(label, value)| Some((label.to_string(), value?))));
+
+        let target = &download.target_path;
+        Event {
+            record_number: 0, // Browser downloads don't have MFT record numbers
+            sequence_number: 0,
+            filename: Some(target.rsplit(['\\', '/']).next().unwrap_or(target).to_string()),
+            file_size: download.total_bytes,
+            allocated_size: None,
+            is_directory: false,
+            is_deleted: false,
+            link_count: None,
+            parent_directory: None,
+            timestamps: EventTimestamps {
+                created: download.start_time,
+                modified: download.end_time,
+                ..EventTimestamps::default()
+            },
+            fn_timestamps: EventTimestamps::default(),
+            alternate_data_streams: Vec::new(),
+            location: Some(target.clone()),
+            event_source: Some("Browser".to_string()),
+            metadata,
+        }
+    }
+
+    /// Convert Office document properties to an Event dated by the embedded created/saved/printed times
+    fn office_to_mft_record(metadata: &OfficeMetadata, file_size: u64, path: &Path) -> Event {
+        let filename = path.file_name().map(|n| n.to_string_lossy().to_string());
+
+        Event {
+            record_number: 0, // Office documents don't have MFT record numbers
+            sequence_number: 0,
+            filename,
+            file_size: Some(file_size),
+            allocated_size: None,
+            is_directory: false,
+            is_deleted: false,
+            link_count: None,
+            parent_directory: None,
+            timestamps: EventTimestamps {
+                created: metadata.created,
+                modified: metadata.last_saved,
+                accessed: metadata.last_printed,
+                mft_modified: None,
+            },
+            fn_timestamps: EventTimestamps::default(),
+            alternate_data_streams: Vec::new(),
+            location: Some(path.to_string_lossy().to_string()),
+            event_source: Some("Office".to_string()),
+            metadata: metadata.fields(),
+        }
+    }
+
+    /// Convert an event log record to an Event, described by the catalogue when the event is known
+    fn evtx_record_to_mft_record(record: &EvtxRecord, catalog: &EventCatalog, source_path: &Path) -> Event {
+        let described = catalog.describe(record);
+        let mut metadata = vec![
+            ("Channel".to_string(), record.channel.clone()),
+            ("Event ID".to_string(), record.event_id.to_string()),
+            ("Provider".to_string(), record.provider.clone()),
+            ("Computer".to_string(), record.computer.clone()),
+            ("Record ID".to_string(), record.record_id.to_string()),
+        ];
+        let fields = [
+            ("Level", record.level.map(|v| v.to_string())),
+            ("Task", record.task.map(|v| v.to_string())),
+            ("Opcode", record.opcode.map(|v| v.to_string())),
+            ("Keywords", record.keywords.clone()),
+            ("User SID", record.user_id.clone()),
+            ("Process ID", record.process_id.map(|v| v.to_string())),
+            ("Thread ID", record.thread_id.map(|v| v.to_string())),
+        ];
+        for (label, value) in fields {
+            if let Some(value) = value {
+                metadata.push((label.to_string(), value));
+            }
+        }
+        // Catalogued fields come first (with value names), followed by the remaining raw data
+        let curated = described.as_ref().map(|(_, fields)| fields.as_slice()).unwrap_or_default();
+        metadata.extend(curated.iter().cloned());
+        for (name, value) in &record.data {
+            if !curated.iter().any(|(field, _)| field == name) {
+                metadata.push((name.clone(), value.clone()));
+            }
+        }
+        let filename = match described {
+            Some((description, _)) => format!("{} {}: {}", record.channel, record.event_id, description),
+            None => record.description(),
+        };
+
+        Event {
+            record_number: 0, // Event log records don't have MFT record numbers
+            sequence_number: 0,
+            filename: Some(filename),
+            file_size: None,
+            allocated_size: None,
+            is_directory: false,
+            is_deleted: false,
+            link_count: None,
+            parent_directory: None,
+            timestamps: EventTimestamps {
+                created: None,
+                modified: record.timestamp,
+                accessed: None,
+                mft_modified: None,
+            },
+            fn_timestamps: EventTimestamps::default(),
+            alternate_data_streams: Vec::new(),
+            location: Some(source_path.to_string_lossy().to_string()),
+            event_source: Some("EVTX".to_string()),
+            metadata,
+        }
+    }
+
     /// Convert Registry Event to Event format
     fn registry_event_to_mft_record(
         event: &crate::registry::RegistryTimelineEvent, 
@@ -1136,7 +1898,8 @@ impl MftParser {
         let key_path = &event.key_path;
         let default_value = "(Default)".to_string();
         let value_name = event.value_name.as_ref().unwrap_or(&default_value);
-        let filename = format!("REG: {}\\{}", key_path, value_name);
+        let filename = event.description.clone()
+            .unwrap_or_else(|| format!("REG: {}\\{}", key_path, value_name));
 
         let location = source_path.parent()
             .map(|p| p.to_string_lossy().to_string())
@@ -1160,7 +1923,8 @@ impl MftParser {
             fn_timestamps: EventTimestamps::default(), // Registry events don't have FILE_NAME attributes (N/A)
             alternate_data_streams: Vec::new(),
             location: Some(format!("{} [Hive: {}, Source: {}]", location, hive_name, source_path.to_string_lossy())),
-            event_source: Some("Registry".to_string()),
+            event_source: Some(event.source.clone().unwrap_or_else(|| "Registry".to_string())),
+            metadata: event.metadata.clone(),
         };
 
         Ok(Some(record))
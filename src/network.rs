@@ -0,0 +1,221 @@
+//! Network profile history parsing module for the SOFTWARE hive
+//!
+//! Supports parsing of:
+//! - NetworkList\Profiles\{GUID} (name, category, type, first/last connected)
+//! - NetworkList\Signatures\Managed and Unmanaged (gateway MAC, DNS suffix)
+
+use crate::registry::{systemtime, RegistryEventType, RegistryHive, RegistryKey, RegistryTimelineEvent};
+use crate::system_context::{LOCAL_SYSTEM_TIME, TIME_BASIS};
+use crate::types::EventTimestamps;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// NetworkList key relative to the SOFTWARE hive root
+const NETWORK_LIST: &str = "Microsoft\\Windows NT\\CurrentVersion\\NetworkList";
+
+/// Network profile with its matching signature
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetworkProfile {
+    /// Profile GUID
+    pub guid: String,
+    /// Registry key path of the profile
+    pub key_path: String,
+    /// Profile name (SSID for wireless networks)
+    pub profile_name: Option<String>,
+    /// Profile description
+    pub description: Option<String>,
+    /// Network category (Public, Private, Domain)
+    pub category: Option<String>,
+    /// Connection type from NameType (Wired, Wireless, Mobile Broadband)
+    pub network_type: Option<String>,
+    /// Whether the network is domain managed (from the signature)
+    pub managed: Option<bool>,
+    /// First connection (local system time until converted with the system time zone)
+    pub first_connected: Option<DateTime<Utc>>,
+    /// Last connection (local system time until converted with the system time zone)
+    pub last_connected: Option<DateTime<Utc>>,
+    /// Default gateway MAC address (from the signature)
+    pub gateway_mac: Option<String>,
+    /// DNS suffix (from the signature)
+    pub dns_suffix: Option<String>,
+}
+
+/// Parse network profiles and join them with their signatures
+pub fn parse_network_profiles(hive: &RegistryHive) -> Vec<NetworkProfile> {
+    let Some(profiles) = hive.open_key(&format!("{}\\Profiles", NETWORK_LIST)) else {
+        return Vec::new();
+    };
+    let signatures = hive.open_key(&format!("{}\\Signatures", NETWORK_LIST));
+
+    profiles
+        .subkeys
+        .iter()
+        .map(|key| {
+            let mut profile = NetworkProfile {
+                guid: key.name.clone(),
+                key_path: key.path.clone(),
+                profile_name: key.string_value("ProfileName"),
+                description: key.string_value("Description"),
+                category: key.value("Category").and_then(|v| v.dword_data()).map(|c| category_name(c).to_string()),
+                network_type: key.value("NameType").and_then(|v| v.dword_data()).map(|t| name_type(t).to_string()),
+                managed: None,
+                first_connected: key.value("DateCreated").and_then(|v| systemtime(&v.data)),
+                last_connected: key.value("DateLastConnected").and_then(|v| systemtime(&v.data)),
+                gateway_mac: None,
+                dns_suffix: None,
+            };
+
+            if let Some(signatures) = &signatures {
+                join_signature(&mut profile, signatures);
+            }
+            profile
+        })
+        .collect()
+}
+
+/// Find the Managed/Unmanaged signature whose ProfileGuid matches the profile
+fn join_signature(profile: &mut NetworkProfile, signatures: &RegistryKey) {
+    for (group, managed) in [("Managed", true), ("Unmanaged", false)] {
+        let Some(group) = signatures.subkey(group) else {
+            continue;
+        };
+        let matching = group.subkeys.iter().find(|sig| {
+            sig.string_value("ProfileGuid").is_some_and(|g| g.eq_ignore_ascii_case(&profile.guid))
+        });
+        if let Some(signature) = matching {
+            profile.managed = Some(managed);
+            profile.gateway_mac = signature
+                .value("DefaultGatewayMac")
+                .filter(|v| v.data.len() >= 6)
+                .map(|v| format_mac(&v.data[..6]));
+            profile.dns_suffix = signature.string_value("DnsSuffix").filter(|s| s != "<none>");
+            return;
+        }
+    }
+}
+
+/// Format a MAC address as colon-separated hex
+fn format_mac(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02X}", b)).collect::<Vec<_>>().join(":")
+}
+
+/// Map the Category value
+fn category_name(category: u32) -> &'static str {
+    match category {
+        0 => "Public",
+        1 => "Private",
+        2 => "Domain",
+        _ => "Unknown",
+    }
+}
+
+/// Map the NameType value (IANA interface type)
+fn name_type(name_type: u32) -> &'static str {
+    match name_type {
+        0x06 => "Wired",
+        0x17 => "VPN",
+        0x47 => "Wireless",
+        0xF3 => "Mobile Broadband",
+        _ => "Unknown",
+    }
+}
+
+/// Extract network profiles as first/last connected events
+pub fn extract_timeline_events(hive: &RegistryHive) -> Vec<RegistryTimelineEvent> {
+    parse_network_profiles(hive)
+        .into_iter()
+        .map(|profile| {
+            let name = profile
+                .profile_name
+                .clone()
+                .or_else(|| profile.description.clone())
+                .unwrap_or_else(|| profile.guid.clone());
+
+            let mut metadata = vec![
+                ("Profile GUID".to_string(), profile.guid.clone()),
+                (TIME_BASIS.to_string(), LOCAL_SYSTEM_TIME.to_string()),
+            ];
+            let fields = [
+                ("Profile Name", &profile.profile_name),
+                ("Description", &profile.description),
+                ("Category", &profile.category),
+                ("Type", &profile.network_type),
+                ("Gateway MAC", &profile.gateway_mac),
+                ("DNS Suffix", &profile.dns_suffix),
+            ];
+            for (label, value) in fields {
+                if let Some(value) = value {
+                    metadata.push((label.to_string(), value.clone()));
+                }
+            }
+            if let Some(managed) = profile.managed {
+                metadata.push(("Managed".to_string(), managed.to_string()));
+            }
+
+            RegistryTimelineEvent {
+                key_path: profile.key_path.clone(),
+                value_name: None,
+                event_type: RegistryEventType::KeyModified,
+                value_type: None,
+                data: None,
+                data_size: None,
+                timestamps: EventTimestamps {
+                    created: profile.first_connected,
+                    modified: profile.last_connected,
+                    accessed: None,
+                    mft_modified: None,
+                },
+                description: Some(format!("Network: {}", name)),
+                metadata,
+                source: Some("Network".to_string()),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::test_hive::TestKey;
+
+    #[test]
+    fn test_parse_network_profiles() {
+        // 2023-03-14 (Tuesday) 09:26:53.589 local time
+        let systemtime = |fields: [u16; 8]| fields.iter().flat_map(|f| f.to_le_bytes()).collect::<Vec<u8>>();
+        let profile = TestKey::new("{A1B2C3D4-0000-0000-0000-000000000001}")
+            .string("ProfileName", "CoffeeShop")
+            .dword("Category", 0)
+            .dword("NameType", 0x47)
+            .binary("DateCreated", &systemtime([2023, 3, 2, 14, 9, 26, 53, 589]))
+            .binary("DateLastConnected", &[0u8; 16]);
+        let signature = TestKey::new("0123456789")
+            .string("ProfileGuid", "{a1b2c3d4-0000-0000-0000-000000000001}")
+            .binary("DefaultGatewayMac", &[0x00, 0x1a, 0x2b, 0x3c, 0x4d, 0x5e])
+            .string("DnsSuffix", "<none>");
+        let hive = TestKey::new("ROOT")
+            .with(&format!("{}\\Profiles", NETWORK_LIST), profile)
+            .with(&format!("{}\\Signatures\\Unmanaged", NETWORK_LIST), signature)
+            .open("SOFTWARE");
+
+        let profiles = parse_network_profiles(&hive);
+        assert_eq!(profiles.len(), 1);
+        let profile = &profiles[0];
+        assert_eq!(profile.profile_name.as_deref(), Some("CoffeeShop"));
+        assert_eq!(profile.network_type.as_deref(), Some("Wireless"));
+        assert_eq!(profile.first_connected.unwrap().to_rfc3339(), "2023-03-14T09:26:53.589+00:00");
+        assert!(profile.last_connected.is_none());
+        assert_eq!(profile.managed, Some(false));
+        assert_eq!(profile.gateway_mac.as_deref(), Some("00:1A:2B:3C:4D:5E"));
+        assert!(profile.dns_suffix.is_none());
+
+        let events = extract_timeline_events(&hive);
+        assert!(events[0].metadata.contains(&(TIME_BASIS.to_string(), LOCAL_SYSTEM_TIME.to_string())));
+    }
+
+    #[test]
+    fn test_format_mac_and_types() {
+        assert_eq!(format_mac(&[0x00, 0x1a, 0x2b, 0x3c, 0x4d, 0x5e]), "00:1A:2B:3C:4D:5E");
+        assert_eq!(name_type(0x47), "Wireless");
+        assert_eq!(category_name(2), "Domain");
+    }
+}
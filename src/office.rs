@@ -0,0 +1,379 @@
+//! Office document metadata parsing module
+//!
+//! Supports parsing of:
+//! - OLE property sets (\x05SummaryInformation, \x05DocumentSummaryInformation) in legacy .doc/.xls/.ppt/.msg files
+//! - OOXML document properties (docProps/core.xml, docProps/app.xml) in ZIP-based .docx/.xlsx/.pptx files
+
+use crate::error::{Error, Result};
+use crate::ole::OleCompoundDocument;
+use crate::registry::{filetime_to_datetime, read_u16, read_u32, read_u64, utf16_string};
+use crate::xml::xml_element;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::io::{Cursor, Read};
+
+/// Legacy (OLE compound file) Office extensions
+const OLE_EXTENSIONS: &[&str] = &["doc", "dot", "xls", "xlt", "ppt", "pot", "msg", "pub", "vsd"];
+/// OOXML (ZIP package) Office extensions
+const OOXML_EXTENSIONS: &[&str] = &["docx", "docm", "dotx", "xlsx", "xlsm", "xltx", "pptx", "pptm", "potx", "vsdx"];
+
+/// OLE compound file signature
+const OLE_SIGNATURE: [u8; 8] = [0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1];
+
+/// SummaryInformation property IDs (PIDSI_*)
+const PIDSI_TITLE: u32 = 2;
+const PIDSI_SUBJECT: u32 = 3;
+const PIDSI_AUTHOR: u32 = 4;
+const PIDSI_TEMPLATE: u32 = 7;
+const PIDSI_LASTAUTHOR: u32 = 8;
+const PIDSI_REVNUMBER: u32 = 9;
+const PIDSI_EDITTIME: u32 = 10;
+const PIDSI_LASTPRINTED: u32 = 11;
+const PIDSI_CREATE_DTM: u32 = 12;
+const PIDSI_LASTSAVE_DTM: u32 = 13;
+const PIDSI_APPNAME: u32 = 18;
+/// DocumentSummaryInformation property IDs (PIDDSI_*)
+const PIDDSI_MANAGER: u32 = 14;
+const PIDDSI_COMPANY: u32 = 15;
+/// Code page property ID, present in every property set
+const PID_CODEPAGE: u32 = 1;
+/// UTF-16 code page
+const CP_WINUNICODE: u16 = 1200;
+
+/// Container format a document's metadata came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OfficeFormat {
+    /// OLE compound file property sets
+    Ole,
+    /// OOXML package properties
+    Ooxml,
+}
+
+/// Document properties of an Office file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OfficeMetadata {
+    /// Container format
+    pub format: OfficeFormat,
+    /// Document title
+    pub title: Option<String>,
+    /// Document subject
+    pub subject: Option<String>,
+    /// Author (creator)
+    pub author: Option<String>,
+    /// User who last saved the document
+    pub last_author: Option<String>,
+    /// Revision number
+    pub revision: Option<String>,
+    /// Creating application
+    pub application: Option<String>,
+    /// Company
+    pub company: Option<String>,
+    /// Manager
+    pub manager: Option<String>,
+    /// Template the document was based on
+    pub template: Option<String>,
+    /// Creation time
+    pub created: Option<DateTime<Utc>>,
+    /// Last saved time
+    pub last_saved: Option<DateTime<Utc>>,
+    /// Last printed time
+    pub last_printed: Option<DateTime<Utc>>,
+    /// Total editing time in seconds
+    pub total_edit_time: Option<u64>,
+}
+
+/// Typed value from an OLE property set
+enum OleValue {
+    Text(String),
+    Integer(i64),
+    FileTime(u64),
+}
+
+impl OfficeMetadata {
+    /// Parse metadata from an OLE compound file or OOXML package
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        if data.starts_with(&OLE_SIGNATURE) {
+            Self::parse_ole(data)
+        } else if data.starts_with(b"PK\x03\x04") {
+            Self::parse_ooxml(data)
+        } else {
+            Err(Error::ParseError("Not an OLE compound file or OOXML package".to_string()))
+        }
+    }
+
+    /// Parse the SummaryInformation and DocumentSummaryInformation property sets
+    pub fn parse_ole(data: &[u8]) -> Result<Self> {
+        let document = OleCompoundDocument::parse(data)?;
+        let summary = document.get_stream("\u{5}SummaryInformation").map(|s| property_set(s)).unwrap_or_default();
+        let document_summary =
+            document.get_stream("\u{5}DocumentSummaryInformation").map(|s| property_set(s)).unwrap_or_default();
+        if summary.is_empty() && document_summary.is_empty() {
+            return Err(Error::ParseError("No summary information property sets".to_string()));
+        }
+
+        let text = |set: &[(u32, OleValue)], id: u32| {
+            set.iter().find(|(pid, _)| *pid == id).and_then(|(_, value)| match value {
+                OleValue::Text(s) => Some(s.clone()).filter(|s| !s.is_empty()),
+                OleValue::Integer(v) => Some(v.to_string()),
+                OleValue::FileTime(_) => None,
+            })
+        };
+        let filetime = |id: u32| {
+            summary.iter().find(|(pid, _)| *pid == id).and_then(|(_, value)| match value {
+                OleValue::FileTime(ft) => Some(*ft),
+                _ => None,
+            })
+        };
+
+        Ok(OfficeMetadata {
+            format: OfficeFormat::Ole,
+            title: text(&summary, PIDSI_TITLE),
+            subject: text(&summary, PIDSI_SUBJECT),
+            author: text(&summary, PIDSI_AUTHOR),
+            last_author: text(&summary, PIDSI_LASTAUTHOR),
+            revision: text(&summary, PIDSI_REVNUMBER),
+            application: text(&summary, PIDSI_APPNAME),
+            company: text(&document_summary, PIDDSI_COMPANY),
+            manager: text(&document_summary, PIDDSI_MANAGER),
+            template: text(&summary, PIDSI_TEMPLATE),
+            created: filetime(PIDSI_CREATE_DTM).and_then(filetime_to_datetime),
+            last_saved: filetime(PIDSI_LASTSAVE_DTM).and_then(filetime_to_datetime),
+            last_printed: filetime(PIDSI_LASTPRINTED).and_then(filetime_to_datetime),
+            // Edit time is stored as a FILETIME-typed duration
+            total_edit_time: filetime(PIDSI_EDITTIME).map(|ticks| ticks / 10_000_000),
+        })
+    }
+
+    /// Parse docProps/core.xml and docProps/app.xml from an OOXML package
+    pub fn parse_ooxml(data: &[u8]) -> Result<Self> {
+        let mut archive = zip::ZipArchive::new(Cursor::new(data))?;
+        let mut read_part = |name: &str| -> Option<String> {
+            let mut part = archive.by_name(name).ok()?;
+            let mut xml = String::new();
+            part.read_to_string(&mut xml).ok()?;
+            Some(xml)
+        };
+        let core = read_part("docProps/core.xml").unwrap_or_default();
+        let app = read_part("docProps/app.xml").unwrap_or_default();
+        if core.is_empty() && app.is_empty() {
+            return Err(Error::ParseError("No docProps parts in OOXML package".to_string()));
+        }
+
+        let date = |name: &str| xml_element(&core, name).and_then(|value| parse_w3cdtf(&value));
+        Ok(OfficeMetadata {
+            format: OfficeFormat::Ooxml,
+            title: xml_element(&core, "title"),
+            subject: xml_element(&core, "subject"),
+            author: xml_element(&core, "creator"),
+            last_author: xml_element(&core, "lastModifiedBy"),
+            revision: xml_element(&core, "revision"),
+            application: xml_element(&app, "Application"),
+            company: xml_element(&app, "Company"),
+            manager: xml_element(&app, "Manager"),
+            template: xml_element(&app, "Template"),
+            created: date("created"),
+            last_saved: date("modified"),
+            last_printed: date("lastPrinted"),
+            // TotalTime is recorded in minutes
+            total_edit_time: xml_element(&app, "TotalTime").and_then(|m| m.parse::<u64>().ok()).map(|m| m * 60),
+        })
+    }
+
+    /// Labelled values for event metadata
+    pub fn fields(&self) -> Vec<(String, String)> {
+        let mut fields = Vec::new();
+        let text_fields = [
+            ("Title", &self.title),
+            ("Subject", &self.subject),
+            ("Author", &self.author),
+            ("Last Saved By", &self.last_author),
+            ("Revision", &self.revision),
+            ("Application", &self.application),
+            ("Company", &self.company),
+            ("Manager", &self.manager),
+            ("Template", &self.template),
+        ];
+        for (label, value) in text_fields {
+            if let Some(value) = value {
+                fields.push((label.to_string(), value.clone()));
+            }
+        }
+        if let Some(seconds) = self.total_edit_time {
+            fields.push((
+                "Total Edit Time".to_string(),
+                format!("{:02}:{:02}:{:02}", seconds / 3600, seconds / 60 % 60, seconds % 60),
+            ));
+        }
+        if let Some(printed) = self.last_printed {
+            fields.push(("Last Printed".to_string(), printed.to_rfc3339()));
+        }
+        fields.push(("Format".to_string(), format!("{:?}", self.format)));
+        fields
+    }
+}
+
+/// Whether a path names an Office document by extension
+pub fn is_office_document(path: &str) -> bool {
+    let extension = path.rsplit_once('.').map(|(_, ext)| ext.to_lowercase()).unwrap_or_default();
+    OLE_EXTENSIONS.contains(&extension.as_str()) || OOXML_EXTENSIONS.contains(&extension.as_str())
+}
+
+/// Read the first property set of a PropertySetStream as (property ID, value) pairs
+fn property_set(stream: &[u8]) -> Vec<(u32, OleValue)> {
+    // Header: byte order(2) + version(2) + system ID(4) + CLSID(16) + set count(4), then FMTID(16) + offset(4)
+    if read_u16(stream, 0) != Some(0xFFFE) {
+        return Vec::new();
+    }
+    let Some(set) = read_u32(stream, 44).and_then(|offset| stream.get(offset as usize..)) else {
+        return Vec::new();
+    };
+    let count = read_u32(set, 4).unwrap_or(0).min(1024) as usize;
+    let entries: Vec<(u32, usize)> = (0..count)
+        .filter_map(|i| Some((read_u32(set, 8 + i * 8)?, read_u32(set, 12 + i * 8)? as usize)))
+        .collect();
+
+    let code_page = entries
+        .iter()
+        .find(|(id, _)| *id == PID_CODEPAGE)
+        .and_then(|(_, offset)| read_u16(set, offset + 4))
+        .unwrap_or(0);
+
+    entries
+        .into_iter()
+        .filter(|(id, _)| *id != PID_CODEPAGE)
+        .filter_map(|(id, offset)| Some((id, ole_value(set.get(offset..)?, code_page)?)))
+        .collect()
+}
+
+/// Decode a TypedPropertyValue
+fn ole_value(data: &[u8], code_page: u16) -> Option<OleValue> {
+    match read_u16(data, 0)? {
+        // VT_I2
+        2 => Some(OleValue::Integer(read_u16(data, 4)? as i16 as i64)),
+        // VT_I4
+        3 => Some(OleValue::Integer(read_u32(data, 4)? as i32 as i64)),
+        // VT_UI4
+        19 => Some(OleValue::Integer(read_u32(data, 4)? as i64)),
+        // VT_LPSTR: byte count then code page string
+        30 => {
+            let bytes = data.get(8..8 + read_u32(data, 4)? as usize)?;
+            let text = if code_page == CP_WINUNICODE {
+                utf16_string(bytes)
+            } else {
+                let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+                match std::str::from_utf8(&bytes[..end]) {
+                    Ok(s) => s.to_string(),
+                    Err(_) => bytes[..end].iter().map(|&b| b as char).collect(),
+                }
+            };
+            Some(OleValue::Text(text))
+        }
+        // VT_LPWSTR: character count then UTF-16
+        31 => Some(OleValue::Text(utf16_string(data.get(8..8 + read_u32(data, 4)? as usize * 2)?))),
+        // VT_FILETIME
+        64 => Some(OleValue::FileTime(read_u64(data, 4)?)),
+        _ => None,
+    }
+}
+
+/// Parse a W3CDTF (dcterms:W3CDTF) date such as 2021-05-04T09:30:00Z
+fn parse_w3cdtf(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.with_timezone(&Utc))
+        .ok()
+        .or_else(|| NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S").ok().map(|dt| dt.and_utc()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    /// Build a SummaryInformation stream with the given typed values
+    fn summary_stream(values: &[(u32, Vec<u8>)]) -> Vec<u8> {
+        let mut set = Vec::new();
+        let header_size = 8 + values.len() * 8;
+        let mut offset = header_size;
+        let mut body = Vec::new();
+        for (id, value) in values {
+            set.extend_from_slice(&id.to_le_bytes());
+            set.extend_from_slice(&(offset as u32).to_le_bytes());
+            body.extend_from_slice(value);
+            offset += value.len();
+        }
+        let mut stream = vec![0xFE, 0xFF, 0, 0, 0, 0, 0, 0];
+        stream.extend_from_slice(&[0u8; 16]);
+        stream.extend_from_slice(&1u32.to_le_bytes());
+        stream.extend_from_slice(&[0u8; 16]);
+        stream.extend_from_slice(&48u32.to_le_bytes());
+        stream.extend_from_slice(&(offset as u32).to_le_bytes());
+        stream.extend_from_slice(&(values.len() as u32).to_le_bytes());
+        stream.extend(set);
+        stream.extend(body);
+        stream
+    }
+
+    fn typed(vt: u16, payload: &[u8]) -> Vec<u8> {
+        let mut value = vt.to_le_bytes().to_vec();
+        value.extend_from_slice(&[0, 0]);
+        value.extend_from_slice(payload);
+        value
+    }
+
+    #[test]
+    fn test_summary_information_property_set() {
+        let mut author = 6u32.to_le_bytes().to_vec();
+        author.extend_from_slice(b"alice\0\0\0");
+        let mut edit = Vec::new();
+        edit.extend_from_slice(&(5400u64 * 10_000_000).to_le_bytes()); // 1.5 hours
+        let stream = summary_stream(&[
+            (PID_CODEPAGE, typed(2, &[0xE4, 0x04, 0, 0])), // 1252
+            (PIDSI_AUTHOR, typed(30, &author)),
+            (PIDSI_EDITTIME, typed(64, &edit)),
+            (PIDSI_CREATE_DTM, typed(64, &132223104000000000u64.to_le_bytes())),
+        ]);
+
+        let values = property_set(&stream);
+        assert_eq!(values.len(), 3);
+        assert!(matches!(&values[0], (PIDSI_AUTHOR, OleValue::Text(s)) if s == "alice"));
+        assert!(matches!(values[1], (PIDSI_EDITTIME, OleValue::FileTime(54_000_000_000))));
+        assert!(property_set(&[0u8; 48]).is_empty());
+    }
+
+    #[test]
+    fn test_parse_ooxml_doc_props() {
+        let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        let options = zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Stored);
+        zip.start_file("docProps/core.xml", options).unwrap();
+        zip.write_all(
+            br#"<?xml version="1.0"?><cp:coreProperties xmlns:cp="x" xmlns:dc="y" xmlns:dcterms="z">
+<dc:creator>Alice &amp; Bob</dc:creator><cp:lastModifiedBy>mallory</cp:lastModifiedBy><cp:revision>7</cp:revision>
+<dcterms:created xsi:type="dcterms:W3CDTF">2021-05-04T09:30:00Z</dcterms:created>
+<dcterms:modified xsi:type="dcterms:W3CDTF">2021-05-06T17:45:10Z</dcterms:modified><dc:title/></cp:coreProperties>"#,
+        )
+        .unwrap();
+        zip.start_file("docProps/app.xml", options).unwrap();
+        zip.write_all(b"<Properties><TotalTime>95</TotalTime><Application>Microsoft Office Word</Application></Properties>")
+            .unwrap();
+        let data = zip.finish().unwrap().into_inner();
+
+        let metadata = OfficeMetadata::parse(&data).unwrap();
+        assert_eq!(metadata.format, OfficeFormat::Ooxml);
+        assert_eq!(metadata.author.as_deref(), Some("Alice & Bob"));
+        assert_eq!(metadata.last_author.as_deref(), Some("mallory"));
+        assert_eq!(metadata.revision.as_deref(), Some("7"));
+        assert!(metadata.title.is_none());
+        assert_eq!(metadata.created.unwrap().to_rfc3339(), "2021-05-04T09:30:00+00:00");
+        assert_eq!(metadata.last_saved.unwrap().to_rfc3339(), "2021-05-06T17:45:10+00:00");
+        assert_eq!(metadata.total_edit_time, Some(95 * 60));
+        assert!(metadata.fields().contains(&("Total Edit Time".to_string(), "01:35:00".to_string())));
+    }
+
+    #[test]
+    fn test_is_office_document() {
+        assert!(is_office_document("C:\\Users\\alice\\Documents\\Budget.XLSX"));
+        assert!(is_office_document("invoice.doc"));
+        assert!(!is_office_document("evidence.zip"));
+        assert!(!is_office_document("README"));
+    }
+}
@@ -124,6 +124,10 @@ impl From<u8> for OleEntryType {
 /// Special sector values
 const FREESECT: u32 = 0xFFFFFFFF;
 const ENDOFCHAIN: u32 = 0xFFFFFFFE;
+#[allow(dead_code)]
+const FATSECT: u32 = 0xFFFFFFFD;
+#[allow(dead_code)]
+const DIFSECT: u32 = 0xFFFFFFFC;
 
 impl OleCompoundDocument {
     /// Parse OLE compound document from bytes
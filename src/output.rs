@@ -422,6 +422,8 @@ mod tests {
                 resident: true,
             }],
             location: Some("Users\\TestUser".to_string()),
+            event_source: Some("MFT".to_string()),
+            metadata: Vec::new(),
         }
     }
 
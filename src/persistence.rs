@@ -0,0 +1,331 @@
+//! Autostart persistence inventory for offline SYSTEM, SOFTWARE and NTUSER.DAT hives
+//!
+//! Supports parsing of:
+//! - Run / RunOnce (HKLM, HKCU and Wow6432Node)
+//! - Services (ImagePath, Start type, ServiceDll)
+//! - Winlogon Shell and Userinit
+//! - Image File Execution Options debuggers
+//! - AppInit_DLLs
+//! - Active Setup StubPath
+
+use crate::registry::{RegistryEventType, RegistryHive, RegistryKey, RegistryTimelineEvent};
+use crate::types::EventTimestamps;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Hive-relative prefixes for HKLM\SOFTWARE and HKCU\Software keys (native and 32-bit views)
+const SOFTWARE_PREFIXES: [&str; 4] = [
+    "Microsoft",
+    "Wow6432Node\\Microsoft",
+    "Software\\Microsoft",
+    "Software\\Wow6432Node\\Microsoft",
+];
+
+/// Autostart location category
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PersistenceCategory {
+    /// Run key
+    Run,
+    /// RunOnce key
+    RunOnce,
+    /// Service or driver
+    Service,
+    /// Winlogon Shell/Userinit
+    Winlogon,
+    /// Image File Execution Options debugger
+    ImageFileExecutionOptions,
+    /// AppInit_DLLs
+    AppInitDlls,
+    /// Active Setup StubPath
+    ActiveSetup,
+}
+
+impl PersistenceCategory {
+    /// Display name for the category
+    pub fn name(&self) -> &'static str {
+        match self {
+            PersistenceCategory::Run => "Run",
+            PersistenceCategory::RunOnce => "RunOnce",
+            PersistenceCategory::Service => "Service",
+            PersistenceCategory::Winlogon => "Winlogon",
+            PersistenceCategory::ImageFileExecutionOptions => "IFEO Debugger",
+            PersistenceCategory::AppInitDlls => "AppInit_DLLs",
+            PersistenceCategory::ActiveSetup => "Active Setup",
+        }
+    }
+}
+
+/// Single autostart entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersistenceEntry {
+    /// Autostart category
+    pub category: PersistenceCategory,
+    /// Hive file the entry came from
+    pub hive: String,
+    /// Registry key path holding the entry
+    pub key_path: String,
+    /// Entry name (value name, service name or target executable)
+    pub name: String,
+    /// Command line, image path or DLL list
+    pub command: String,
+    /// Last write time of the key
+    pub last_written: Option<DateTime<Utc>>,
+    /// Category-specific details (start type, ServiceDll, ...)
+    pub details: Vec<(String, String)>,
+}
+
+/// Structured persistence report across one or more hives
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersistenceReport {
+    /// Hive files examined
+    pub hives: Vec<String>,
+    /// Number of entries per category
+    pub summary: Vec<(String, usize)>,
+    /// All autostart entries
+    pub entries: Vec<PersistenceEntry>,
+}
+
+/// Collect every supported autostart entry from a hive
+pub fn parse_persistence(hive: &RegistryHive) -> Vec<PersistenceEntry> {
+    let mut entries = Vec::new();
+
+    for prefix in SOFTWARE_PREFIXES {
+        for (subkey, category) in [("Run", PersistenceCategory::Run), ("RunOnce", PersistenceCategory::RunOnce)] {
+            if let Some(key) = hive.open_key(&format!("{}\\Windows\\CurrentVersion\\{}", prefix, subkey)) {
+                for value in &key.values {
+                    if let Some(command) = value.string_data().filter(|c| !c.is_empty()) {
+                        entries.push(entry(hive, category, &key, &value.name, command, Vec::new()));
+                    }
+                }
+            }
+        }
+
+        if let Some(key) = hive.open_key(&format!("{}\\Windows NT\\CurrentVersion\\Winlogon", prefix)) {
+            for name in ["Shell", "Userinit"] {
+                if let Some(command) = key.string_value(name) {
+                    entries.push(entry(hive, PersistenceCategory::Winlogon, &key, name, command, Vec::new()));
+                }
+            }
+        }
+
+        if let Some(key) = hive.open_key(&format!("{}\\Windows NT\\CurrentVersion\\Image File Execution Options", prefix)) {
+            for image in &key.subkeys {
+                if let Some(debugger) = image.string_value("Debugger") {
+                    entries.push(entry(hive, PersistenceCategory::ImageFileExecutionOptions, image, &image.name, debugger, Vec::new()));
+                }
+            }
+        }
+
+        if let Some(key) = hive.open_key(&format!("{}\\Windows NT\\CurrentVersion\\Windows", prefix)) {
+            if let Some(dlls) = key.string_value("AppInit_DLLs") {
+                let mut details = Vec::new();
+                if let Some(load) = key.value("LoadAppInit_DLLs").and_then(|v| v.dword_data()) {
+                    details.push(("LoadAppInit_DLLs".to_string(), load.to_string()));
+                }
+                entries.push(entry(hive, PersistenceCategory::AppInitDlls, &key, "AppInit_DLLs", dlls, details));
+            }
+        }
+
+        if let Some(key) = hive.open_key(&format!("{}\\Active Setup\\Installed Components", prefix)) {
+            for component in &key.subkeys {
+                if let Some(stub) = component.string_value("StubPath") {
+                    let mut details = Vec::new();
+                    if let Some(name) = component.string_value("") {
+                        details.push(("Component".to_string(), name));
+                    }
+                    if let Some(version) = component.string_value("Version") {
+                        details.push(("Version".to_string(), version));
+                    }
+                    entries.push(entry(hive, PersistenceCategory::ActiveSetup, component, &component.name, stub, details));
+                }
+            }
+        }
+    }
+
+    if let Some(control_set) = hive.current_control_set() {
+        if let Some(services) = hive.open_key(&format!("{}\\Services", control_set)) {
+            for service in &services.subkeys {
+                entries.extend(parse_service(hive, service));
+            }
+        }
+    }
+
+    entries
+}
+
+/// Parse a Services\<name> key (ImagePath, Start, ServiceDll)
+fn parse_service(hive: &RegistryHive, service: &RegistryKey) -> Option<PersistenceEntry> {
+    let image_path = service.string_value("ImagePath");
+    let service_dll = service
+        .subkey("Parameters")
+        .and_then(|p| p.string_value("ServiceDll"))
+        .or_else(|| service.string_value("ServiceDll"));
+    let command = image_path.clone().or_else(|| service_dll.clone())?;
+
+    let mut details = Vec::new();
+    if let Some(start) = service.value("Start").and_then(|v| v.dword_data()) {
+        details.push(("Start".to_string(), start_type_name(start).to_string()));
+    }
+    if let Some(display_name) = service.string_value("DisplayName") {
+        details.push(("Display Name".to_string(), display_name));
+    }
+    if let Some(dll) = service_dll {
+        details.push(("ServiceDll".to_string(), dll));
+    }
+
+    Some(entry(hive, PersistenceCategory::Service, service, &service.name, command, details))
+}
+
+/// Map a service Start value to its name
+fn start_type_name(start: u32) -> &'static str {
+    match start {
+        0 => "Boot",
+        1 => "System",
+        2 => "Automatic",
+        3 => "Manual",
+        4 => "Disabled",
+        _ => "Unknown",
+    }
+}
+
+/// Build an entry stamped with the key's last write time
+fn entry(
+    hive: &RegistryHive,
+    category: PersistenceCategory,
+    key: &RegistryKey,
+    name: &str,
+    command: String,
+    details: Vec<(String, String)>,
+) -> PersistenceEntry {
+    PersistenceEntry {
+        category,
+        hive: hive.file_path.clone(),
+        key_path: key.path.clone(),
+        name: name.to_string(),
+        command,
+        last_written: key.last_written,
+        details,
+    }
+}
+
+/// Build a persistence report across hives
+pub fn persistence_report(hives: &[&RegistryHive]) -> PersistenceReport {
+    let entries: Vec<PersistenceEntry> = hives.iter().flat_map(|h| parse_persistence(h)).collect();
+
+    let mut summary: Vec<(String, usize)> = Vec::new();
+    for entry in &entries {
+        let name = entry.category.name();
+        match summary.iter_mut().find(|(n, _)| n == name) {
+            Some((_, count)) => *count += 1,
+            None => summary.push((name.to_string(), 1)),
+        }
+    }
+
+    PersistenceReport {
+        hives: hives.iter().map(|h| h.file_path.clone()).collect(),
+        summary,
+        entries,
+    }
+}
+
+/// Extract autostart entries as timeline events at their key's last write time
+pub fn extract_timeline_events(hive: &RegistryHive) -> Vec<RegistryTimelineEvent> {
+    parse_persistence(hive)
+        .into_iter()
+        .map(|entry| {
+            let mut metadata = vec![
+                ("Category".to_string(), entry.category.name().to_string()),
+                ("Name".to_string(), entry.name.clone()),
+                ("Command".to_string(), entry.command.clone()),
+            ];
+            metadata.extend(entry.details.iter().cloned());
+
+            RegistryTimelineEvent {
+                key_path: entry.key_path.clone(),
+                value_name: Some(entry.name.clone()),
+                event_type: RegistryEventType::RunEntry,
+                value_type: None,
+                data: None,
+                data_size: None,
+                timestamps: EventTimestamps {
+                    created: None,
+                    modified: entry.last_written,
+                    accessed: None,
+                    mft_modified: None,
+                },
+                description: Some(format!("{}: {} -> {}", entry.category.name(), entry.name, entry.command)),
+                metadata,
+                source: Some("Persistence".to_string()),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::test_hive::TestKey;
+
+    #[test]
+    fn test_parse_persistence() {
+        let software = TestKey::new("ROOT")
+            .with(
+                "Microsoft\\Windows\\CurrentVersion",
+                TestKey::new("Run").string("SecurityHealth", "C:\\Windows\\system32\\SecurityHealthSystray.exe"),
+            )
+            .with(
+                "Wow6432Node\\Microsoft\\Windows\\CurrentVersion",
+                TestKey::new("RunOnce").string("Updater", "C:\\Users\\Public\\upd.exe").string("Empty", ""),
+            )
+            .with(
+                "Microsoft\\Windows NT\\CurrentVersion\\Image File Execution Options",
+                TestKey::new("sethc.exe").string("Debugger", "C:\\Windows\\system32\\cmd.exe"),
+            )
+            .with(
+                "Microsoft\\Windows NT\\CurrentVersion\\Image File Execution Options",
+                TestKey::new("notepad.exe").dword("GlobalFlag", 0x200),
+            )
+            .open("SOFTWARE");
+
+        let entries = parse_persistence(&software);
+        assert_eq!(entries.len(), 3);
+        let run = entries.iter().find(|e| e.category == PersistenceCategory::Run).unwrap();
+        assert_eq!(run.name, "SecurityHealth");
+        assert_eq!(run.command, "C:\\Windows\\system32\\SecurityHealthSystray.exe");
+        assert!(run.last_written.is_some());
+        let run_once = entries.iter().find(|e| e.category == PersistenceCategory::RunOnce).unwrap();
+        assert_eq!(run_once.name, "Updater");
+        assert!(run_once.key_path.contains("Wow6432Node"));
+        let ifeo = entries
+            .iter()
+            .find(|e| e.category == PersistenceCategory::ImageFileExecutionOptions)
+            .unwrap();
+        assert_eq!(ifeo.name, "sethc.exe");
+        assert_eq!(ifeo.command, "C:\\Windows\\system32\\cmd.exe");
+
+        let system = TestKey::new("ROOT")
+            .with("", TestKey::new("Select").dword("Current", 1))
+            .with(
+                "ControlSet001\\Services",
+                TestKey::new("EvilSvc")
+                    .string("ImagePath", "%SystemRoot%\\system32\\svchost.exe -k netsvcs")
+                    .dword("Start", 2)
+                    .subkey(TestKey::new("Parameters").string("ServiceDll", "C:\\Windows\\evil.dll")),
+            )
+            .with("ControlSet001\\Services", TestKey::new("NoImage").dword("Start", 3))
+            .open("SYSTEM");
+
+        let entries = parse_persistence(&system);
+        assert_eq!(entries.len(), 1);
+        let service = &entries[0];
+        assert_eq!(service.category, PersistenceCategory::Service);
+        assert_eq!(service.name, "EvilSvc");
+        assert_eq!(service.command, "%SystemRoot%\\system32\\svchost.exe -k netsvcs");
+        assert!(service.details.contains(&("Start".to_string(), "Automatic".to_string())));
+        assert!(service.details.contains(&("ServiceDll".to_string(), "C:\\Windows\\evil.dll".to_string())));
+
+        let report = persistence_report(&[&software, &system]);
+        assert_eq!(report.entries.len(), 4);
+        assert!(report.summary.contains(&("Service".to_string(), 1)));
+    }
+}
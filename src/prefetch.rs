@@ -0,0 +1,249 @@
+//! Windows Prefetch (.pf) parsing module
+//!
+//! Supports parsing of:
+//! - Version 17 (Windows XP / 2003)
+//! - Version 23 (Windows Vista / 7)
+//! - Version 26 (Windows 8.x)
+//! - Version 30 / 31 (Windows 10 / 11), including MAM (Xpress Huffman) compressed files
+
+use crate::error::{Error, Result};
+use crate::registry::{filetime_to_datetime, read_u32, read_u64, utf16_string};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Uncompressed prefetch signature at offset 4
+const SCCA_SIGNATURE: &[u8; 4] = b"SCCA";
+/// Compressed prefetch signature ("MAM" followed by the compression format)
+const MAM_SIGNATURE: &[u8; 3] = b"MAM";
+/// MAM compression format for Xpress Huffman
+const MAM_XPRESS_HUFFMAN: u8 = 0x04;
+/// MAM flag indicating a CRC32 follows the decompressed size
+const MAM_CRC_FLAG: u8 = 0x80;
+/// Upper bound on decompressed size to guard against corrupt headers
+const MAX_DECOMPRESSED_SIZE: usize = 64 * 1024 * 1024;
+
+/// Volume referenced by a prefetch file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrefetchVolume {
+    /// Volume device path (e.g. \VOLUME{01d...-1234abcd})
+    pub device_path: String,
+    /// Volume creation time
+    pub created: Option<DateTime<Utc>>,
+    /// Volume serial number
+    pub serial_number: String,
+}
+
+/// Parsed prefetch file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrefetchFile {
+    /// Format version (17, 23, 26, 30, 31)
+    pub version: u32,
+    /// Whether the file was MAM compressed
+    pub compressed: bool,
+    /// Executable name from the header
+    pub executable: String,
+    /// Prefetch path hash
+    pub hash: String,
+    /// Run count
+    pub run_count: u32,
+    /// Last run times, most recent first (up to eight)
+    pub last_run_times: Vec<DateTime<Utc>>,
+    /// Files loaded during the first seconds of execution
+    pub loaded_files: Vec<String>,
+    /// Volumes referenced
+    pub volumes: Vec<PrefetchVolume>,
+}
+
+impl PrefetchFile {
+    /// Parse a prefetch file, decompressing MAM-compressed data first
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        if data.len() >= 8 && &data[0..3] == MAM_SIGNATURE {
+            let decompressed = decompress_mam(data)?;
+            let mut prefetch = Self::parse_uncompressed(&decompressed)?;
+            prefetch.compressed = true;
+            return Ok(prefetch);
+        }
+        Self::parse_uncompressed(data)
+    }
+
+    /// Parse an uncompressed (SCCA) prefetch file
+    fn parse_uncompressed(data: &[u8]) -> Result<Self> {
+        if data.len() < 84 || &data[4..8] != SCCA_SIGNATURE {
+            return Err(Error::ParseError("Invalid prefetch signature".to_string()));
+        }
+
+        let version = read_u32(data, 0).unwrap_or(0);
+        let executable = utf16_string(&data[16..76]);
+        let hash = format!("{:08X}", read_u32(data, 76).unwrap_or(0));
+
+        let field = |offset: usize| {
+            read_u32(data, offset).ok_or_else(|| Error::ParseError("Prefetch file information truncated".to_string()))
+        };
+        let metrics_offset = field(84)?;
+        let filenames_offset = field(100)? as usize;
+        let filenames_size = field(104)? as usize;
+        let volumes_offset = field(108)? as usize;
+        let volume_count = field(112)? as usize;
+
+        // (last run times offset, number of run times, run count offset, volume entry size)
+        let (run_times_offset, run_time_count, run_count_offset, volume_entry_size) = match version {
+            17 => (120, 1, 144, 40),
+            23 => (128, 1, 152, 104),
+            26 => (128, 8, 208, 104),
+            // Windows 10 variant with a 216-byte file information block has the run count at 200
+            30 | 31 if metrics_offset == 0x128 => (128, 8, 200, 96),
+            30 | 31 => (128, 8, 208, 96),
+            _ => return Err(Error::ParseError(format!("Unsupported prefetch version {}", version))),
+        };
+
+        let last_run_times = (0..run_time_count)
+            .filter_map(|i| read_u64(data, run_times_offset + i * 8))
+            .filter_map(filetime_to_datetime)
+            .collect();
+        let run_count = read_u32(data, run_count_offset).unwrap_or(0);
+
+        let loaded_files = data
+            .get(filenames_offset..filenames_offset.saturating_add(filenames_size))
+            .map(split_utf16_strings)
+            .unwrap_or_default();
+
+        let volumes = (0..volume_count)
+            .filter_map(|i| parse_volume(data, volumes_offset, volumes_offset + i * volume_entry_size))
+            .collect();
+
+        Ok(PrefetchFile {
+            version,
+            compressed: false,
+            executable,
+            hash,
+            run_count,
+            last_run_times,
+            loaded_files,
+            volumes,
+        })
+    }
+}
+
+/// Parse a volume information entry (device path offset is relative to the volumes block)
+fn parse_volume(data: &[u8], volumes_offset: usize, entry: usize) -> Option<PrefetchVolume> {
+    let path_offset = volumes_offset + read_u32(data, entry)? as usize;
+    let path_chars = read_u32(data, entry + 4)? as usize;
+    let device_path = utf16_string(data.get(path_offset..path_offset + path_chars * 2)?);
+
+    Some(PrefetchVolume {
+        device_path,
+        created: read_u64(data, entry + 8).and_then(filetime_to_datetime),
+        serial_number: format!("{:08X}", read_u32(data, entry + 16)?),
+    })
+}
+
+/// Split a block of null-terminated UTF-16 strings
+fn split_utf16_strings(data: &[u8]) -> Vec<String> {
+    let units: Vec<u16> = data
+        .chunks_exact(2)
+        .map(|c| u16::from_le_bytes([c[0], c[1]]))
+        .collect();
+    units
+        .split(|&c| c == 0)
+        .filter(|s| !s.is_empty())
+        .map(String::from_utf16_lossy)
+        .collect()
+}
+
+/// Decompress a MAM container (Windows 10+ prefetch)
+pub fn decompress_mam(data: &[u8]) -> Result<Vec<u8>> {
+    let format = data[3];
+    if format & 0x0F != MAM_XPRESS_HUFFMAN {
+        return Err(Error::ParseError(format!("Unsupported MAM compression format {:#x}", format)));
+    }
+
+    let size = read_u32(data, 4).unwrap_or(0) as usize;
+    if size == 0 || size > MAX_DECOMPRESSED_SIZE {
+        return Err(Error::ParseError(format!("Invalid MAM decompressed size {}", size)));
+    }
+
+    let start = if format & MAM_CRC_FLAG != 0 { 12 } else { 8 };
+    let compressed = data
+        .get(start..)
+        .ok_or_else(|| Error::ParseError("MAM header truncated".to_string()))?;
+    crate::xpress::decompress_huffman(compressed, size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::test_hive::utf16;
+    use chrono::Datelike;
+
+    /// Build a minimal version 30 prefetch file with two run times, two files and one volume
+    fn build_v30() -> Vec<u8> {
+        let mut data = vec![0u8; 0x130];
+        data[0..4].copy_from_slice(&30u32.to_le_bytes());
+        data[4..8].copy_from_slice(SCCA_SIGNATURE);
+        let name = utf16("NOTEPAD.EXE");
+        data[16..16 + name.len()].copy_from_slice(&name);
+        data[76..80].copy_from_slice(&0xD8414F97u32.to_le_bytes());
+        data[84..88].copy_from_slice(&0x130u32.to_le_bytes());
+        data[128..136].copy_from_slice(&132223104000000000u64.to_le_bytes()); // 2020-01-01
+        data[136..144].copy_from_slice(&131592384000000000u64.to_le_bytes()); // 2018-01-01
+        data[208..212].copy_from_slice(&7u32.to_le_bytes());
+
+        let files = utf16("\\VOLUME{1}\\WINDOWS\\SYSTEM32\\NTDLL.DLL\0\\VOLUME{1}\\WINDOWS\\NOTEPAD.EXE\0");
+        let filenames_offset = data.len();
+        data.extend_from_slice(&files);
+        data[100..104].copy_from_slice(&(filenames_offset as u32).to_le_bytes());
+        data[104..108].copy_from_slice(&(files.len() as u32).to_le_bytes());
+
+        let volumes_offset = data.len();
+        let path = utf16("\\VOLUME{1}");
+        let mut volume = vec![0u8; 96];
+        volume[0..4].copy_from_slice(&96u32.to_le_bytes());
+        volume[4..8].copy_from_slice(&10u32.to_le_bytes());
+        volume[16..20].copy_from_slice(&0x1234ABCDu32.to_le_bytes());
+        data.extend_from_slice(&volume);
+        data.extend_from_slice(&path);
+        data[108..112].copy_from_slice(&(volumes_offset as u32).to_le_bytes());
+        data[112..116].copy_from_slice(&1u32.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn test_parse_v30() {
+        let prefetch = PrefetchFile::parse(&build_v30()).unwrap();
+        assert_eq!(prefetch.executable, "NOTEPAD.EXE");
+        assert_eq!(prefetch.hash, "D8414F97");
+        assert_eq!(prefetch.run_count, 7);
+        assert_eq!(prefetch.last_run_times.len(), 2);
+        assert_eq!(prefetch.last_run_times[0].year(), 2020);
+        assert_eq!(prefetch.loaded_files.len(), 2);
+        assert_eq!(prefetch.volumes[0].device_path, "\\VOLUME{1}");
+        assert_eq!(prefetch.volumes[0].serial_number, "1234ABCD");
+    }
+
+    #[test]
+    fn test_parse_mam_compressed() {
+        let plain = build_v30();
+
+        // Literal-only Xpress Huffman stream: all 512 symbols use 9-bit codes equal to the symbol
+        let mut bits: Vec<bool> = plain.iter().flat_map(|&b| (0..9).rev().map(move |i| (b as u16) >> i & 1 == 1)).collect();
+        bits.resize(bits.len().div_ceil(16) * 16 + 32, false);
+        let mut data = b"MAM\x04".to_vec();
+        data.extend_from_slice(&(plain.len() as u32).to_le_bytes());
+        data.extend(vec![0x99u8; 256]);
+        for chunk in bits.chunks(16) {
+            let word = chunk.iter().fold(0u16, |acc, &b| (acc << 1) | b as u16);
+            data.extend_from_slice(&word.to_le_bytes());
+        }
+
+        let prefetch = PrefetchFile::parse(&data).unwrap();
+        assert!(prefetch.compressed);
+        assert_eq!(prefetch.executable, "NOTEPAD.EXE");
+        assert_eq!(prefetch.run_count, 7);
+    }
+
+    #[test]
+    fn test_invalid_signature() {
+        assert!(PrefetchFile::parse(&[0u8; 100]).is_err());
+        assert!(PrefetchFile::parse(b"MAM\x02\x10\x00\x00\x00").is_err());
+    }
+}
@@ -0,0 +1,305 @@
+//! Windows property system schema
+//!
+//! Supports parsing of:
+//! - System.*, Document.*, Media.*, Music.*, Link.*, Volume.*, Photo.* and GPS property keys
+//! - Typed interpretation of values (timestamps, sizes, durations, file attributes, GPS coordinates)
+
+use crate::property_store::PropertyData;
+
+/// How a property value should be interpreted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PropertyKind {
+    /// Free text or identifier
+    Text,
+    /// Plain number
+    Number,
+    /// FILETIME that dates the item
+    Timestamp,
+    /// Size in bytes
+    Size,
+    /// Duration in 100-nanosecond units
+    Duration,
+    /// FILE_ATTRIBUTE_* flags
+    Attributes,
+    /// Degrees, minutes and seconds
+    GpsCoordinate,
+}
+
+/// A property key with its canonical name and value interpretation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PropertyDefinition {
+    /// Format ID (GUID without braces)
+    pub format_id: &'static str,
+    /// Property ID within the format
+    pub property_id: u32,
+    /// Canonical name (e.g. System.Photo.DateTaken)
+    pub name: &'static str,
+    /// Value interpretation
+    pub kind: PropertyKind,
+}
+
+macro_rules! schema {
+    ($(($format_id:literal, $property_id:literal, $name:literal, $kind:ident)),* $(,)?) => {
+        &[$(PropertyDefinition {
+            format_id: $format_id,
+            property_id: $property_id,
+            name: $name,
+            kind: PropertyKind::$kind,
+        }),*]
+    };
+}
+
+/// Property keys from propkey.h
+const SCHEMA: &[PropertyDefinition] = schema![
+    // Storage (file system) properties
+    ("B725F130-47EF-101A-A5F1-02608C9EEBAC", 2, "System.ItemFolderNameDisplay", Text),
+    ("B725F130-47EF-101A-A5F1-02608C9EEBAC", 4, "System.ItemTypeText", Text),
+    ("B725F130-47EF-101A-A5F1-02608C9EEBAC", 10, "System.ItemNameDisplay", Text),
+    ("B725F130-47EF-101A-A5F1-02608C9EEBAC", 12, "System.Size", Size),
+    ("B725F130-47EF-101A-A5F1-02608C9EEBAC", 13, "System.FileAttributes", Attributes),
+    ("B725F130-47EF-101A-A5F1-02608C9EEBAC", 14, "System.DateModified", Timestamp),
+    ("B725F130-47EF-101A-A5F1-02608C9EEBAC", 15, "System.DateCreated", Timestamp),
+    ("B725F130-47EF-101A-A5F1-02608C9EEBAC", 16, "System.DateAccessed", Timestamp),
+    ("B725F130-47EF-101A-A5F1-02608C9EEBAC", 18, "System.FileAllocationSize", Size),
+    ("41CF5AE0-F75A-4806-BD87-59C7D9248EB9", 100, "System.FileName", Text),
+    ("E4F10A3C-49E6-405D-8288-A23BD4EEAA6C", 100, "System.FileExtension", Text),
+    ("9B174B34-40FF-11D2-A27E-00C04FC30871", 4, "System.FileOwner", Text),
+    ("2CBAA8F5-D81F-47CA-B17A-F8D822300131", 100, "System.DateAcquired", Timestamp),
+    ("F7DB74B4-4287-4103-AFBA-F1B13DCD75CF", 100, "System.ItemDate", Timestamp),
+    ("1E3EE840-BC2B-476C-8237-2ACD1A839B22", 3, "System.Kind", Text),
+    ("46588AE2-4CBC-4338-BBFC-139326986DCE", 4, "System.SID", Text),
+    ("446D16B1-8DAD-4870-A748-402EA43D788C", 100, "System.ThumbnailCacheId", Number),
+    ("446D16B1-8DAD-4870-A748-402EA43D788C", 104, "System.VolumeId", Text),
+    ("9F4C2855-9F79-4B39-A8D0-E1D42DE1D5F3", 5, "System.AppUserModel.ID", Text),
+    ("841E4F90-FF59-4D16-8947-E81BBFFAB36D", 16, "System.Software.DateLastUsed", Timestamp),
+    // Shell item properties
+    ("28636AA6-953D-11D2-B5D6-00C04FD918D0", 5, "System.ComputerName", Text),
+    ("28636AA6-953D-11D2-B5D6-00C04FD918D0", 8, "System.ItemPathDisplayNarrow", Text),
+    ("28636AA6-953D-11D2-B5D6-00C04FD918D0", 9, "System.PerceivedType", Number),
+    ("28636AA6-953D-11D2-B5D6-00C04FD918D0", 11, "System.ItemType", Text),
+    ("28636AA6-953D-11D2-B5D6-00C04FD918D0", 24, "System.ParsingName", Text),
+    ("28636AA6-953D-11D2-B5D6-00C04FD918D0", 25, "System.SFGAOFlags", Number),
+    ("28636AA6-953D-11D2-B5D6-00C04FD918D0", 30, "System.ParsingPath", Text),
+    ("E3E0584C-B788-4A5A-BB20-7F5A44C9ACDD", 6, "System.ItemFolderPathDisplay", Text),
+    ("E3E0584C-B788-4A5A-BB20-7F5A44C9ACDD", 7, "System.ItemPathDisplay", Text),
+    ("E3E0584C-B788-4A5A-BB20-7F5A44C9ACDD", 19, "System.Message.DateSent", Timestamp),
+    ("E3E0584C-B788-4A5A-BB20-7F5A44C9ACDD", 20, "System.Message.DateReceived", Timestamp),
+    ("49691C90-7E17-101A-A91C-08002B2ECDA9", 9, "System.ItemUrl", Text),
+    // Summary information (Document.*)
+    ("F29F85E0-4FF9-1068-AB91-08002B27B3D9", 2, "System.Title", Text),
+    ("F29F85E0-4FF9-1068-AB91-08002B27B3D9", 3, "System.Subject", Text),
+    ("F29F85E0-4FF9-1068-AB91-08002B27B3D9", 4, "System.Author", Text),
+    ("F29F85E0-4FF9-1068-AB91-08002B27B3D9", 5, "System.Keywords", Text),
+    ("F29F85E0-4FF9-1068-AB91-08002B27B3D9", 6, "System.Comment", Text),
+    ("F29F85E0-4FF9-1068-AB91-08002B27B3D9", 7, "System.Document.Template", Text),
+    ("F29F85E0-4FF9-1068-AB91-08002B27B3D9", 8, "System.Document.LastAuthor", Text),
+    ("F29F85E0-4FF9-1068-AB91-08002B27B3D9", 9, "System.Document.RevisionNumber", Text),
+    ("F29F85E0-4FF9-1068-AB91-08002B27B3D9", 10, "System.Document.TotalEditingTime", Duration),
+    ("F29F85E0-4FF9-1068-AB91-08002B27B3D9", 11, "System.Document.DatePrinted", Timestamp),
+    ("F29F85E0-4FF9-1068-AB91-08002B27B3D9", 12, "System.Document.DateCreated", Timestamp),
+    ("F29F85E0-4FF9-1068-AB91-08002B27B3D9", 13, "System.Document.DateSaved", Timestamp),
+    ("F29F85E0-4FF9-1068-AB91-08002B27B3D9", 14, "System.Document.PageCount", Number),
+    ("F29F85E0-4FF9-1068-AB91-08002B27B3D9", 15, "System.Document.WordCount", Number),
+    ("F29F85E0-4FF9-1068-AB91-08002B27B3D9", 16, "System.Document.CharacterCount", Number),
+    ("F29F85E0-4FF9-1068-AB91-08002B27B3D9", 18, "System.ApplicationName", Text),
+    // Document summary information
+    ("D5CDD502-2E9C-101B-9397-08002B2CF9AE", 2, "System.Category", Text),
+    ("D5CDD502-2E9C-101B-9397-08002B2CF9AE", 3, "System.Document.PresentationFormat", Text),
+    ("D5CDD502-2E9C-101B-9397-08002B2CF9AE", 4, "System.Document.ByteCount", Size),
+    ("D5CDD502-2E9C-101B-9397-08002B2CF9AE", 5, "System.Document.LineCount", Number),
+    ("D5CDD502-2E9C-101B-9397-08002B2CF9AE", 6, "System.Document.ParagraphCount", Number),
+    ("D5CDD502-2E9C-101B-9397-08002B2CF9AE", 7, "System.Document.SlideCount", Number),
+    ("D5CDD502-2E9C-101B-9397-08002B2CF9AE", 8, "System.Document.NoteCount", Number),
+    ("D5CDD502-2E9C-101B-9397-08002B2CF9AE", 9, "System.Document.HiddenSlideCount", Number),
+    ("D5CDD502-2E9C-101B-9397-08002B2CF9AE", 14, "System.Document.Manager", Text),
+    ("D5CDD502-2E9C-101B-9397-08002B2CF9AE", 15, "System.Company", Text),
+    ("D5CDD502-2E9C-101B-9397-08002B2CF9AE", 26, "System.ContentType", Text),
+    ("D5CDD502-2E9C-101B-9397-08002B2CF9AE", 27, "System.ContentStatus", Text),
+    ("D5CDD502-2E9C-101B-9397-08002B2CF9AE", 28, "System.Language", Text),
+    ("D5CDD502-2E9C-101B-9397-08002B2CF9AE", 29, "System.Document.Version", Text),
+    // Media, music and video
+    ("64440490-4C8B-11D1-8B70-080036B11A03", 3, "System.Media.Duration", Duration),
+    ("64440490-4C8B-11D1-8B70-080036B11A03", 4, "System.Audio.EncodingBitrate", Number),
+    ("64440490-4C8B-11D1-8B70-080036B11A03", 5, "System.Audio.SampleRate", Number),
+    ("64440491-4C8B-11D1-8B70-080036B11A03", 3, "System.Video.FrameWidth", Number),
+    ("64440491-4C8B-11D1-8B70-080036B11A03", 4, "System.Video.FrameHeight", Number),
+    ("64440491-4C8B-11D1-8B70-080036B11A03", 6, "System.Video.FrameRate", Number),
+    ("56A3372E-CE9C-11D2-9F0E-006097C686F6", 2, "System.Music.Artist", Text),
+    ("56A3372E-CE9C-11D2-9F0E-006097C686F6", 4, "System.Music.AlbumTitle", Text),
+    ("56A3372E-CE9C-11D2-9F0E-006097C686F6", 5, "System.Media.Year", Number),
+    ("56A3372E-CE9C-11D2-9F0E-006097C686F6", 7, "System.Music.TrackNumber", Number),
+    ("56A3372E-CE9C-11D2-9F0E-006097C686F6", 11, "System.Music.Genre", Text),
+    ("2E4B640D-5019-46D8-8881-55414CC5CAA0", 100, "System.Media.DateEncoded", Timestamp),
+    ("DE41CC29-6971-4290-B472-F59F2E2F31E2", 100, "System.Media.DateReleased", Text),
+    // Images and photos
+    ("6444048F-4C8B-11D1-8B70-080036B11A03", 3, "System.Image.HorizontalSize", Number),
+    ("6444048F-4C8B-11D1-8B70-080036B11A03", 4, "System.Image.VerticalSize", Number),
+    ("14B81DA1-0135-4D31-96D9-6CBFC9671A99", 271, "System.Photo.CameraManufacturer", Text),
+    ("14B81DA1-0135-4D31-96D9-6CBFC9671A99", 272, "System.Photo.CameraModel", Text),
+    ("14B81DA1-0135-4D31-96D9-6CBFC9671A99", 274, "System.Photo.Orientation", Number),
+    ("14B81DA1-0135-4D31-96D9-6CBFC9671A99", 305, "System.SoftwareUsed", Text),
+    ("14B81DA1-0135-4D31-96D9-6CBFC9671A99", 18258, "System.DateImported", Timestamp),
+    ("14B81DA1-0135-4D31-96D9-6CBFC9671A99", 33434, "System.Photo.ExposureTime", Number),
+    ("14B81DA1-0135-4D31-96D9-6CBFC9671A99", 33437, "System.Photo.FNumber", Number),
+    ("14B81DA1-0135-4D31-96D9-6CBFC9671A99", 34855, "System.Photo.ISOSpeed", Number),
+    ("14B81DA1-0135-4D31-96D9-6CBFC9671A99", 36867, "System.Photo.DateTaken", Timestamp),
+    ("14B81DA1-0135-4D31-96D9-6CBFC9671A99", 37385, "System.Photo.Flash", Number),
+    ("14B81DA1-0135-4D31-96D9-6CBFC9671A99", 37386, "System.Photo.FocalLength", Number),
+    // GPS
+    ("8727CFFF-4868-4EC6-AD5B-81B98521D1AB", 100, "System.GPS.Latitude", GpsCoordinate),
+    ("029C0252-5B86-46C7-ACA0-2769FFC8E3D4", 100, "System.GPS.LatitudeRef", Text),
+    ("C4C4DBB2-B593-466B-BBDA-D03D27D5E43A", 100, "System.GPS.Longitude", GpsCoordinate),
+    ("33DCF22B-28D5-464C-8035-1EE9EFD25278", 100, "System.GPS.LongitudeRef", Text),
+    ("827EDB4F-5B73-44A7-891D-FDFFABEA35CA", 100, "System.GPS.Altitude", Number),
+    ("3602C812-0F3B-45F0-85AD-603468D69423", 100, "System.GPS.Date", Timestamp),
+    // Links
+    ("B9B4B3FC-2B51-4A42-B5D8-324146AFCF25", 2, "System.Link.TargetParsingPath", Text),
+    ("B9B4B3FC-2B51-4A42-B5D8-324146AFCF25", 3, "System.Link.Status", Number),
+    ("B9B4B3FC-2B51-4A42-B5D8-324146AFCF25", 5, "System.Link.Comment", Text),
+    ("B9B4B3FC-2B51-4A42-B5D8-324146AFCF25", 8, "System.Link.TargetSFGAOFlags", Number),
+    ("436F2667-14E2-4FEB-B30A-146C53B5B674", 100, "System.Link.Arguments", Text),
+    ("5CBF2787-48CF-4208-B90E-EE5E5D420294", 2, "System.Link.TargetUrl", Text),
+    ("5CBF2787-48CF-4208-B90E-EE5E5D420294", 23, "System.Link.DateVisited", Timestamp),
+    ("7A7D76F4-B630-4BD7-95FF-37CC51A975C9", 2, "System.Link.TargetExtension", Text),
+    // Volumes
+    ("9B174B35-40FF-11D2-A27E-00C04FC30871", 2, "System.FreeSpace", Size),
+    ("9B174B35-40FF-11D2-A27E-00C04FC30871", 3, "System.Capacity", Size),
+    ("9B174B35-40FF-11D2-A27E-00C04FC30871", 4, "System.Volume.FileSystem", Text),
+];
+
+/// File attribute flag names
+const FILE_ATTRIBUTES: [(u32, &str); 10] = [
+    (0x0001, "ReadOnly"),
+    (0x0002, "Hidden"),
+    (0x0004, "System"),
+    (0x0010, "Directory"),
+    (0x0020, "Archive"),
+    (0x0080, "Normal"),
+    (0x0100, "Temporary"),
+    (0x0400, "ReparsePoint"),
+    (0x0800, "Compressed"),
+    (0x4000, "Encrypted"),
+];
+
+/// Find a property definition by format ID (with or without braces) and property ID
+pub fn lookup(format_id: &str, property_id: u32) -> Option<&'static PropertyDefinition> {
+    let format_id = format_id.trim_matches(|c| c == '{' || c == '}');
+    SCHEMA
+        .iter()
+        .find(|definition| definition.property_id == property_id && definition.format_id.eq_ignore_ascii_case(format_id))
+}
+
+/// Find a property definition by canonical name
+pub fn lookup_name(name: &str) -> Option<&'static PropertyDefinition> {
+    SCHEMA.iter().find(|definition| definition.name.eq_ignore_ascii_case(name))
+}
+
+impl PropertyDefinition {
+    /// Name without the "System." prefix (e.g. Photo.DateTaken)
+    pub fn short_name(&self) -> &'static str {
+        self.name.strip_prefix("System.").unwrap_or(self.name)
+    }
+
+    /// Render a value according to the property's kind
+    pub fn interpret(&self, value: &PropertyData) -> String {
+        match (self.kind, value) {
+            (PropertyKind::Size, _) => match as_u64(value) {
+                Some(bytes) => format!("{} bytes", bytes),
+                None => value.to_string(),
+            },
+            (PropertyKind::Duration, _) => match as_u64(value) {
+                Some(ticks) => {
+                    let seconds = ticks / 10_000_000;
+                    format!("{:02}:{:02}:{:02}", seconds / 3600, seconds / 60 % 60, seconds % 60)
+                }
+                None => value.to_string(),
+            },
+            (PropertyKind::Attributes, _) => match as_u64(value) {
+                Some(flags) => {
+                    let names: Vec<&str> = FILE_ATTRIBUTES
+                        .iter()
+                        .filter(|(flag, _)| flags as u32 & flag != 0)
+                        .map(|(_, name)| *name)
+                        .collect();
+                    format!("0x{:X} ({})", flags, names.join(", "))
+                }
+                None => value.to_string(),
+            },
+            (PropertyKind::GpsCoordinate, PropertyData::Array(parts)) if parts.len() == 3 => {
+                let parts: Vec<f64> = parts.iter().filter_map(as_f64).collect();
+                match parts[..] {
+                    [degrees, minutes, seconds] => format!("{}° {}' {:.2}\"", degrees, minutes, seconds),
+                    _ => value.to_string(),
+                }
+            }
+            _ => value.to_string(),
+        }
+    }
+}
+
+/// Convert a degrees/minutes/seconds coordinate and its N/S/E/W reference to signed decimal degrees
+pub fn gps_decimal(value: &PropertyData, reference: Option<&str>) -> Option<f64> {
+    let PropertyData::Array(parts) = value else {
+        return None;
+    };
+    let parts: Vec<f64> = parts.iter().filter_map(as_f64).collect();
+    let [degrees, minutes, seconds] = parts[..] else {
+        return None;
+    };
+    let decimal = degrees + minutes / 60.0 + seconds / 3600.0;
+    match reference.map(|r| r.trim().to_ascii_uppercase()) {
+        Some(r) if r == "S" || r == "W" => Some(-decimal),
+        _ => Some(decimal),
+    }
+}
+
+/// Integer value of a numeric property
+fn as_u64(value: &PropertyData) -> Option<u64> {
+    match value {
+        PropertyData::UI4(v) => Some(*v as u64),
+        PropertyData::UI8(v) => Some(*v),
+        PropertyData::I4(v) => u64::try_from(*v).ok(),
+        PropertyData::I8(v) => u64::try_from(*v).ok(),
+        _ => None,
+    }
+}
+
+/// Floating-point value of a numeric property
+fn as_f64(value: &PropertyData) -> Option<f64> {
+    match value {
+        PropertyData::Double(v) => Some(*v),
+        PropertyData::Float(v) => Some(*v as f64),
+        other => as_u64(other).map(|v| v as f64),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookup() {
+        let taken = lookup("{14b81da1-0135-4d31-96d9-6cbfc9671a99}", 36867).unwrap();
+        assert_eq!(taken.name, "System.Photo.DateTaken");
+        assert_eq!(taken.short_name(), "Photo.DateTaken");
+        assert_eq!(taken.kind, PropertyKind::Timestamp);
+        assert_eq!(lookup_name("System.DateAcquired").unwrap().property_id, 100);
+        assert!(lookup("B725F130-47EF-101A-A5F1-02608C9EEBAC", 9999).is_none());
+    }
+
+    #[test]
+    fn test_interpret_values() {
+        let size = lookup_name("System.Size").unwrap();
+        assert_eq!(size.interpret(&PropertyData::UI8(2048)), "2048 bytes");
+
+        let duration = lookup_name("System.Media.Duration").unwrap();
+        assert_eq!(duration.interpret(&PropertyData::UI8(37_230_000_000)), "01:02:03");
+
+        let attributes = lookup_name("System.FileAttributes").unwrap();
+        assert_eq!(attributes.interpret(&PropertyData::UI4(0x21)), "0x21 (ReadOnly, Archive)");
+
+        let latitude = PropertyData::Array(vec![PropertyData::Double(51.0), PropertyData::Double(30.0), PropertyData::Double(36.0)]);
+        assert_eq!(lookup_name("System.GPS.Latitude").unwrap().interpret(&latitude), "51° 30' 36.00\"");
+        assert_eq!(gps_decimal(&latitude, Some("S")), Some(-51.51));
+    }
+}
@@ -1,9 +1,10 @@
 //! Windows PropertyStore parsing module
 //!
 //! Parses serialized property stores found in LNK files, jumplists, and shell items.
-//! Supports thousands of FormatID/PropertyID combinations with proper data type handling.
+//! Property names and typed value interpretation come from the static schema in `property_schema`.
 
 use crate::error::{Error, Result};
+use crate::property_schema::{self, PropertyKind};
 use byteorder::{LittleEndian, ReadBytesExt};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -43,8 +44,10 @@ pub struct PropertyValue {
     pub raw_data: Vec<u8>,
     /// Parsed value
     pub parsed_value: PropertyData,
-    /// Human-readable description
+    /// Canonical property name from the schema (e.g. System.Photo.DateTaken)
     pub description: Option<String>,
+    /// Value interpreted according to the property's schema kind
+    pub display_value: String,
 }
 
 /// Property data types
@@ -82,6 +85,38 @@ pub enum PropertyData {
     Unknown(Vec<u8>),
 }
 
+impl std::fmt::Display for PropertyData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PropertyData::Empty => Ok(()),
+            PropertyData::String(s) | PropertyData::AnsiString(s) => write!(f, "{}", s),
+            PropertyData::I4(v) => write!(f, "{}", v),
+            PropertyData::UI4(v) => write!(f, "{}", v),
+            PropertyData::I8(v) => write!(f, "{}", v),
+            PropertyData::UI8(v) => write!(f, "{}", v),
+            PropertyData::Bool(v) => write!(f, "{}", v),
+            PropertyData::FileTime(Some(dt)) => write!(f, "{}", dt.format("%Y-%m-%d %H:%M:%S UTC")),
+            PropertyData::FileTime(None) => write!(f, "-"),
+            PropertyData::Guid(guid) => write!(f, "{{{}}}", PropertyStore::format_guid(guid)),
+            PropertyData::Float(v) => write!(f, "{}", v),
+            PropertyData::Double(v) => write!(f, "{}", v),
+            PropertyData::Array(values) => {
+                let values: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+                write!(f, "{}", values.join(", "))
+            }
+            PropertyData::Binary(bytes) | PropertyData::Unknown(bytes) => write!(f, "{} bytes", bytes.len()),
+        }
+    }
+}
+
+/// FormatID of stores whose properties are named by strings (D5CDD505-2E9C-101B-9397-08002B2CF9AE)
+const STRING_NAMED_FORMAT_ID: [u8; 16] = [
+    0x05, 0xD5, 0xCD, 0xD5, 0x9C, 0x2E, 0x1B, 0x10, 0x93, 0x97, 0x08, 0x00, 0x2B, 0x2C, 0xF9, 0xAE,
+];
+
+/// Serialized property storage version ("1SPS")
+const PROPERTY_STORE_VERSION: u32 = 0x5350_5331;
+
 /// Property type constants (VT_* values)
 #[allow(dead_code)]
 mod property_types {
@@ -150,6 +185,9 @@ impl PropertyStore {
         // Read Format ID (GUID)
         let mut format_id = [0u8; 16];
         cursor.read_exact(&mut format_id)?;
+        if version != PROPERTY_STORE_VERSION {
+            return Err(Error::ParseError(format!("Unexpected PropertyStore version 0x{:08X}", version)));
+        }
         
         let header = PropertyStoreHeader {
             size,
@@ -158,14 +196,16 @@ impl PropertyStore {
         };
         
         let mut properties = HashMap::new();
+        let end = (size as usize).clamp(24, data.len());
+        let named = format_id == STRING_NAMED_FORMAT_ID;
         
-        // Parse properties until end of data
-        while cursor.position() < data.len() as u64 {
-            if let Ok(prop) = Self::parse_property(&mut cursor, &format_id) {
-                let key = Self::make_property_key(&format_id, prop.property_id);
-                properties.insert(key, prop);
-            } else {
-                break; // Stop on parse error
+        // Parse property values until the zero-size terminator or end of the store
+        while cursor.position() + 4 <= end as u64 {
+            match Self::parse_property(&mut cursor, &format_id, named) {
+                Ok(Some((key, prop))) => {
+                    properties.insert(key, prop);
+                }
+                _ => break, // Terminator or parse error
             }
         }
         
@@ -176,33 +216,77 @@ impl PropertyStore {
         })
     }
     
-    /// Parse a single property
-    fn parse_property(cursor: &mut Cursor<&[u8]>, format_id: &[u8; 16]) -> Result<PropertyValue> {
-        // Property header: size(4) + property_id(4) + reserved(4) + type(2) + padding(2)
+    /// Parse a sequence of serialized property stores terminated by a zero size
+    pub fn parse_all(data: &[u8]) -> Vec<Self> {
+        let mut stores = Vec::new();
+        let mut offset = 0;
+        while let Some(size) = data.get(offset..offset + 4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as usize) {
+            if size < 24 || offset + size > data.len() {
+                break;
+            }
+            if let Ok(store) = Self::parse(&data[offset..offset + size]) {
+                stores.push(store);
+            }
+            offset += size;
+        }
+        stores
+    }
+    
+    /// Parse a single property value record; `None` marks the end of the store
+    fn parse_property(cursor: &mut Cursor<&[u8]>, format_id: &[u8; 16], named: bool) -> Result<Option<(String, PropertyValue)>> {
+        // Integer name: size(4) + property_id(4) + reserved(1); string name: size(4) + name_size(4) + reserved(1) + name
+        let start = cursor.position();
         let prop_size = cursor.read_u32::<LittleEndian>()?;
-        let property_id = cursor.read_u32::<LittleEndian>()?;
-        let _reserved = cursor.read_u32::<LittleEndian>()?;
+        if prop_size == 0 {
+            return Ok(None);
+        }
+        let id_or_name_size = cursor.read_u32::<LittleEndian>()?;
+        let _reserved = cursor.read_u8()?;
+        
+        let (property_id, name) = if named {
+            let mut name_data = vec![0u8; id_or_name_size as usize];
+            cursor.read_exact(&mut name_data)?;
+            let chars: Vec<u16> = name_data.chunks_exact(2)
+                .map(|chunk| u16::from_le_bytes([chunk[0], chunk[1]]))
+                .take_while(|&c| c != 0)
+                .collect();
+            (0, Some(String::from_utf16_lossy(&chars)))
+        } else {
+            (id_or_name_size, None)
+        };
+        
+        // Typed value: type(2) + padding(2) + value
         let property_type = cursor.read_u16::<LittleEndian>()?;
         let _padding = cursor.read_u16::<LittleEndian>()?;
         
-        // Calculate data size (subtract header)
-        let data_size = prop_size.saturating_sub(16) as usize;
+        let data_size = (start + prop_size as u64).saturating_sub(cursor.position()) as usize;
         let mut raw_data = vec![0u8; data_size];
         cursor.read_exact(&mut raw_data)?;
         
         // Parse the value based on type
         let parsed_value = Self::parse_property_value(&raw_data, property_type)?;
         
-        // Get description for known properties
-        let description = Self::get_property_description(format_id, property_id);
+        // Get the schema definition for known properties
+        let (key, definition, description) = match name {
+            Some(name) => (format!("{}\\{}", Self::format_guid(format_id), name), None, Some(name)),
+            None => {
+                let definition = property_schema::lookup(&Self::format_guid(format_id), property_id);
+                (Self::make_property_key(format_id, property_id), definition, definition.map(|d| d.name.to_string()))
+            }
+        };
+        let display_value = match definition {
+            Some(definition) => definition.interpret(&parsed_value),
+            None => parsed_value.to_string(),
+        };
         
-        Ok(PropertyValue {
+        Ok(Some((key, PropertyValue {
             property_id,
             property_type,
             raw_data,
             parsed_value,
             description,
-        })
+            display_value,
+        })))
     }
     
     /// Parse property value based on type
@@ -237,9 +321,9 @@ impl PropertyStore {
                 Ok(PropertyData::Bool(val != 0))
             },
             
-            property_types::VT_LPWSTR => {
-                // Unicode string (UTF-16LE)
-                let utf16_data: Vec<u16> = data.chunks_exact(2)
+            property_types::VT_LPWSTR | property_types::VT_BSTR => {
+                // Unicode string (UTF-16LE) after a character count
+                let utf16_data: Vec<u16> = data.get(4..).unwrap_or(&[]).chunks_exact(2)
                     .map(|chunk| u16::from_le_bytes([chunk[0], chunk[1]]))
                     .take_while(|&c| c != 0)
                     .collect();
@@ -247,7 +331,8 @@ impl PropertyStore {
             },
             
             property_types::VT_LPSTR => {
-                // ANSI string
+                // ANSI string after a byte count
+                let data = data.get(4..).unwrap_or(&[]);
                 let null_pos = data.iter().position(|&b| b == 0).unwrap_or(data.len());
                 let string = String::from_utf8_lossy(&data[..null_pos]).to_string();
                 Ok(PropertyData::AnsiString(string))
@@ -309,11 +394,11 @@ impl PropertyStore {
         for _ in 0..count {
             match element_type {
                 property_types::VT_LPWSTR => {
-                    // String vector - each element has size prefix
+                    // String vector - each element has a character count prefix and is padded to 4 bytes
                     if cursor.position() + 4 > data.len() as u64 {
                         break;
                     }
-                    let str_size = cursor.read_u32::<LittleEndian>()? as usize;
+                    let str_size = (cursor.read_u32::<LittleEndian>()? as usize * 2 + 3) & !3;
                     if cursor.position() + str_size as u64 > data.len() as u64 {
                         break;
                     }
@@ -399,13 +484,6 @@ impl PropertyStore {
         ).to_uppercase()
     }
     
-    /// Get human-readable property description
-    fn get_property_description(format_id: &[u8; 16], property_id: u32) -> Option<String> {
-        let descriptions = get_property_descriptions();
-        let key = Self::make_property_key(format_id, property_id);
-        descriptions.get(&key).cloned()
-    }
-    
     /// Get property by key
     pub fn get_property(&self, format_id: &[u8; 16], property_id: u32) -> Option<&PropertyValue> {
         let key = Self::make_property_key(format_id, property_id);
@@ -416,65 +494,41 @@ impl PropertyStore {
     pub fn get_all_properties(&self) -> Vec<(String, &PropertyValue)> {
         self.properties.iter().map(|(k, v)| (k.clone(), v)).collect()
     }
-}
-
-/// Get comprehensive property descriptions mapping
-fn get_property_descriptions() -> HashMap<String, String> {
-    let mut descriptions = HashMap::new();
     
-    // System properties (28636AA6-953D-11D2-B5D6-00C04FD918D0)
-    descriptions.insert("28636AA6-953D-11D2-B5D6-00C04FD918D0\\0".to_string(), "FindData".to_string());
-    descriptions.insert("28636AA6-953D-11D2-B5D6-00C04FD918D0\\1".to_string(), "Network Resource".to_string());
-    descriptions.insert("28636AA6-953D-11D2-B5D6-00C04FD918D0\\2".to_string(), "DescriptionID".to_string());
-    descriptions.insert("28636AA6-953D-11D2-B5D6-00C04FD918D0\\3".to_string(), "Which Folder".to_string());
-    descriptions.insert("28636AA6-953D-11D2-B5D6-00C04FD918D0\\4".to_string(), "Network Location".to_string());
-    descriptions.insert("28636AA6-953D-11D2-B5D6-00C04FD918D0\\5".to_string(), "ComputerName".to_string());
-    descriptions.insert("28636AA6-953D-11D2-B5D6-00C04FD918D0\\6".to_string(), "NamespaceCLSID".to_string());
-    descriptions.insert("28636AA6-953D-11D2-B5D6-00C04FD918D0\\8".to_string(), "ItemPathDisplayNarrow".to_string());
-    descriptions.insert("28636AA6-953D-11D2-B5D6-00C04FD918D0\\9".to_string(), "PerceivedType".to_string());
-    descriptions.insert("28636AA6-953D-11D2-B5D6-00C04FD918D0\\10".to_string(), "Computer Simple Name".to_string());
-    descriptions.insert("28636AA6-953D-11D2-B5D6-00C04FD918D0\\11".to_string(), "ItemType".to_string());
-    descriptions.insert("28636AA6-953D-11D2-B5D6-00C04FD918D0\\12".to_string(), "FileCount".to_string());
-    descriptions.insert("28636AA6-953D-11D2-B5D6-00C04FD918D0\\14".to_string(), "TotalFileSize".to_string());
-    descriptions.insert("28636AA6-953D-11D2-B5D6-00C04FD918D0\\22".to_string(), "Max Stack Count".to_string());
-    descriptions.insert("28636AA6-953D-11D2-B5D6-00C04FD918D0\\23".to_string(), "List Description".to_string());
-    descriptions.insert("28636AA6-953D-11D2-B5D6-00C04FD918D0\\24".to_string(), "ParsingName".to_string());
-    descriptions.insert("28636AA6-953D-11D2-B5D6-00C04FD918D0\\25".to_string(), "SFGAOFlags".to_string());
-    
-    // File properties (B725F130-47EF-101A-A5F1-02608C9EEBAC)
-    descriptions.insert("B725F130-47EF-101A-A5F1-02608C9EEBAC\\2".to_string(), "FileSize".to_string());
-    descriptions.insert("B725F130-47EF-101A-A5F1-02608C9EEBAC\\3".to_string(), "DateCreated".to_string());
-    descriptions.insert("B725F130-47EF-101A-A5F1-02608C9EEBAC\\4".to_string(), "DateAccessed".to_string());
-    descriptions.insert("B725F130-47EF-101A-A5F1-02608C9EEBAC\\5".to_string(), "DateModified".to_string());
-    descriptions.insert("B725F130-47EF-101A-A5F1-02608C9EEBAC\\10".to_string(), "FileName".to_string());
-    descriptions.insert("B725F130-47EF-101A-A5F1-02608C9EEBAC\\11".to_string(), "FileAttributes".to_string());
-    
-    // Shell properties (F29F85E0-4FF9-1068-AB91-08002B27B3D9)
-    descriptions.insert("F29F85E0-4FF9-1068-AB91-08002B27B3D9\\2".to_string(), "Title".to_string());
-    descriptions.insert("F29F85E0-4FF9-1068-AB91-08002B27B3D9\\4".to_string(), "Author".to_string());
-    descriptions.insert("F29F85E0-4FF9-1068-AB91-08002B27B3D9\\5".to_string(), "Subject".to_string());
-    descriptions.insert("F29F85E0-4FF9-1068-AB91-08002B27B3D9\\6".to_string(), "Comments".to_string());
-    descriptions.insert("F29F85E0-4FF9-1068-AB91-08002B27B3D9\\12".to_string(), "CreateDateTime".to_string());
-    descriptions.insert("F29F85E0-4FF9-1068-AB91-08002B27B3D9\\13".to_string(), "LastSaveDateTime".to_string());
-    
-    // Volume properties (9B174B35-40FF-11D2-A27E-00C04FC30871)  
-    descriptions.insert("9B174B35-40FF-11D2-A27E-00C04FC30871\\2".to_string(), "VolumeLabel".to_string());
-    descriptions.insert("9B174B35-40FF-11D2-A27E-00C04FC30871\\3".to_string(), "VolumeSerial".to_string());
-    descriptions.insert("9B174B35-40FF-11D2-A27E-00C04FC30871\\4".to_string(), "FileSystem".to_string());
-    descriptions.insert("9B174B35-40FF-11D2-A27E-00C04FC30871\\5".to_string(), "DriveType".to_string());
-    
-    // Security properties (46588AE2-4CBC-4338-BBFC-139326986DCE)
-    descriptions.insert("46588AE2-4CBC-4338-BBFC-139326986DCE\\4".to_string(), "SID".to_string());
+    /// Get a property by canonical schema name
+    pub fn get_named(&self, name: &str) -> Option<&PropertyValue> {
+        self.properties.values().find(|value| value.description.as_deref().is_some_and(|d| d.eq_ignore_ascii_case(name)))
+    }
     
-    // More comprehensive descriptions can be added here
-    // The Jumplist-Browser has over 1000 FormatID/PropertyID combinations
+    /// Timestamp-kind properties that date the item, ordered by time
+    pub fn timestamps(&self) -> Vec<(String, DateTime<Utc>)> {
+        let mut timestamps: Vec<(String, DateTime<Utc>)> = self.properties.iter()
+            .filter_map(|(key, value)| {
+                let (format_id, property_id) = key.rsplit_once('\\')?;
+                let definition = property_schema::lookup(format_id, property_id.parse().ok()?)?;
+                match (definition.kind, &value.parsed_value) {
+                    (PropertyKind::Timestamp, PropertyData::FileTime(Some(dt))) => Some((definition.name.to_string(), *dt)),
+                    _ => None,
+                }
+            })
+            .collect();
+        timestamps.sort();
+        timestamps
+    }
     
-    descriptions
+    /// GPS position as signed decimal (latitude, longitude)
+    pub fn gps_position(&self) -> Option<(f64, f64)> {
+        let reference = |name: &str| self.get_named(name).map(|value| value.parsed_value.to_string());
+        let latitude = property_schema::gps_decimal(&self.get_named("System.GPS.Latitude")?.parsed_value, reference("System.GPS.LatitudeRef").as_deref())?;
+        let longitude = property_schema::gps_decimal(&self.get_named("System.GPS.Longitude")?.parsed_value, reference("System.GPS.LongitudeRef").as_deref())?;
+        Some((latitude, longitude))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::registry::test_hive::utf16z;
     
     #[test]
     fn test_format_guid() {
@@ -497,4 +551,40 @@ mod tests {
         assert_eq!(PropertyStore::get_type_size(property_types::VT_CLSID), 16);
         assert_eq!(PropertyStore::get_type_size(property_types::VT_LPWSTR), 0); // Variable size
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_schema_names_and_timestamps() {
+        // System.Photo.DateTaken (VT_FILETIME) and System.Photo.CameraModel (VT_LPWSTR)
+        let mut records = Vec::new();
+        let mut taken = 36867u32.to_le_bytes().to_vec();
+        taken.push(0);
+        taken.extend_from_slice(&property_types::VT_FILETIME.to_le_bytes());
+        taken.extend_from_slice(&0u16.to_le_bytes());
+        taken.extend_from_slice(&132223104000000000u64.to_le_bytes()); // 2020-01-01
+        records.extend_from_slice(&((taken.len() + 4) as u32).to_le_bytes());
+        records.extend(taken);
+        
+        let mut model = 272u32.to_le_bytes().to_vec();
+        model.push(0);
+        model.extend_from_slice(&property_types::VT_LPWSTR.to_le_bytes());
+        model.extend_from_slice(&0u16.to_le_bytes());
+        model.extend_from_slice(&4u32.to_le_bytes());
+        model.extend(utf16z("X100"));
+        records.extend_from_slice(&((model.len() + 4) as u32).to_le_bytes());
+        records.extend(model);
+        records.extend_from_slice(&0u32.to_le_bytes());
+        
+        let mut data = ((24 + records.len()) as u32).to_le_bytes().to_vec();
+        data.extend_from_slice(b"1SPS");
+        data.extend_from_slice(&[0xA1, 0x1D, 0xB8, 0x14, 0x35, 0x01, 0x31, 0x4D, 0x96, 0xD9, 0x6C, 0xBF, 0xC9, 0x67, 0x1A, 0x99]);
+        data.extend(records);
+        
+        let store = PropertyStore::parse(&data).unwrap();
+        assert_eq!(store.get_named("System.Photo.CameraModel").unwrap().display_value, "X100");
+        let timestamps = store.timestamps();
+        assert_eq!(timestamps.len(), 1);
+        assert_eq!(timestamps[0].0, "System.Photo.DateTaken");
+        assert_eq!(timestamps[0].1.to_rfc3339(), "2020-01-01T00:00:00+00:00");
+        assert!(store.gps_position().is_none());
+    }
+}
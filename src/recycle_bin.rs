@@ -0,0 +1,148 @@
+//! Windows Recycle Bin ($I index file) parsing module
+//!
+//! Supports parsing of:
+//! - Version 1 $I files (Windows Vista / 7 / 8, fixed 260-character path)
+//! - Version 2 $I files (Windows 10+, length-prefixed path)
+//! - Owner SID from the $Recycle.Bin\<SID> folder and pairing with the $R content file
+
+use crate::error::{Error, Result};
+use crate::registry::{filetime_to_datetime, read_u32, read_u64, utf16_string};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Offset of the original path in version 1 files
+const V1_PATH_OFFSET: usize = 24;
+/// Length of the fixed path field in version 1 files (MAX_PATH UTF-16 characters)
+const V1_PATH_CHARS: usize = 260;
+/// Offset of the original path in version 2 files (after the character count)
+const V2_PATH_OFFSET: usize = 28;
+
+/// Parsed $I index file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecycleBinEntry {
+    /// Format version (1 or 2)
+    pub version: u64,
+    /// Original file size in bytes
+    pub original_size: u64,
+    /// Time the file was deleted to the Recycle Bin
+    pub deleted: Option<DateTime<Utc>>,
+    /// Original full path
+    pub original_path: String,
+    /// Owner SID from the $Recycle.Bin subfolder
+    pub sid: Option<String>,
+    /// Path of the $I file
+    pub index_path: String,
+    /// Path of the matching $R content file
+    pub content_path: String,
+}
+
+impl RecycleBinEntry {
+    /// Parse a $I file; `path` is used for the owner SID and $R pairing
+    pub fn parse(data: &[u8], path: &str) -> Result<Self> {
+        let version = read_u64(data, 0).ok_or_else(|| Error::ParseError("$I header truncated".to_string()))?;
+        let original_size = read_u64(data, 8).unwrap_or(0);
+        let deleted = read_u64(data, 16).and_then(filetime_to_datetime);
+
+        let original_path = match version {
+            1 => data
+                .get(V1_PATH_OFFSET..(V1_PATH_OFFSET + V1_PATH_CHARS * 2).min(data.len()))
+                .map(utf16_string),
+            2 => {
+                let chars = read_u32(data, 24).unwrap_or(0) as usize;
+                data.get(V2_PATH_OFFSET..V2_PATH_OFFSET + chars * 2).map(utf16_string)
+            }
+            _ => return Err(Error::ParseError(format!("Unsupported $I version {}", version))),
+        }
+        .ok_or_else(|| Error::ParseError("$I original path truncated".to_string()))?;
+
+        Ok(RecycleBinEntry {
+            version,
+            original_size,
+            deleted,
+            original_path,
+            sid: owner_sid(path),
+            index_path: path.to_string(),
+            content_path: content_path(path),
+        })
+    }
+
+    /// File name of the original path
+    pub fn original_name(&self) -> &str {
+        self.original_path.rsplit('\\').next().unwrap_or(&self.original_path)
+    }
+}
+
+/// Whether a path names a $I index file ("$I" followed by a six-character identifier)
+pub fn is_index_file(path: &str) -> bool {
+    let name = path.rsplit(['/', '\\']).next().unwrap_or(path);
+    name.starts_with("$I")
+        && name.len() >= 8
+        && name.as_bytes()[2..8].iter().all(|b| b.is_ascii_alphanumeric())
+}
+
+/// SID of the $Recycle.Bin subfolder a path lives in
+fn owner_sid(path: &str) -> Option<String> {
+    let mut components = path.split(['/', '\\']);
+    components.find(|c| c.eq_ignore_ascii_case("$Recycle.Bin"))?;
+    components.next().filter(|c| c.starts_with("S-")).map(|c| c.to_string())
+}
+
+/// Path of the $R content file that pairs with a $I file
+fn content_path(path: &str) -> String {
+    let split = path.rfind(['/', '\\']).map(|i| i + 1).unwrap_or(0);
+    let (directory, name) = path.split_at(split);
+    format!("{}$R{}", directory, name.get(2..).unwrap_or(""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::test_hive::{utf16, utf16z};
+    use chrono::Datelike;
+
+    fn header(version: u64) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(&version.to_le_bytes());
+        data.extend_from_slice(&4096u64.to_le_bytes());
+        data.extend_from_slice(&132223104000000000u64.to_le_bytes()); // 2020-01-01
+        data
+    }
+
+    #[test]
+    fn test_parse_v1() {
+        let mut data = header(1);
+        let mut path = utf16("C:\\Users\\alice\\secret.docx");
+        path.resize(V1_PATH_CHARS * 2, 0);
+        data.extend(path);
+
+        let entry = RecycleBinEntry::parse(&data, "C/$Recycle.Bin/S-1-5-21-1-2-3-1001/$IAB12CD.docx").unwrap();
+        assert_eq!(entry.original_path, "C:\\Users\\alice\\secret.docx");
+        assert_eq!(entry.original_name(), "secret.docx");
+        assert_eq!(entry.original_size, 4096);
+        assert_eq!(entry.deleted.unwrap().year(), 2020);
+        assert_eq!(entry.sid.as_deref(), Some("S-1-5-21-1-2-3-1001"));
+        assert_eq!(entry.content_path, "C/$Recycle.Bin/S-1-5-21-1-2-3-1001/$RAB12CD.docx");
+    }
+
+    #[test]
+    fn test_parse_v2() {
+        let mut data = header(2);
+        let path = utf16z("D:\\tools\\mimikatz.exe");
+        data.extend_from_slice(&((path.len() / 2) as u32).to_le_bytes());
+        data.extend(path);
+
+        let entry = RecycleBinEntry::parse(&data, "$IXYZ789.exe").unwrap();
+        assert_eq!(entry.original_path, "D:\\tools\\mimikatz.exe");
+        assert!(entry.sid.is_none());
+        assert_eq!(entry.content_path, "$RXYZ789.exe");
+        assert!(RecycleBinEntry::parse(&header(3), "$IXYZ789.exe").is_err());
+    }
+
+    #[test]
+    fn test_is_index_file() {
+        assert!(is_index_file("C:\\$Recycle.Bin\\S-1-5-18\\$I0A1B2C.txt"));
+        assert!(is_index_file("$IABCDEF"));
+        assert!(!is_index_file("$RABCDEF.txt"));
+        assert!(!is_index_file("$I30"));
+    }
+}
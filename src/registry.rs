@@ -7,9 +7,11 @@
 //! - Recent documents and search history
 
 use crate::error::{Error, Result};
+use crate::jumplist::{get_mru_locations, parse_registry_mru};
+use crate::shell_item::ItemIdList;
 use crate::types::EventTimestamps;
 use byteorder::{LittleEndian, ReadBytesExt};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use std::io::{Cursor, Read};
 use std::path::Path;
@@ -31,6 +33,12 @@ pub struct RegistryTimelineEvent {
     pub data_size: Option<u32>,
     /// Timestamps
     pub timestamps: EventTimestamps,
+    /// Human-readable description (e.g. decoded MRU path)
+    pub description: Option<String>,
+    /// Additional metadata
+    pub metadata: Vec<(String, String)>,
+    /// Event source override for artifacts stored in hives (defaults to "Registry")
+    pub source: Option<String>,
 }
 
 /// Registry event types
@@ -52,18 +60,16 @@ pub enum RegistryEventType {
     SearchHistory,
 }
 
+/// 100ns intervals between 1601-01-01 (FILETIME epoch) and 1970-01-01
+const FILETIME_EPOCH_DIFF: u64 = 116444736000000000;
+
 /// Registry parser for extracting timeline events
-pub struct RegistryParser {
-    /// FILETIME epoch difference
-    filetime_epoch_diff: u64,
-}
+pub struct RegistryParser;
 
 impl RegistryParser {
     /// Create new registry parser
     pub fn new() -> Self {
-        Self {
-            filetime_epoch_diff: 116444736000000000,
-        }
+        Self
     }
 
     /// Parse registry data and create hive structure
@@ -85,6 +91,8 @@ impl RegistryParser {
             
         let hive_type = if filename.contains("ntuser") {
             HiveType::NtUser
+        } else if filename.contains("amcache") {
+            HiveType::Amcache
         } else if filename.contains("system") {
             HiveType::System
         } else if filename.contains("software") {
@@ -102,6 +110,7 @@ impl RegistryParser {
             root_key: None,
             file_path: path.to_string_lossy().to_string(),
             hive_type,
+            data: data.to_vec(),
         })
     }
 
@@ -123,15 +132,192 @@ impl RegistryParser {
                 accessed: None,
                 mft_modified: None,
             },
+            description: None,
+            metadata: Vec::new(),
+            source: None,
         };
         events.push(hive_event);
 
-        // TODO: Extract specific MRU, shell bag, and other timeline events
-        // For now, this provides basic hive-level timeline information
+        events.extend(self.extract_mru_events(hive));
+        events.extend(crate::shimcache::extract_timeline_events(hive));
+        events.extend(crate::amcache::extract_timeline_events(hive));
+        events.extend(crate::bam::extract_timeline_events(hive));
+        events.extend(crate::persistence::extract_timeline_events(hive));
+        events.extend(crate::sam::extract_timeline_events(hive));
+        events.extend(crate::network::extract_timeline_events(hive));
         
         Ok(events)
     }
 
+    /// Extract MRU list entries (RecentDocs, OpenSavePidlMRU, LastVisitedPidlMRU, RunMRU,
+    /// WordWheelQuery, TypedPaths, TypedURLs) and ShellBags (BagMRU)
+    pub fn extract_mru_events(&self, hive: &RegistryHive) -> Vec<RegistryTimelineEvent> {
+        let mut events = Vec::new();
+
+        for location in get_mru_locations() {
+            let mru_type = MruType::from_key_path(location);
+            if mru_type == MruType::BagMru {
+                if let Some(key) = hive.open_key(location) {
+                    Self::collect_bag_mru(&key, &[], &mut events);
+                }
+                continue;
+            }
+            if !matches!(
+                mru_type,
+                MruType::RecentDocs
+                    | MruType::OpenSavePidlMru
+                    | MruType::LastVisitedPidlMru
+                    | MruType::RunMru
+                    | MruType::WordWheelQuery
+                    | MruType::TypedPaths
+                    | MruType::TypedUrls
+            ) {
+                continue;
+            }
+
+            if let Some(key) = hive.open_key(location) {
+                // TypedURLsTime (Windows 8+) holds a FILETIME per urlN value
+                let times = match mru_type {
+                    MruType::TypedUrls => hive.open_key(&format!("{}Time", location)),
+                    _ => None,
+                };
+                Self::collect_mru_key(&key, mru_type, times.as_ref(), &mut events);
+            }
+        }
+
+        events
+    }
+
+    /// Emit events for one MRU key and its subkeys (e.g. RecentDocs\.docx)
+    fn collect_mru_key(
+        key: &RegistryKey,
+        mru_type: MruType,
+        times: Option<&RegistryKey>,
+        events: &mut Vec<RegistryTimelineEvent>,
+    ) {
+        for (position, value_name) in mru_order(key).iter().enumerate() {
+            let Some(value) = key.value(value_name) else {
+                continue;
+            };
+
+            let entry = parse_registry_mru(&key.path, &value.name, &value.data);
+            let Some(parsed) = entry.parsed_path else {
+                continue;
+            };
+
+            // Per-entry times when recorded, otherwise the key's last write time only dates
+            // the most recently used entry
+            let entry_time = times
+                .and_then(|t| t.value(&value.name))
+                .and_then(|v| v.qword_data())
+                .and_then(filetime_to_datetime);
+            let modified = entry_time.or(if position == 0 { key.last_written } else { None });
+
+            events.push(RegistryTimelineEvent {
+                key_path: key.path.clone(),
+                value_name: Some(value.name.clone()),
+                event_type: match mru_type {
+                    MruType::RecentDocs => RegistryEventType::RecentDoc,
+                    MruType::WordWheelQuery => RegistryEventType::SearchHistory,
+                    _ => RegistryEventType::MruEntry,
+                },
+                value_type: Some(value.value_type),
+                data: Some(value.data.clone()),
+                data_size: Some(value.data_size),
+                timestamps: EventTimestamps {
+                    created: None,
+                    modified,
+                    accessed: None,
+                    mft_modified: None,
+                },
+                description: Some(format!("{}: {}", mru_type.label(), parsed)),
+                metadata: vec![
+                    ("MRU Type".to_string(), mru_type.label().to_string()),
+                    ("MRU Position".to_string(), position.to_string()),
+                    ("Key".to_string(), key.path.clone()),
+                    ("Value".to_string(), value.name.clone()),
+                ],
+                source: None,
+            });
+        }
+
+        for subkey in &key.subkeys {
+            Self::collect_mru_key(subkey, mru_type, None, events);
+        }
+    }
+
+    /// Walk a BagMRU key. Each numbered value holds one shell item and the subkey with the same
+    /// name holds the items below it, so paths are resolved from the parent items' PIDL.
+    fn collect_bag_mru(key: &RegistryKey, parent_pidl: &[u8], events: &mut Vec<RegistryTimelineEvent>) {
+        for (position, value_name) in mru_order(key).iter().enumerate() {
+            let Some(value) = key.value(value_name) else {
+                continue;
+            };
+            // The value is a single-item PIDL; keep the item without its terminator
+            let Some(item) = read_u16(&value.data, 0).and_then(|size| value.data.get(..size as usize)) else {
+                continue;
+            };
+            let mut pidl = parent_pidl.to_vec();
+            pidl.extend_from_slice(item);
+
+            if let Some(path) = ItemIdList::parse_pidl(&pidl).ok().and_then(|id_list| id_list.full_path) {
+                events.push(RegistryTimelineEvent {
+                    key_path: key.path.clone(),
+                    value_name: Some(value.name.clone()),
+                    event_type: RegistryEventType::ShellBag,
+                    value_type: Some(value.value_type),
+                    data: Some(value.data.clone()),
+                    data_size: Some(value.data_size),
+                    timestamps: EventTimestamps {
+                        created: None,
+                        modified: if position == 0 { key.last_written } else { None },
+                        accessed: None,
+                        mft_modified: None,
+                    },
+                    description: Some(format!("{}: {}", MruType::BagMru.label(), path)),
+                    metadata: vec![
+                        ("MRU Type".to_string(), MruType::BagMru.label().to_string()),
+                        ("MRU Position".to_string(), position.to_string()),
+                        ("Key".to_string(), key.path.clone()),
+                        ("Value".to_string(), value.name.clone()),
+                    ],
+                    source: None,
+                });
+
+                // ShellBag items can carry property stores whose timestamps date the item itself
+                let property_timestamps = ItemIdList::parse_pidl(item)
+                    .map(|id_list| id_list.property_timestamps())
+                    .unwrap_or_default();
+                for (name, timestamp) in property_timestamps {
+                    events.push(RegistryTimelineEvent {
+                        key_path: key.path.clone(),
+                        value_name: Some(value.name.clone()),
+                        event_type: RegistryEventType::ShellBag,
+                        value_type: Some(value.value_type),
+                        data: None,
+                        data_size: Some(value.data_size),
+                        timestamps: EventTimestamps {
+                            created: Some(timestamp),
+                            ..EventTimestamps::default()
+                        },
+                        description: Some(format!("{} ({})", path, name.strip_prefix("System.").unwrap_or(&name))),
+                        metadata: vec![
+                            ("Property".to_string(), name),
+                            ("Link Source".to_string(), MruType::BagMru.label().to_string()),
+                            ("Key".to_string(), key.path.clone()),
+                            ("Value".to_string(), value.name.clone()),
+                        ],
+                        source: Some("PropertyStore".to_string()),
+                    });
+                }
+            }
+
+            if let Some(subkey) = key.subkey(value_name) {
+                Self::collect_bag_mru(subkey, &pidl, events);
+            }
+        }
+    }
+
     /// Parse hive header
     fn parse_hive_header(&self, cursor: &mut Cursor<&[u8]>) -> Result<HiveHeader> {
         let mut signature = [0u8; 4];
@@ -170,13 +356,41 @@ impl RegistryParser {
 
     /// Convert FILETIME to DateTime<Utc>
     fn filetime_to_datetime(&self, filetime: u64) -> Option<DateTime<Utc>> {
-        if filetime == 0 {
-            return None;
-        }
+        filetime_to_datetime(filetime)
+    }
+}
 
-        let unix_time = (filetime.saturating_sub(self.filetime_epoch_diff)) / 10000000;
-        DateTime::from_timestamp(unix_time as i64, 0)
+/// Value names of an MRU key in most-recently-used order (MRUListEx, MRUList, or url1..urlN)
+fn mru_order(key: &RegistryKey) -> Vec<String> {
+    if let Some(list) = key.value("MRUListEx") {
+        return list
+            .data
+            .chunks_exact(4)
+            .map(|chunk| u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+            .take_while(|&index| index != u32::MAX)
+            .map(|index| index.to_string())
+            .collect();
     }
+
+    if let Some(list) = key.value("MRUList").and_then(|v| v.string_data()) {
+        return list.chars().map(|c| c.to_string()).collect();
+    }
+
+    // TypedPaths/TypedURLs number their values with url1 being the most recent
+    let mut numbered: Vec<(u32, String)> = key
+        .values
+        .iter()
+        .filter_map(|v| {
+            let index = v
+                .name
+                .get(..3)
+                .filter(|prefix| prefix.eq_ignore_ascii_case("url"))
+                .and_then(|_| v.name.get(3..)?.parse().ok())?;
+            Some((index, v.name.clone()))
+        })
+        .collect();
+    numbered.sort();
+    numbered.into_iter().map(|(_, name)| name).collect()
 }
 
 impl Default for RegistryParser {
@@ -198,6 +412,8 @@ pub enum HiveType {
     Sam,
     /// SECURITY hive
     Security,
+    /// Amcache.hve program inventory hive
+    Amcache,
     /// Unknown hive type
     Unknown,
 }
@@ -213,6 +429,297 @@ pub struct RegistryHive {
     pub file_path: String,
     /// Hive type (NTUSER, SYSTEM, SOFTWARE, etc.)
     pub hive_type: HiveType,
+    /// Raw hive data used for on-demand key lookups
+    #[serde(skip)]
+    data: Vec<u8>,
+}
+
+/// Offset of the first hive bin; cell offsets are relative to it
+const HBIN_BASE: usize = 0x1000;
+/// Maximum subkey depth loaded by `RegistryHive::open_key`
+const MAX_KEY_DEPTH: usize = 32;
+/// Key node flag: name stored as ASCII
+const KEY_COMP_NAME: u16 = 0x0020;
+/// Value flag: name stored as ASCII
+const VALUE_COMP_NAME: u16 = 0x0001;
+/// Maximum data size of a single big data segment
+const BIG_DATA_SEGMENT_SIZE: usize = 16344;
+
+impl RegistryHive {
+    /// Open a key by path relative to the root key (case-insensitive), loading its subtree
+    pub fn open_key(&self, path: &str) -> Option<RegistryKey> {
+        let mut offset = self.header.root_cell_offset;
+        let mut key_path = String::new();
+
+        for component in path.split('\\').filter(|c| !c.is_empty()) {
+            let nk = self.cell(offset)?;
+            offset = self.subkey_offsets(read_u32(nk, 28)?, 0)
+                .into_iter()
+                .find(|&child| {
+                    self.cell(child)
+                        .and_then(|child_nk| self.key_name(child_nk))
+                        .is_some_and(|name| name.eq_ignore_ascii_case(component))
+                })?;
+            let name = self.cell(offset).and_then(|nk| self.key_name(nk))?;
+            key_path = if key_path.is_empty() { name } else { format!("{}\\{}", key_path, name) };
+        }
+
+        self.read_key(offset, key_path, 0)
+    }
+
+    /// Get cell data (without the size field) at an offset relative to the first hive bin
+    fn cell(&self, offset: u32) -> Option<&[u8]> {
+        let start = HBIN_BASE.checked_add(offset as usize)?;
+        let size = i32::from_le_bytes(self.data.get(start..start + 4)?.try_into().ok()?);
+        let size = size.unsigned_abs() as usize;
+        if size < 4 {
+            return None;
+        }
+        self.data.get(start + 4..start + size)
+    }
+
+    /// Read a key node and its subtree
+    fn read_key(&self, offset: u32, path: String, depth: usize) -> Option<RegistryKey> {
+        let nk = self.cell(offset)?;
+        if nk.get(0..2)? != b"nk" {
+            return None;
+        }
+
+        let name = self.key_name(nk)?;
+        let last_written = filetime_to_datetime(read_u64(nk, 4)?);
+        let value_count = read_u32(nk, 36)?;
+        let values = if value_count > 0 {
+            self.read_values(read_u32(nk, 40)?, value_count)
+        } else {
+            Vec::new()
+        };
+
+        let class_length = read_u16(nk, 74)? as usize;
+        let class_name = if class_length > 0 {
+            self.cell(read_u32(nk, 48)?)
+                .and_then(|class| class.get(..class_length))
+                .map(utf16_string)
+        } else {
+            None
+        };
+
+        let mut subkeys = Vec::new();
+        if depth < MAX_KEY_DEPTH && read_u32(nk, 20)? > 0 {
+            for child in self.subkey_offsets(read_u32(nk, 28)?, 0) {
+                let child_name = match self.cell(child).and_then(|child_nk| self.key_name(child_nk)) {
+                    Some(child_name) => child_name,
+                    None => continue,
+                };
+                let child_path = if path.is_empty() {
+                    child_name
+                } else {
+                    format!("{}\\{}", path, child_name)
+                };
+                if let Some(subkey) = self.read_key(child, child_path, depth + 1) {
+                    subkeys.push(subkey);
+                }
+            }
+        }
+
+        Some(RegistryKey {
+            name,
+            path,
+            last_written,
+            subkeys,
+            values,
+            class_name,
+            security_descriptor: None,
+        })
+    }
+
+    /// Name of the current control set (e.g. "ControlSet001") from SYSTEM\\Select
+    pub fn current_control_set(&self) -> Option<String> {
+        let select = self.open_key("Select")?;
+        let current = select.value("Current").and_then(|v| v.dword_data())?;
+        Some(format!("ControlSet{:03}", current))
+    }
+
+    /// Decode the name of a key node
+    fn key_name(&self, nk: &[u8]) -> Option<String> {
+        if nk.get(0..2)? != b"nk" {
+            return None;
+        }
+        let flags = read_u16(nk, 2)?;
+        let length = read_u16(nk, 72)? as usize;
+        let raw = nk.get(76..76 + length)?;
+        Some(if flags & KEY_COMP_NAME != 0 {
+            raw.iter().map(|&b| b as char).collect()
+        } else {
+            utf16_string(raw)
+        })
+    }
+
+    /// Collect key node offsets from an lf/lh/li/ri subkey list
+    fn subkey_offsets(&self, list_offset: u32, depth: usize) -> Vec<u32> {
+        let Some(list) = self.cell(list_offset) else {
+            return Vec::new();
+        };
+        let count = read_u16(list, 2).unwrap_or(0) as usize;
+
+        match list.get(0..2) {
+            Some(b"lf") | Some(b"lh") => (0..count).filter_map(|i| read_u32(list, 4 + i * 8)).collect(),
+            Some(b"li") => (0..count).filter_map(|i| read_u32(list, 4 + i * 4)).collect(),
+            Some(b"ri") if depth < 2 => (0..count)
+                .filter_map(|i| read_u32(list, 4 + i * 4))
+                .flat_map(|sublist| self.subkey_offsets(sublist, depth + 1))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Read the values of a key from its value list
+    fn read_values(&self, list_offset: u32, count: u32) -> Vec<RegistryValue> {
+        let Some(list) = self.cell(list_offset) else {
+            return Vec::new();
+        };
+        (0..count as usize)
+            .filter_map(|i| read_u32(list, i * 4))
+            .filter_map(|offset| self.read_value(offset))
+            .collect()
+    }
+
+    /// Read a value key (vk) record
+    fn read_value(&self, offset: u32) -> Option<RegistryValue> {
+        let vk = self.cell(offset)?;
+        if vk.get(0..2)? != b"vk" {
+            return None;
+        }
+
+        let name_length = read_u16(vk, 2)? as usize;
+        let data_size = read_u32(vk, 4)?;
+        let data_offset = read_u32(vk, 8)?;
+        let value_type = RegistryValueType::from(read_u32(vk, 12)?);
+        let flags = read_u16(vk, 16)?;
+
+        let raw_name = vk.get(20..20 + name_length)?;
+        let name = if flags & VALUE_COMP_NAME != 0 {
+            raw_name.iter().map(|&b| b as char).collect()
+        } else {
+            utf16_string(raw_name)
+        };
+
+        let data = self.value_data(data_size, data_offset);
+        let length = data.len() as u32;
+        let mut value = RegistryValue {
+            name,
+            value_type,
+            data,
+            parsed_data: None,
+            data_size: length,
+        };
+        value.parsed_data = value.string_data();
+        Some(value)
+    }
+
+    /// Read value data, handling resident (inline) and big data (db) storage
+    fn value_data(&self, data_size: u32, data_offset: u32) -> Vec<u8> {
+        // High bit set: data (up to 4 bytes) is stored in the offset field
+        if data_size & 0x8000_0000 != 0 {
+            let length = ((data_size & 0x7FFF_FFFF) as usize).min(4);
+            return data_offset.to_le_bytes()[..length].to_vec();
+        }
+
+        let length = data_size as usize;
+        let Some(cell) = self.cell(data_offset) else {
+            return Vec::new();
+        };
+
+        if length > BIG_DATA_SEGMENT_SIZE && cell.get(0..2) == Some(b"db") {
+            let segment_count = read_u16(cell, 2).unwrap_or(0) as usize;
+            let mut data = Vec::with_capacity(length);
+            if let Some(segments) = read_u32(cell, 4).and_then(|list| self.cell(list)) {
+                for i in 0..segment_count {
+                    let Some(segment) = read_u32(segments, i * 4).and_then(|s| self.cell(s)) else {
+                        break;
+                    };
+                    let remaining = length - data.len();
+                    data.extend_from_slice(&segment[..segment.len().min(BIG_DATA_SEGMENT_SIZE).min(remaining)]);
+                }
+            }
+            return data;
+        }
+
+        cell[..length.min(cell.len())].to_vec()
+    }
+}
+
+/// Read a little-endian u16 at an offset
+pub(crate) fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(data.get(offset..offset + 2)?.try_into().ok()?))
+}
+
+/// Read a little-endian u32 at an offset
+pub(crate) fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(data.get(offset..offset + 4)?.try_into().ok()?))
+}
+
+/// Read a little-endian u64 at an offset
+pub(crate) fn read_u64(data: &[u8], offset: usize) -> Option<u64> {
+    Some(u64::from_le_bytes(data.get(offset..offset + 8)?.try_into().ok()?))
+}
+
+/// Decode UTF-16LE bytes, stopping at the first null
+pub(crate) fn utf16_string(data: &[u8]) -> String {
+    let chars: Vec<u16> = data
+        .chunks_exact(2)
+        .map(|chunk| u16::from_le_bytes([chunk[0], chunk[1]]))
+        .take_while(|&c| c != 0)
+        .collect();
+    String::from_utf16_lossy(&chars)
+}
+
+/// Format a little-endian GUID as {XXXXXXXX-XXXX-XXXX-XXXX-XXXXXXXXXXXX}
+pub(crate) fn format_guid(bytes: &[u8]) -> String {
+    if bytes.len() < 16 {
+        return String::new();
+    }
+    format!(
+        "{{{:08X}-{:04X}-{:04X}-{}-{}}}",
+        read_u32(bytes, 0).unwrap_or(0),
+        read_u16(bytes, 4).unwrap_or(0),
+        read_u16(bytes, 6).unwrap_or(0),
+        bytes[8..10].iter().map(|b| format!("{:02X}", b)).collect::<String>(),
+        bytes[10..16].iter().map(|b| format!("{:02X}", b)).collect::<String>()
+    )
+}
+
+/// Format a binary SID as S-R-A-S1-S2...
+pub(crate) fn format_sid(bytes: &[u8]) -> String {
+    if bytes.len() < 8 {
+        return String::new();
+    }
+    let authority = bytes[2..8].iter().fold(0u64, |acc, &b| (acc << 8) | b as u64);
+    let mut sid = format!("S-{}-{}", bytes[0], authority);
+    for i in 0..bytes[1] as usize {
+        match read_u32(bytes, 8 + i * 4) {
+            Some(sub) => sid.push_str(&format!("-{}", sub)),
+            None => break,
+        }
+    }
+    sid
+}
+
+/// Convert a 16-byte SYSTEMTIME
+pub(crate) fn systemtime(bytes: &[u8]) -> Option<DateTime<Utc>> {
+    let field = |i: usize| read_u16(bytes, i * 2).map(|v| v as u32);
+    NaiveDate::from_ymd_opt(field(0)? as i32, field(1)?, field(3)?)?
+        .and_hms_milli_opt(field(4)?, field(5)?, field(6)?, field(7)?)
+        .map(|dt| dt.and_utc())
+}
+
+/// Convert FILETIME to DateTime<Utc> (shared by the artifact parsers)
+pub(crate) fn filetime_to_datetime(filetime: u64) -> Option<DateTime<Utc>> {
+    if filetime == 0 {
+        return None;
+    }
+
+    let unix_time = (filetime.saturating_sub(FILETIME_EPOCH_DIFF)) / 10000000;
+    DateTime::from_timestamp(unix_time as i64, 0)
 }
 
 /// Registry hive header
@@ -263,6 +770,26 @@ pub struct RegistryKey {
     pub security_descriptor: Option<Vec<u8>>,
 }
 
+impl RegistryKey {
+    /// Get a direct subkey by name (case-insensitive)
+    pub fn subkey(&self, name: &str) -> Option<&RegistryKey> {
+        self.subkeys.iter().find(|k| k.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Get a value by name (case-insensitive)
+    pub fn value(&self, name: &str) -> Option<&RegistryValue> {
+        self.values.iter().find(|v| v.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Get a string value, trimmed, when it is present and non-empty
+    pub fn string_value(&self, name: &str) -> Option<String> {
+        self.value(name)
+            .and_then(|v| v.string_data())
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+    }
+}
+
 /// Registry value
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RegistryValue {
@@ -297,14 +824,134 @@ pub enum RegistryValueType {
     Unknown(u32),
 }
 
+impl RegistryValue {
+    /// Decode string data (REG_SZ, REG_EXPAND_SZ, REG_LINK)
+    pub fn string_data(&self) -> Option<String> {
+        match self.value_type {
+            RegistryValueType::String | RegistryValueType::ExpandString | RegistryValueType::Link => {
+                Some(utf16_string(&self.data))
+            }
+            _ => None,
+        }
+    }
+
+    /// Decode REG_DWORD data
+    pub fn dword_data(&self) -> Option<u32> {
+        Some(u32::from_le_bytes(self.data.get(0..4)?.try_into().ok()?))
+    }
+
+    /// Decode REG_QWORD data
+    pub fn qword_data(&self) -> Option<u64> {
+        Some(u64::from_le_bytes(self.data.get(0..8)?.try_into().ok()?))
+    }
+}
+
+impl From<u32> for RegistryValueType {
+    fn from(value: u32) -> Self {
+        match value {
+            0 => RegistryValueType::None,
+            1 => RegistryValueType::String,
+            2 => RegistryValueType::ExpandString,
+            3 => RegistryValueType::Binary,
+            4 => RegistryValueType::DWord,
+            5 => RegistryValueType::DWordBigEndian,
+            6 => RegistryValueType::Link,
+            7 => RegistryValueType::MultiString,
+            8 => RegistryValueType::ResourceList,
+            9 => RegistryValueType::FullResourceDescriptor,
+            10 => RegistryValueType::ResourceRequirementsList,
+            11 => RegistryValueType::QWord,
+            _ => RegistryValueType::Unknown(value),
+        }
+    }
+}
+
+/// MRU list types found in NTUSER.DAT
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MruType {
+    RecentDocs,           // Recent Documents
+    BagMru,              // Shell Bags
+    OpenSavePidlMru,     // Open/Save Dialog
+    LastVisitedPidlMru,  // Last Visited Folders
+    RunMru,              // Run Dialog
+    WordWheelQuery,      // Search Terms
+    TypedPaths,          // Explorer Address Bar
+    TypedUrls,           // Internet Explorer / Edge Address Bar
+    JumplistData,        // Jumplist Data
+    RecentApps,          // Recent Applications
+    TaskBand,            // Taskbar
+    StartPage,           // Start Menu
+    LockScreen,          // Lock Screen
+    Unknown,
+}
+
+impl MruType {
+    /// Determine MRU type from a registry key path
+    pub fn from_key_path(key_path: &str) -> Self {
+        let key_lower = key_path.to_lowercase();
+        if key_lower.contains("recentdocs") {
+            MruType::RecentDocs
+        } else if key_lower.contains("bagmru") {
+            MruType::BagMru
+        } else if key_lower.contains("opensavepidlmru") {
+            MruType::OpenSavePidlMru
+        } else if key_lower.contains("lastvisitedpidlmru") {
+            MruType::LastVisitedPidlMru
+        } else if key_lower.contains("runmru") {
+            MruType::RunMru
+        } else if key_lower.contains("wordwheelquery") {
+            MruType::WordWheelQuery
+        } else if key_lower.contains("typedpaths") {
+            MruType::TypedPaths
+        } else if key_lower.contains("typedurls") {
+            MruType::TypedUrls
+        } else if key_lower.contains("jumplistdata") {
+            MruType::JumplistData
+        } else if key_lower.contains("recentapps") {
+            MruType::RecentApps
+        } else if key_lower.contains("taskband") {
+            MruType::TaskBand
+        } else if key_lower.contains("startpage") {
+            MruType::StartPage
+        } else if key_lower.contains("lock screen") {
+            MruType::LockScreen
+        } else {
+            MruType::Unknown
+        }
+    }
+
+    /// Short label used in event descriptions
+    pub fn label(&self) -> &'static str {
+        match self {
+            MruType::RecentDocs => "RecentDocs",
+            MruType::BagMru => "BagMRU",
+            MruType::OpenSavePidlMru => "OpenSavePidlMRU",
+            MruType::LastVisitedPidlMru => "LastVisitedPidlMRU",
+            MruType::RunMru => "RunMRU",
+            MruType::WordWheelQuery => "WordWheelQuery",
+            MruType::TypedPaths => "TypedPaths",
+            MruType::TypedUrls => "TypedURLs",
+            MruType::JumplistData => "JumplistData",
+            MruType::RecentApps => "RecentApps",
+            MruType::TaskBand => "Taskband",
+            MruType::StartPage => "StartPage",
+            MruType::LockScreen => "Lock Screen",
+            MruType::Unknown => "MRU",
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use super::test_hive::TestKey;
     use super::*;
+    use chrono::Datelike;
 
     #[test]
     fn test_registry_parser_creation() {
         let parser = RegistryParser::new();
-        assert_eq!(parser.filetime_epoch_diff, 116444736000000000);
+        // The FILETIME epoch difference maps to the Unix epoch
+        assert_eq!(parser.filetime_to_datetime(116444736000000000).unwrap().timestamp(), 0);
     }
 
     #[test]
@@ -333,4 +980,338 @@ mod tests {
         let small_data = vec![0u8; 10];
         assert!(parser.parse_registry_data(&small_data, path).is_err());
     }
-}
\ No newline at end of file
+
+    /// Build a minimal NTUSER.DAT containing a RunMRU key with two commands
+    fn build_run_mru_hive() -> Vec<u8> {
+        build_string_values_hive(
+            &["Software", "Microsoft", "Windows", "CurrentVersion", "Explorer", "RunMRU"],
+            &[("a", "calc\\1"), ("b", "cmd\\1"), ("MRUList", "ba")],
+        )
+    }
+
+    /// Build a minimal hive with one key (path given root-first) holding REG_SZ values
+    fn build_string_values_hive(path: &[&str], values: &[(&str, &str)]) -> Vec<u8> {
+        let leaf = values
+            .iter()
+            .fold(TestKey::new(path[path.len() - 1]), |key, &(name, data)| key.string(name, data));
+        TestKey::new("ROOT").with(&path[..path.len() - 1].join("\\"), leaf).build_hive()
+    }
+
+    #[test]
+    fn test_open_key_case_insensitive() {
+        let parser = RegistryParser::new();
+        let data = build_run_mru_hive();
+        let hive = parser.parse_registry_data(&data, Path::new("NTUSER.DAT")).unwrap();
+
+        let key = hive.open_key("software\\microsoft\\windows\\currentversion\\explorer\\runmru").unwrap();
+        assert_eq!(key.path, "Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\RunMRU");
+        assert_eq!(key.values.len(), 3);
+        assert_eq!(key.value("mrulist").and_then(|v| v.string_data()), Some("ba".to_string()));
+        assert!(hive.open_key("Software\\Missing").is_none());
+    }
+
+    #[test]
+    fn test_mru_events_follow_mru_order() {
+        let parser = RegistryParser::new();
+        let data = build_run_mru_hive();
+        let hive = parser.parse_registry_data(&data, Path::new("NTUSER.DAT")).unwrap();
+
+        let events = parser.extract_mru_events(&hive);
+        assert_eq!(events.len(), 2);
+
+        // Only the most recently used entry carries the key's last write time
+        assert_eq!(events[0].description.as_deref(), Some("RunMRU: cmd"));
+        assert_eq!(events[0].timestamps.modified.unwrap().year(), 2020);
+        assert_eq!(events[1].description.as_deref(), Some("RunMRU: calc"));
+        assert!(events[1].timestamps.modified.is_none());
+    }
+
+    #[test]
+    fn test_typed_paths_numeric_order() {
+        let parser = RegistryParser::new();
+        let data = build_string_values_hive(
+            &["Software", "Microsoft", "Windows", "CurrentVersion", "Explorer", "TypedPaths"],
+            &[("url10", "C:\\Old"), ("url2", "\\\\server\\share"), ("url1", "C:\\Evidence")],
+        );
+        let hive = parser.parse_registry_data(&data, Path::new("NTUSER.DAT")).unwrap();
+
+        let events = parser.extract_mru_events(&hive);
+        let descriptions: Vec<_> = events.iter().filter_map(|e| e.description.as_deref()).collect();
+        assert_eq!(
+            descriptions,
+            vec!["TypedPaths: C:\\Evidence", "TypedPaths: \\\\server\\share", "TypedPaths: C:\\Old"]
+        );
+        assert!(events[0].timestamps.modified.is_some());
+        assert!(events[1].timestamps.modified.is_none());
+    }
+
+    #[test]
+    fn test_typed_urls_ignore_default_and_short_value_names() {
+        let parser = RegistryParser::new();
+        let data = build_string_values_hive(
+            &["Software", "Microsoft", "Internet Explorer", "TypedURLs"],
+            &[("", "ignored"), ("ur", "ignored"), ("url", "ignored"), ("url1", "http://example.com/")],
+        );
+        let hive = parser.parse_registry_data(&data, Path::new("NTUSER.DAT")).unwrap();
+
+        let events = parser.extract_mru_events(&hive);
+        let descriptions: Vec<_> = events.iter().filter_map(|e| e.description.as_deref()).collect();
+        assert_eq!(descriptions, vec!["TypedURLs: http://example.com/"]);
+    }
+
+    /// Single-item PIDL as stored in a BagMRU value
+    fn bag_item(data: &[u8]) -> Vec<u8> {
+        let mut item = ((data.len() + 2) as u16).to_le_bytes().to_vec();
+        item.extend_from_slice(data);
+        item.extend_from_slice(&[0, 0]);
+        item
+    }
+
+    #[test]
+    fn test_bag_mru_paths_and_property_timestamps() {
+        // My Computer > C:\ > users property view item named "Evidence" with System.DateModified
+        let mut my_computer = vec![0x1F, 0x50];
+        my_computer.extend_from_slice(&[0xE0, 0x4F, 0xD0, 0x20, 0xEA, 0x3A, 0x69, 0x10, 0xA2, 0xD8, 0x08, 0x00, 0x2B, 0x30, 0x30, 0x9D]);
+        let mut volume = vec![0x2F];
+        volume.extend_from_slice(b"C:\\");
+        volume.resize(23, 0);
+
+        let property = |id: u32, vt: u16, payload: &[u8]| {
+            let mut value = id.to_le_bytes().to_vec();
+            value.push(0);
+            value.extend_from_slice(&vt.to_le_bytes());
+            value.extend_from_slice(&0u16.to_le_bytes());
+            value.extend_from_slice(payload);
+            let mut record = ((value.len() + 4) as u32).to_le_bytes().to_vec();
+            record.extend(value);
+            record
+        };
+        let name = test_hive::utf16z("Evidence");
+        let mut name_payload = ((name.len() / 2) as u32).to_le_bytes().to_vec();
+        name_payload.extend(name);
+        let records = [
+            property(10, 31, &name_payload),                          // System.ItemNameDisplay
+            property(14, 64, &132539328000000000u64.to_le_bytes()), // System.DateModified 2021-01-01
+        ]
+        .concat();
+        let mut store = ((24 + records.len() + 4) as u32).to_le_bytes().to_vec();
+        store.extend_from_slice(b"1SPS");
+        store.extend_from_slice(&[0x30, 0xF1, 0x25, 0xB7, 0xEF, 0x47, 0x1A, 0x10, 0xA5, 0xF1, 0x02, 0x60, 0x8C, 0x9E, 0xEB, 0xAC]);
+        store.extend(records);
+        store.extend_from_slice(&0u32.to_le_bytes());
+        store.extend_from_slice(&0u32.to_le_bytes());
+        let mut users_view = vec![0x00, 0x00, 0x00, 0x00];
+        users_view.extend_from_slice(&0x23FE_BBEEu32.to_le_bytes());
+        users_view.extend_from_slice(&(store.len() as u16).to_le_bytes());
+        users_view.extend_from_slice(&[0, 0]);
+        users_view.extend(store);
+
+        let mru_list_ex = |indices: &[u32]| indices.iter().chain(&[u32::MAX]).flat_map(|i| i.to_le_bytes()).collect::<Vec<u8>>();
+        let folder = TestKey::new("0")
+            .binary("0", &bag_item(&users_view))
+            .binary("MRUListEx", &mru_list_ex(&[0]));
+        let volume_key = TestKey::new("0")
+            .binary("0", &bag_item(&volume))
+            .binary("MRUListEx", &mru_list_ex(&[0]))
+            .subkey(folder);
+        let bag_mru = TestKey::new("BagMRU")
+            .last_written(132539328000000000)
+            .binary("0", &bag_item(&my_computer))
+            .binary("MRUListEx", &mru_list_ex(&[0]))
+            .subkey(volume_key);
+        let hive = TestKey::new("ROOT")
+            .with("Local Settings\\Software\\Microsoft\\Windows\\Shell", bag_mru)
+            .open("UsrClass.dat");
+
+        let events = RegistryParser::new().extract_mru_events(&hive);
+        let descriptions: Vec<_> = events.iter().filter_map(|e| e.description.as_deref()).collect();
+        assert_eq!(
+            descriptions,
+            vec![
+                "BagMRU: My Computer",
+                "BagMRU: C:",
+                "BagMRU: C:\\Evidence",
+                "C:\\Evidence (DateModified)",
+            ]
+        );
+        assert!(events.iter().all(|e| matches!(e.event_type, RegistryEventType::ShellBag)));
+        assert_eq!(events[0].timestamps.modified.unwrap().year(), 2021);
+        assert_eq!(events[2].key_path, "Local Settings\\Software\\Microsoft\\Windows\\Shell\\BagMRU\\0\\0");
+
+        let property_event = &events[3];
+        assert_eq!(property_event.source.as_deref(), Some("PropertyStore"));
+        assert_eq!(property_event.timestamps.created.unwrap().year(), 2021);
+        assert!(property_event.metadata.contains(&("Property".to_string(), "System.DateModified".to_string())));
+    }
+}
+
+/// In-memory hive builder for hive-backed artifact tests
+#[cfg(test)]
+pub(crate) mod test_hive {
+    use super::{RegistryHive, RegistryParser, HBIN_BASE, KEY_COMP_NAME, VALUE_COMP_NAME};
+    use std::path::Path;
+
+    /// Default key last write time (2020-01-01 00:00:00 UTC)
+    pub(crate) const DEFAULT_LAST_WRITTEN: u64 = 132223104000000000;
+
+    /// Key to serialize into a test hive
+    pub(crate) struct TestKey {
+        name: String,
+        last_written: u64,
+        values: Vec<(String, u32, Vec<u8>)>,
+        subkeys: Vec<TestKey>,
+    }
+
+    impl TestKey {
+        pub(crate) fn new(name: &str) -> Self {
+            Self {
+                name: name.to_string(),
+                last_written: DEFAULT_LAST_WRITTEN,
+                values: Vec::new(),
+                subkeys: Vec::new(),
+            }
+        }
+
+        /// Set the key's last write time (FILETIME)
+        pub(crate) fn last_written(mut self, filetime: u64) -> Self {
+            self.last_written = filetime;
+            self
+        }
+
+        /// Add a value of any type
+        pub(crate) fn value(mut self, name: &str, value_type: u32, data: Vec<u8>) -> Self {
+            self.values.push((name.to_string(), value_type, data));
+            self
+        }
+
+        /// Add a null-terminated REG_SZ value
+        pub(crate) fn string(self, name: &str, data: &str) -> Self {
+            self.value(name, 1, utf16z(data))
+        }
+
+        /// Add a REG_DWORD value
+        pub(crate) fn dword(self, name: &str, data: u32) -> Self {
+            self.value(name, 4, data.to_le_bytes().to_vec())
+        }
+
+        /// Add a REG_BINARY value
+        pub(crate) fn binary(self, name: &str, data: &[u8]) -> Self {
+            self.value(name, 3, data.to_vec())
+        }
+
+        /// Add a direct subkey
+        pub(crate) fn subkey(mut self, key: TestKey) -> Self {
+            self.subkeys.push(key);
+            self
+        }
+
+        /// Add `key` below the backslash-separated `path`, creating or reusing intermediate keys
+        pub(crate) fn with(mut self, path: &str, key: TestKey) -> Self {
+            self.insert(path.split('\\').filter(|c| !c.is_empty()), key);
+            self
+        }
+
+        fn insert<'a>(&mut self, mut path: impl Iterator<Item = &'a str>, key: TestKey) {
+            match path.next() {
+                None => self.subkeys.push(key),
+                Some(component) => {
+                    let index = match self.subkeys.iter().position(|k| k.name.eq_ignore_ascii_case(component)) {
+                        Some(index) => index,
+                        None => {
+                            self.subkeys.push(TestKey::new(component));
+                            self.subkeys.len() - 1
+                        }
+                    };
+                    self.subkeys[index].insert(path, key);
+                }
+            }
+        }
+
+        /// Serialize this key as the root of a hive file
+        pub(crate) fn build_hive(&self) -> Vec<u8> {
+            let mut bin = b"hbin".to_vec();
+            bin.resize(32, 0);
+            let root = self.write(&mut bin);
+
+            let mut data = vec![0u8; HBIN_BASE];
+            data[0..4].copy_from_slice(b"regf");
+            data[36..40].copy_from_slice(&root.to_le_bytes());
+            data[40..44].copy_from_slice(&(bin.len() as u32).to_le_bytes());
+            data.extend_from_slice(&bin);
+            data
+        }
+
+        /// Serialize this key as the root of a hive and open it under `file_name`
+        pub(crate) fn open(&self, file_name: &str) -> RegistryHive {
+            RegistryParser::new()
+                .parse_registry_data(&self.build_hive(), Path::new(file_name))
+                .unwrap()
+        }
+
+        /// Write the key, its values and subkeys, returning the key node offset
+        fn write(&self, bin: &mut Vec<u8>) -> u32 {
+            let mut value_offsets = Vec::new();
+            for (name, value_type, data) in &self.values {
+                let data_offset = push_cell(bin, data);
+                let mut vk = vec![0u8; 20];
+                vk[0..2].copy_from_slice(b"vk");
+                vk[2..4].copy_from_slice(&(name.len() as u16).to_le_bytes());
+                vk[4..8].copy_from_slice(&(data.len() as u32).to_le_bytes());
+                vk[8..12].copy_from_slice(&data_offset.to_le_bytes());
+                vk[12..16].copy_from_slice(&value_type.to_le_bytes());
+                vk[16..18].copy_from_slice(&VALUE_COMP_NAME.to_le_bytes());
+                vk.extend_from_slice(name.as_bytes());
+                value_offsets.push(push_cell(bin, &vk));
+            }
+            let value_list: Vec<u8> = value_offsets.iter().flat_map(|o| o.to_le_bytes()).collect();
+            let value_list = if value_offsets.is_empty() { u32::MAX } else { push_cell(bin, &value_list) };
+
+            let children: Vec<u32> = self.subkeys.iter().map(|subkey| subkey.write(bin)).collect();
+            let subkey_list = if children.is_empty() {
+                u32::MAX
+            } else {
+                let mut lf = b"lf".to_vec();
+                lf.extend_from_slice(&(children.len() as u16).to_le_bytes());
+                for child in &children {
+                    lf.extend_from_slice(&child.to_le_bytes());
+                    lf.extend_from_slice(&0u32.to_le_bytes());
+                }
+                push_cell(bin, &lf)
+            };
+
+            let mut nk = vec![0u8; 76];
+            nk[0..2].copy_from_slice(b"nk");
+            nk[2..4].copy_from_slice(&KEY_COMP_NAME.to_le_bytes());
+            nk[4..12].copy_from_slice(&self.last_written.to_le_bytes());
+            nk[20..24].copy_from_slice(&(children.len() as u32).to_le_bytes());
+            nk[28..32].copy_from_slice(&subkey_list.to_le_bytes());
+            nk[36..40].copy_from_slice(&(self.values.len() as u32).to_le_bytes());
+            nk[40..44].copy_from_slice(&value_list.to_le_bytes());
+            nk[72..74].copy_from_slice(&(self.name.len() as u16).to_le_bytes());
+            nk.extend_from_slice(self.name.as_bytes());
+            push_cell(bin, &nk)
+        }
+    }
+
+    /// Append a cell to a hive bin, returning its offset
+    fn push_cell(bin: &mut Vec<u8>, content: &[u8]) -> u32 {
+        let offset = bin.len();
+        let size = (content.len() + 4 + 7) & !7;
+        bin.extend_from_slice(&(-(size as i32)).to_le_bytes());
+        bin.extend_from_slice(content);
+        bin.resize(offset + size, 0);
+        offset as u32
+    }
+
+    /// Encode a string as UTF-16LE without a terminator
+    pub(crate) fn utf16(s: &str) -> Vec<u8> {
+        s.encode_utf16().flat_map(|c| c.to_le_bytes()).collect()
+    }
+
+    /// Encode a string as null-terminated UTF-16LE
+    pub(crate) fn utf16z(s: &str) -> Vec<u8> {
+        let mut data = utf16(s);
+        data.extend_from_slice(&[0, 0]);
+        data
+    }
+}
@@ -0,0 +1,246 @@
+//! SAM hive parsing module for local user accounts
+//!
+//! Supports parsing of:
+//! - SAM\Domains\Account\Users\<RID> F values (logon/password times, counts, flags)
+//! - SAM\Domains\Account\Users\<RID> V values (username, full name, comment)
+//! - SAM\Domains\Account\Users\Names (account creation approximated by key last write)
+
+use crate::registry::{
+    filetime_to_datetime, read_u16, read_u32, read_u64, utf16_string, RegistryEventType, RegistryHive,
+    RegistryKey, RegistryTimelineEvent,
+};
+use crate::types::EventTimestamps;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Size of the V value header; string offsets are relative to its end
+const V_HEADER_SIZE: usize = 0xCC;
+
+/// Account control flags (ACB_*) stored in the F value
+const ACCOUNT_FLAGS: [(u16, &str); 16] = [
+    (0x0001, "Disabled"),
+    (0x0002, "HomeDirRequired"),
+    (0x0004, "PasswordNotRequired"),
+    (0x0008, "TempDuplicate"),
+    (0x0010, "NormalAccount"),
+    (0x0020, "MNSLogon"),
+    (0x0040, "InterdomainTrust"),
+    (0x0080, "WorkstationTrust"),
+    (0x0100, "ServerTrust"),
+    (0x0200, "PasswordDoesNotExpire"),
+    (0x0400, "Locked"),
+    (0x0800, "EncryptedTextPasswordAllowed"),
+    (0x1000, "SmartcardRequired"),
+    (0x2000, "TrustedForDelegation"),
+    (0x4000, "NotDelegated"),
+    (0x8000, "UseDesKeyOnly"),
+];
+
+/// Local user account from the SAM hive
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SamAccount {
+    /// Relative identifier
+    pub rid: u32,
+    /// Registry key path of the account
+    pub key_path: String,
+    /// Username
+    pub username: String,
+    /// Full name
+    pub full_name: Option<String>,
+    /// Comment / description
+    pub comment: Option<String>,
+    /// Account creation (Names\<username> key last write)
+    pub created: Option<DateTime<Utc>>,
+    /// Last successful logon
+    pub last_logon: Option<DateTime<Utc>>,
+    /// Last password change
+    pub password_last_set: Option<DateTime<Utc>>,
+    /// Account expiry
+    pub account_expires: Option<DateTime<Utc>>,
+    /// Last failed logon (incorrect password)
+    pub last_failed_logon: Option<DateTime<Utc>>,
+    /// Successful logon count
+    pub logon_count: u16,
+    /// Failed logon count
+    pub failed_logon_count: u16,
+    /// Account control flags
+    pub flags: u16,
+}
+
+impl SamAccount {
+    /// Names of the set account control flags
+    pub fn flag_names(&self) -> Vec<&'static str> {
+        ACCOUNT_FLAGS
+            .iter()
+            .filter(|(bit, _)| self.flags & bit != 0)
+            .map(|(_, name)| *name)
+            .collect()
+    }
+}
+
+/// Parse all local accounts from a SAM hive
+pub fn parse_sam(hive: &RegistryHive) -> Vec<SamAccount> {
+    let Some(users) = hive.open_key("SAM\\Domains\\Account\\Users") else {
+        return Vec::new();
+    };
+    let names = users.subkey("Names");
+
+    users
+        .subkeys
+        .iter()
+        .filter(|k| !k.name.eq_ignore_ascii_case("Names"))
+        .filter_map(|key| parse_account(key, names))
+        .collect()
+}
+
+/// Parse a Users\<RID> key
+fn parse_account(key: &RegistryKey, names: Option<&RegistryKey>) -> Option<SamAccount> {
+    let f = &key.value("F")?.data;
+    let v = key.value("V").map(|v| v.data.as_slice()).unwrap_or(&[]);
+
+    let rid = read_u32(f, 0x30).or_else(|| u32::from_str_radix(&key.name, 16).ok())?;
+    let username = v_string(v, 0x0C).unwrap_or_else(|| format!("RID {}", rid));
+    let created = names
+        .and_then(|n| n.subkey(&username))
+        .and_then(|k| k.last_written);
+
+    Some(SamAccount {
+        rid,
+        key_path: key.path.clone(),
+        full_name: v_string(v, 0x18),
+        comment: v_string(v, 0x24),
+        created,
+        last_logon: f_time(f, 0x08),
+        password_last_set: f_time(f, 0x18),
+        account_expires: f_time(f, 0x20),
+        last_failed_logon: f_time(f, 0x28),
+        flags: read_u16(f, 0x38).unwrap_or(0),
+        failed_logon_count: read_u16(f, 0x40).unwrap_or(0),
+        logon_count: read_u16(f, 0x42).unwrap_or(0),
+        username,
+    })
+}
+
+/// Read a FILETIME from the F value ("never" values are treated as unset)
+fn f_time(f: &[u8], offset: usize) -> Option<DateTime<Utc>> {
+    read_u64(f, offset)
+        .filter(|&ft| ft < 0x7FFF_FFFF_FFFF_FFFF)
+        .and_then(filetime_to_datetime)
+}
+
+/// Read a UTF-16 string referenced by an (offset, length) pair in the V value header
+fn v_string(v: &[u8], entry: usize) -> Option<String> {
+    let offset = read_u32(v, entry)? as usize + V_HEADER_SIZE;
+    let length = read_u32(v, entry + 4)? as usize;
+    let s = utf16_string(v.get(offset..offset.checked_add(length)?)?);
+    (!s.is_empty()).then_some(s)
+}
+
+/// Extract one event per account timestamp
+pub fn extract_timeline_events(hive: &RegistryHive) -> Vec<RegistryTimelineEvent> {
+    let mut events = Vec::new();
+
+    for account in parse_sam(hive) {
+        let mut metadata = vec![
+            ("Username".to_string(), account.username.clone()),
+            ("RID".to_string(), account.rid.to_string()),
+            ("Logon Count".to_string(), account.logon_count.to_string()),
+            ("Failed Logon Count".to_string(), account.failed_logon_count.to_string()),
+            ("Flags".to_string(), account.flag_names().join(", ")),
+        ];
+        if let Some(full_name) = &account.full_name {
+            metadata.push(("Full Name".to_string(), full_name.clone()));
+        }
+        if let Some(comment) = &account.comment {
+            metadata.push(("Comment".to_string(), comment.clone()));
+        }
+
+        let activity = [
+            ("Account created", account.created),
+            ("Last logon", account.last_logon),
+            ("Password last set", account.password_last_set),
+            ("Account expires", account.account_expires),
+            ("Last failed logon", account.last_failed_logon),
+        ];
+
+        for (label, time) in activity {
+            let Some(time) = time else {
+                continue;
+            };
+            let is_creation = label == "Account created";
+            events.push(RegistryTimelineEvent {
+                key_path: account.key_path.clone(),
+                value_name: Some(if is_creation { "(Names)" } else { "F" }.to_string()),
+                event_type: RegistryEventType::KeyModified,
+                value_type: None,
+                data: None,
+                data_size: None,
+                timestamps: EventTimestamps {
+                    created: is_creation.then_some(time),
+                    modified: (!is_creation).then_some(time),
+                    accessed: None,
+                    mft_modified: None,
+                },
+                description: Some(format!("SAM: {} ({}) - {}", account.username, account.rid, label)),
+                metadata: metadata.clone(),
+                source: Some("SAM".to_string()),
+            });
+        }
+    }
+
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::test_hive::utf16;
+    use chrono::Datelike;
+
+    #[test]
+    fn test_v_string() {
+        let mut v = vec![0u8; V_HEADER_SIZE];
+        let name = utf16("alice");
+        let full_name = utf16("Alice Smith");
+        v[0x0C..0x10].copy_from_slice(&0u32.to_le_bytes());
+        v[0x10..0x14].copy_from_slice(&(name.len() as u32).to_le_bytes());
+        v[0x18..0x1C].copy_from_slice(&(name.len() as u32).to_le_bytes());
+        v[0x1C..0x20].copy_from_slice(&(full_name.len() as u32).to_le_bytes());
+        v.extend_from_slice(&name);
+        v.extend_from_slice(&full_name);
+
+        assert_eq!(v_string(&v, 0x0C).as_deref(), Some("alice"));
+        assert_eq!(v_string(&v, 0x18).as_deref(), Some("Alice Smith"));
+        assert_eq!(v_string(&v, 0x24), None);
+    }
+
+    #[test]
+    fn test_f_time_skips_never() {
+        let mut f = vec![0u8; 0x50];
+        f[0x08..0x10].copy_from_slice(&132223104000000000u64.to_le_bytes()); // 2020-01-01
+        f[0x20..0x28].copy_from_slice(&0x7FFF_FFFF_FFFF_FFFFu64.to_le_bytes());
+        assert_eq!(f_time(&f, 0x08).unwrap().year(), 2020);
+        assert!(f_time(&f, 0x20).is_none());
+        assert!(f_time(&f, 0x18).is_none());
+    }
+
+    #[test]
+    fn test_flag_names() {
+        let account = SamAccount {
+            rid: 500,
+            key_path: String::new(),
+            username: "Administrator".to_string(),
+            full_name: None,
+            comment: None,
+            created: None,
+            last_logon: None,
+            password_last_set: None,
+            account_expires: None,
+            last_failed_logon: None,
+            logon_count: 0,
+            failed_logon_count: 0,
+            flags: 0x0211,
+        };
+        assert_eq!(account.flag_names(), vec!["Disabled", "NormalAccount", "PasswordDoesNotExpire"]);
+    }
+}
@@ -0,0 +1,652 @@
+//! Scheduled task parsing module
+//!
+//! Supports parsing of:
+//! - Task Scheduler 2.0 XML task files (Windows\System32\Tasks, UTF-16 or UTF-8)
+//! - Task Scheduler 1.0 .job files (Windows\Tasks)
+//! - TaskCache\Tree and TaskCache\Tasks in the SOFTWARE hive, including DynamicInfo run times and
+//!   Exec/COM handler actions
+
+use crate::error::{Error, Result};
+use crate::registry::{
+    filetime_to_datetime, read_u16, read_u32, read_u64, systemtime, utf16_string, RegistryHive, RegistryKey,
+};
+use crate::system_context::{LOCAL_SYSTEM_TIME, TIME_BASIS};
+use crate::xml::xml_element;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use std::path::Path;
+
+/// TaskCache key in the SOFTWARE hive
+const TASK_CACHE_KEY: &str = "Microsoft\\Windows NT\\CurrentVersion\\Schedule\\TaskCache";
+/// Bytes read from an .xml file to look for the Task root element
+const XML_SNIFF_SIZE: u64 = 4096;
+/// Size of the .job fixed-length data section
+const JOB_FIXED_SIZE: usize = 68;
+/// Size of a .job trigger
+const JOB_TRIGGER_SIZE: usize = 48;
+/// Action magic numbers in TaskCache Actions values
+const ACTION_EXEC: u16 = 0x6666;
+const ACTION_COM_HANDLER: u16 = 0x7777;
+
+/// Task file format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TaskFormat {
+    /// Task Scheduler 2.0 XML
+    Xml,
+    /// Task Scheduler 1.0 .job
+    Job,
+}
+
+/// Scheduled task definition from a task file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledTask {
+    /// Task path as used by TaskCache (e.g. \Microsoft\Windows\Defrag\ScheduledDefrag)
+    pub path: String,
+    /// File format
+    pub format: TaskFormat,
+    /// RegistrationInfo Date
+    pub registration_date: Option<DateTime<Utc>>,
+    /// RegistrationInfo Date had no UTC offset, so it is local time on the host kept as written
+    pub registration_date_is_local: bool,
+    /// RegistrationInfo Author (.job author)
+    pub author: Option<String>,
+    /// RegistrationInfo Description (.job comment)
+    pub description: Option<String>,
+    /// Actions, with Exec actions as command lines
+    pub actions: Vec<String>,
+    /// Trigger schedules
+    pub triggers: Vec<String>,
+    /// Account the task runs as, with logon type and run level
+    pub principal: Option<String>,
+    /// Whether the task is enabled
+    pub enabled: bool,
+    /// Whether the task is hidden in the Task Scheduler UI
+    pub hidden: bool,
+    /// Last run time (.job)
+    pub last_run: Option<DateTime<Utc>>,
+    /// Status of the last run (.job)
+    pub status: Option<String>,
+    /// Exit code of the last run (.job)
+    pub exit_code: Option<u32>,
+}
+
+impl ScheduledTask {
+    /// Task name (last component of the path)
+    pub fn name(&self) -> &str {
+        self.path.rsplit('\\').next().unwrap_or(&self.path)
+    }
+
+    /// Labelled values for event metadata
+    pub fn fields(&self) -> Vec<(String, String)> {
+        let mut fields = vec![
+            ("Task Path".to_string(), self.path.clone()),
+            ("Format".to_string(), format!("{:?}", self.format)),
+        ];
+        let optional = [
+            ("Author", self.author.clone()),
+            ("Description", self.description.clone()),
+            ("Command Line", Some(self.actions.join("; ")).filter(|a| !a.is_empty())),
+            ("Triggers", Some(self.triggers.join("; ")).filter(|t| !t.is_empty())),
+            ("Principal", self.principal.clone()),
+            ("Enabled", (!self.enabled).then(|| "false".to_string())),
+            ("Hidden", self.hidden.then(|| "true".to_string())),
+            ("Status", self.status.clone()),
+            ("Exit Code", self.exit_code.map(|c| format!("0x{:08X}", c))),
+            (TIME_BASIS, self.registration_date_is_local.then(|| LOCAL_SYSTEM_TIME.to_string())),
+        ];
+        fields.extend(optional.into_iter().filter_map(|(label, value)| Some((label.to_string(), value?))));
+        fields
+    }
+}
+
+/// Task registration from TaskCache in the SOFTWARE hive
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskCacheEntry {
+    /// Task GUID
+    pub id: String,
+    /// Task path
+    pub path: String,
+    /// Author value
+    pub author: Option<String>,
+    /// Date value (registration date as written by the task)
+    pub date: Option<String>,
+    /// Command lines and COM handlers from the Actions value
+    pub actions: Vec<String>,
+    /// Creation time from DynamicInfo
+    pub created: Option<DateTime<Utc>>,
+    /// Last run time from DynamicInfo
+    pub last_run: Option<DateTime<Utc>>,
+    /// Last successful run time from DynamicInfo (Windows 10+)
+    pub last_success: Option<DateTime<Utc>>,
+    /// Result of the last run from DynamicInfo
+    pub last_result: Option<u32>,
+    /// Last written time of the Tasks key
+    pub key_last_written: Option<DateTime<Utc>>,
+    /// Tree key has no SD value, which hides the task from schtasks and the Task Scheduler UI
+    pub missing_security_descriptor: bool,
+}
+
+impl TaskCacheEntry {
+    /// Labelled values for event metadata
+    pub fn fields(&self) -> Vec<(String, String)> {
+        let optional = [
+            ("Task ID", Some(self.id.clone())),
+            ("TaskCache Author", self.author.clone()),
+            ("TaskCache Date", self.date.clone()),
+            ("TaskCache Actions", Some(self.actions.join("; ")).filter(|a| !a.is_empty())),
+            ("TaskCache Created", self.created.map(|t| t.to_rfc3339())),
+            ("Last Run", self.last_run.map(|t| t.to_rfc3339())),
+            ("Last Successful Run", self.last_success.map(|t| t.to_rfc3339())),
+            ("Last Result", self.last_result.map(|r| format!("0x{:08X}", r))),
+            ("TaskCache Key Written", self.key_last_written.map(|t| t.to_rfc3339())),
+            ("Tree SD", self.missing_security_descriptor.then(|| "Missing".to_string())),
+        ];
+        optional.into_iter().filter_map(|(label, value)| Some((label.to_string(), value?))).collect()
+    }
+}
+
+/// Whether a path is a scheduled task file (System32\Tasks entry or .job file)
+pub fn is_task_file(path: &str) -> bool {
+    let lower = path.to_lowercase().replace('\\', "/");
+    lower.ends_with(".job") || lower.contains("system32/tasks/")
+}
+
+/// Whether text starts with a Task root element (Task Scheduler 2.0 definition)
+pub fn is_task_xml(data: &[u8]) -> bool {
+    let text = decode_text(data).unwrap_or_else(|| String::from_utf8_lossy(data).into_owned());
+    let mut rest = text.as_str();
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start + 1..];
+        // Skip the XML declaration, comments and processing instructions
+        if rest.starts_with('?') || rest.starts_with('!') {
+            continue;
+        }
+        let name = rest.split(|c: char| c.is_whitespace() || c == '>' || c == '/').next().unwrap_or("");
+        return name.rsplit(':').next() == Some("Task");
+    }
+    false
+}
+
+/// Whether the file at `path` is a task XML, reading only its first few kilobytes
+pub fn is_task_xml_file(path: &Path) -> bool {
+    let mut head = Vec::new();
+    std::fs::File::open(path)
+        .and_then(|file| file.take(XML_SNIFF_SIZE).read_to_end(&mut head))
+        .is_ok()
+        && is_task_xml(&head)
+}
+
+/// Task path for a task file: the part below System32\Tasks, or \name for .job files
+pub fn task_path(file_path: &str) -> String {
+    let normalized = file_path.replace('/', "\\");
+    let lower = normalized.to_ascii_lowercase();
+    if let Some(index) = lower.find("system32\\tasks\\") {
+        return normalized[index + "system32\\tasks".len()..].to_string();
+    }
+    let name = normalized.rsplit('\\').next().unwrap_or(&normalized);
+    let stem = if name.to_ascii_lowercase().ends_with(".job") { &name[..name.len() - 4] } else { name };
+    format!("\\{}", stem)
+}
+
+/// Parse an XML task file or a .job file
+pub fn parse_task(data: &[u8], file_path: &str) -> Result<ScheduledTask> {
+    match decode_text(data) {
+        Some(xml) if xml.trim_start().starts_with('<') => parse_task_xml(&xml, file_path),
+        _ => parse_job(data, file_path),
+    }
+}
+
+/// Decode a UTF-16 (with BOM or detected from the first character) or UTF-8 text file
+fn decode_text(data: &[u8]) -> Option<String> {
+    let utf16 = |bytes: &[u8], big_endian: bool| {
+        let chars: Vec<u16> = bytes
+            .chunks_exact(2)
+            .map(|c| if big_endian { u16::from_be_bytes([c[0], c[1]]) } else { u16::from_le_bytes([c[0], c[1]]) })
+            .collect();
+        String::from_utf16_lossy(&chars)
+    };
+    match data {
+        [0xFF, 0xFE, rest @ ..] => Some(utf16(rest, false)),
+        [0xFE, 0xFF, rest @ ..] => Some(utf16(rest, true)),
+        [b'<', 0, ..] => Some(utf16(data, false)),
+        [0xEF, 0xBB, 0xBF, rest @ ..] => std::str::from_utf8(rest).ok().map(|s| s.to_string()),
+        _ => std::str::from_utf8(data).ok().map(|s| s.to_string()),
+    }
+}
+
+/// Parse a Task Scheduler 2.0 XML definition
+fn parse_task_xml(xml: &str, file_path: &str) -> Result<ScheduledTask> {
+    let task = xml_child(xml, "Task").ok_or_else(|| Error::ParseError("No Task element in task XML".to_string()))?;
+    let registration = xml_child(task, "RegistrationInfo").unwrap_or("");
+    let settings = xml_child(task, "Settings").unwrap_or("");
+    let flag = |name: &str| xml_element(settings, name).map(|v| v.eq_ignore_ascii_case("true"));
+
+    let actions = xml_child(task, "Actions").map_or_else(Vec::new, |actions| {
+        xml_children(actions)
+            .into_iter()
+            .map(|(kind, action)| match kind.as_str() {
+                "Exec" => command_line(xml_element(action, "Command").unwrap_or_default(), xml_element(action, "Arguments")),
+                "ComHandler" => format!("COM handler {}", xml_element(action, "ClassId").unwrap_or_default()),
+                "SendEmail" => format!("Send e-mail to {}", xml_element(action, "To").unwrap_or_default()),
+                "ShowMessage" => format!("Show message {}", xml_element(action, "Title").unwrap_or_default()),
+                other => other.to_string(),
+            })
+            .collect()
+    });
+    let triggers = xml_child(task, "Triggers").map_or_else(Vec::new, |triggers| {
+        xml_children(triggers).into_iter().map(|(kind, trigger)| describe_xml_trigger(&kind, trigger)).collect()
+    });
+    let principal = xml_child(task, "Principals").and_then(|p| xml_child(p, "Principal")).and_then(|principal| {
+        let account = xml_element(principal, "UserId").or_else(|| xml_element(principal, "GroupId"))?;
+        let details: Vec<String> = ["LogonType", "RunLevel"].iter().filter_map(|name| xml_element(principal, name)).collect();
+        Some(if details.is_empty() { account } else { format!("{} ({})", account, details.join(", ")) })
+    });
+
+    let registration_date = xml_element(registration, "Date").as_deref().and_then(parse_xml_date);
+    Ok(ScheduledTask {
+        path: xml_element(registration, "URI").unwrap_or_else(|| task_path(file_path)),
+        format: TaskFormat::Xml,
+        registration_date: registration_date.map(|(date, _)| date),
+        registration_date_is_local: registration_date.is_some_and(|(_, is_local)| is_local),
+        author: xml_element(registration, "Author"),
+        description: xml_element(registration, "Description"),
+        actions,
+        triggers,
+        principal,
+        enabled: flag("Enabled").unwrap_or(true),
+        hidden: flag("Hidden").unwrap_or(false),
+        last_run: None,
+        status: None,
+        exit_code: None,
+    })
+}
+
+/// Describe an XML trigger, e.g. "Calendar at 2021-01-01T09:00:00, every 1 day(s), repeating every PT1H"
+fn describe_xml_trigger(kind: &str, trigger: &str) -> String {
+    let mut parts = vec![kind.strip_suffix("Trigger").unwrap_or(kind).to_string()];
+    if let Some(start) = xml_element(trigger, "StartBoundary") {
+        parts[0] = format!("{} at {}", parts[0], start);
+    }
+    if let Some(days) = xml_element(trigger, "DaysInterval") {
+        parts.push(format!("every {} day(s)", days));
+    } else if let Some(weeks) = xml_element(trigger, "WeeksInterval") {
+        parts.push(format!("every {} week(s)", weeks));
+    } else if xml_child(trigger, "ScheduleByMonth").is_some() || xml_child(trigger, "ScheduleByMonthDayOfWeek").is_some() {
+        parts.push("monthly".to_string());
+    }
+    if let Some(user) = xml_element(trigger, "UserId") {
+        parts.push(format!("for {}", user));
+    }
+    if let Some(interval) = xml_child(trigger, "Repetition").and_then(|r| xml_element(r, "Interval")) {
+        parts.push(format!("repeating every {}", interval));
+    }
+    if let Some(delay) = xml_element(trigger, "Delay") {
+        parts.push(format!("delayed {}", delay));
+    }
+    if xml_element(trigger, "Enabled").is_some_and(|e| e.eq_ignore_ascii_case("false")) {
+        parts.push("disabled".to_string());
+    }
+    parts.join(", ")
+}
+
+/// Parse an xs:dateTime, flagging dates without an offset, which are local time on the host
+/// and kept as written
+fn parse_xml_date(date: &str) -> Option<(DateTime<Utc>, bool)> {
+    DateTime::parse_from_rfc3339(date)
+        .map(|d| (d.with_timezone(&Utc), false))
+        .ok()
+        .or_else(|| NaiveDateTime::parse_from_str(date, "%Y-%m-%dT%H:%M:%S%.f").ok().map(|d| (d.and_utc(), true)))
+}
+
+/// Direct child elements of an XML fragment as (local name, inner content), in document order
+fn xml_children(xml: &str) -> Vec<(String, &str)> {
+    let mut children = Vec::new();
+    let mut depth = 0usize;
+    let mut open: Option<(String, usize)> = None;
+    let mut pos = 0;
+    while let Some(start) = xml[pos..].find('<').map(|i| pos + i) {
+        let Some(end) = xml[start..].find('>').map(|i| start + i) else { break };
+        let tag = &xml[start + 1..end];
+        pos = end + 1;
+        if tag.starts_with('?') || tag.starts_with('!') {
+            continue;
+        }
+        let name = tag.trim_start_matches('/').split(|c: char| c.is_whitespace() || c == '/').next().unwrap_or("");
+        let name = name.rsplit(':').next().unwrap_or(name).to_string();
+        if tag.starts_with('/') {
+            depth = depth.saturating_sub(1);
+            if depth == 0 {
+                if let Some((name, inner_start)) = open.take() {
+                    children.push((name, &xml[inner_start..start]));
+                }
+            }
+        } else if tag.ends_with('/') {
+            if depth == 0 {
+                children.push((name, ""));
+            }
+        } else {
+            if depth == 0 {
+                open = Some((name, pos));
+            }
+            depth += 1;
+        }
+    }
+    children
+}
+
+/// Inner content of the first direct child element with the given local name
+fn xml_child<'a>(xml: &'a str, name: &str) -> Option<&'a str> {
+    xml_children(xml).into_iter().find(|(child, _)| child == name).map(|(_, inner)| inner)
+}
+
+fn command_line(command: String, arguments: Option<String>) -> String {
+    match arguments {
+        Some(arguments) => format!("{} {}", command, arguments),
+        None => command,
+    }
+}
+
+/// Parse a Task Scheduler 1.0 .job file
+fn parse_job(data: &[u8], file_path: &str) -> Result<ScheduledTask> {
+    let invalid = || Error::ParseError("Invalid .job file".to_string());
+    let strings_offset = read_u16(data, 20).ok_or_else(invalid)? as usize;
+    let triggers_offset = read_u16(data, 22).ok_or_else(invalid)? as usize;
+    if data.len() < JOB_FIXED_SIZE || strings_offset < JOB_FIXED_SIZE || triggers_offset < strings_offset {
+        return Err(invalid());
+    }
+
+    // Application name, parameters, working directory, author and comment, each prefixed by a character count
+    let mut strings = Vec::new();
+    let mut pos = strings_offset;
+    for _ in 0..5 {
+        let count = read_u16(data, pos).ok_or_else(invalid)? as usize;
+        let bytes = data.get(pos + 2..pos + 2 + count * 2).ok_or_else(invalid)?;
+        strings.push(Some(utf16_string(bytes)).filter(|s| !s.is_empty()));
+        pos += 2 + count * 2;
+    }
+    let [application, parameters, _working_directory, author, comment] = <[Option<String>; 5]>::try_from(strings).map_err(|_| invalid())?;
+
+    let trigger_count = read_u16(data, triggers_offset).unwrap_or(0) as usize;
+    let triggers = (0..trigger_count)
+        .filter_map(|i| data.get(triggers_offset + 2 + i * JOB_TRIGGER_SIZE..triggers_offset + 2 + (i + 1) * JOB_TRIGGER_SIZE))
+        .map(describe_job_trigger)
+        .collect();
+
+    let status = read_u32(data, 44).unwrap_or(0);
+    let flags = read_u32(data, 48).unwrap_or(0);
+    Ok(ScheduledTask {
+        path: task_path(file_path),
+        format: TaskFormat::Job,
+        registration_date: None,
+        registration_date_is_local: false,
+        author,
+        description: comment,
+        actions: application.map(|application| command_line(application, parameters)).into_iter().collect(),
+        triggers,
+        principal: None,
+        enabled: flags & 0x4 == 0,
+        hidden: flags & 0x200 != 0,
+        last_run: data.get(52..68).and_then(systemtime),
+        status: Some(job_status(status)),
+        exit_code: read_u32(data, 40),
+    })
+}
+
+/// Describe a .job trigger, e.g. "Daily at 09:30 from 2021-01-01, every 1 day(s)"
+fn describe_job_trigger(trigger: &[u8]) -> String {
+    let field = |offset: usize| read_u16(trigger, offset).unwrap_or(0);
+    let start = format!("{:02}:{:02} from {:04}-{:02}-{:02}", field(16), field(18), field(4), field(6), field(8));
+    let mut description = match read_u32(trigger, 32).unwrap_or(u32::MAX) {
+        0 => format!("Once at {}", start),
+        1 => format!("Daily at {}, every {} day(s)", start, field(36)),
+        2 => format!("Weekly at {}, every {} week(s)", start, field(36)),
+        3 | 4 => format!("Monthly at {}", start),
+        5 => "On idle".to_string(),
+        6 => "At system start".to_string(),
+        7 => "At logon".to_string(),
+        other => format!("Trigger type {}", other),
+    };
+    // Repetition interval in minutes
+    if let Some(interval) = read_u32(trigger, 24).filter(|&m| m > 0) {
+        description.push_str(&format!(", repeating every {} minute(s)", interval));
+    }
+    if read_u32(trigger, 28).unwrap_or(0) & 0x4 != 0 {
+        description.push_str(", disabled");
+    }
+    description
+}
+
+/// Name a .job status code
+fn job_status(status: u32) -> String {
+    match status {
+        0x0004_1300 => "Ready".to_string(),
+        0x0004_1301 => "Running".to_string(),
+        0x0004_1302 => "Disabled".to_string(),
+        0x0004_1303 => "Has not run".to_string(),
+        0x0004_1304 => "No more runs".to_string(),
+        0x0004_1305 => "Not scheduled".to_string(),
+        0x0004_1306 => "Terminated".to_string(),
+        0x0004_1307 => "No valid triggers".to_string(),
+        other => format!("0x{:08X}", other),
+    }
+}
+
+/// Read task registrations from TaskCache\Tasks, with paths and SD values from TaskCache\Tree
+pub fn parse_task_cache(hive: &RegistryHive) -> Vec<TaskCacheEntry> {
+    let Some(cache) = hive.open_key(TASK_CACHE_KEY) else {
+        return Vec::new();
+    };
+    // Task GUID -> (path, has SD value)
+    let mut tree = Vec::new();
+    if let Some(root) = cache.subkey("Tree") {
+        collect_tree(root, "", &mut tree);
+    }
+
+    let Some(tasks) = cache.subkey("Tasks") else {
+        return Vec::new();
+    };
+    tasks
+        .subkeys
+        .iter()
+        .map(|task| {
+            let string = |name: &str| task.value(name).and_then(|v| v.string_data()).filter(|s| !s.is_empty());
+            let tree_entry = tree.iter().find(|(id, _, _)| id.eq_ignore_ascii_case(&task.name));
+            let dynamic_info = task.value("DynamicInfo").map(|v| v.data.as_slice()).unwrap_or(&[]);
+            let filetime = |offset: usize| read_u64(dynamic_info, offset).and_then(filetime_to_datetime);
+
+            TaskCacheEntry {
+                id: task.name.clone(),
+                path: string("Path").or_else(|| tree_entry.map(|(_, path, _)| path.clone())).unwrap_or_default(),
+                author: string("Author"),
+                date: string("Date"),
+                actions: task.value("Actions").map_or_else(Vec::new, |v| cache_actions(&v.data)),
+                created: filetime(4),
+                last_run: filetime(12),
+                last_success: filetime(28),
+                last_result: read_u32(dynamic_info, 24).filter(|_| dynamic_info.len() >= 28),
+                key_last_written: task.last_written,
+                missing_security_descriptor: tree_entry.is_some_and(|(_, _, has_sd)| !has_sd),
+            }
+        })
+        .collect()
+}
+
+/// Collect (task GUID, path, has SD value) from the Tree key hierarchy
+fn collect_tree(key: &RegistryKey, path: &str, tasks: &mut Vec<(String, String, bool)>) {
+    for child in &key.subkeys {
+        let child_path = format!("{}\\{}", path, child.name);
+        if let Some(id) = child.value("Id").and_then(|v| v.string_data()) {
+            tasks.push((id, child_path.clone(), child.value("SD").is_some()));
+        }
+        collect_tree(child, &child_path, tasks);
+    }
+}
+
+/// Decode Exec and COM handler actions from a TaskCache Actions value
+fn cache_actions(data: &[u8]) -> Vec<String> {
+    let mut actions = Vec::new();
+    let Some(version) = read_u16(data, 0) else {
+        return actions;
+    };
+    // Length-prefixed UTF-16 string
+    let string = |pos: &mut usize| -> Option<String> {
+        let length = read_u32(data, *pos)? as usize;
+        let value = utf16_string(data.get(*pos + 4..*pos + 4 + length)?);
+        *pos += 4 + length;
+        Some(value)
+    };
+
+    let mut pos = 2;
+    // Version 2+ starts with the principal ID the actions run under
+    if version >= 2 && string(&mut pos).is_none() {
+        return actions;
+    }
+    while let Some(magic) = read_u16(data, pos) {
+        pos += 2;
+        let Some(_id) = string(&mut pos) else { break };
+        match magic {
+            ACTION_EXEC => {
+                let (Some(command), Some(arguments), Some(_directory)) = (string(&mut pos), string(&mut pos), string(&mut pos)) else {
+                    break;
+                };
+                if version >= 3 {
+                    pos += 2;
+                }
+                actions.push(command_line(command, Some(arguments).filter(|a| !a.is_empty())));
+            }
+            ACTION_COM_HANDLER => {
+                let Some(class_id) = data.get(pos..pos + 16).map(crate::registry::format_guid) else { break };
+                pos += 16;
+                if string(&mut pos).is_none() {
+                    break;
+                }
+                actions.push(format!("COM handler {}", class_id));
+            }
+            _ => break,
+        }
+    }
+    actions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::test_hive::{utf16, utf16z};
+
+    const TASK_XML: &str = r#"<?xml version="1.0" encoding="UTF-16"?>
+<Task version="1.2" xmlns="http://schemas.microsoft.com/windows/2004/02/mit/task">
+  <RegistrationInfo>
+    <Date>2021-03-04T10:15:00.1234567</Date>
+    <Author>CORP\mallory</Author>
+    <URI>\Updater\Check</URI>
+  </RegistrationInfo>
+  <Triggers>
+    <LogonTrigger><Enabled>true</Enabled><UserId>CORP\alice</UserId></LogonTrigger>
+    <CalendarTrigger>
+      <StartBoundary>2021-03-05T09:00:00</StartBoundary>
+      <Repetition><Interval>PT1H</Interval></Repetition>
+      <ScheduleByDay><DaysInterval>1</DaysInterval></ScheduleByDay>
+    </CalendarTrigger>
+  </Triggers>
+  <Principals>
+    <Principal id="Author"><UserId>S-1-5-18</UserId><RunLevel>HighestAvailable</RunLevel></Principal>
+  </Principals>
+  <Settings><Enabled>true</Enabled><Hidden>true</Hidden></Settings>
+  <Actions Context="Author">
+    <Exec><Command>C:\ProgramData\u.exe</Command><Arguments>-k &quot;run&quot;</Arguments></Exec>
+  </Actions>
+</Task>"#;
+
+    #[test]
+    fn test_parse_task_xml() {
+        let mut data = vec![0xFF, 0xFE];
+        data.extend(utf16(TASK_XML));
+        let task = parse_task(&data, "Windows/System32/Tasks/Updater/Check").unwrap();
+
+        assert_eq!(task.format, TaskFormat::Xml);
+        assert_eq!(task.path, "\\Updater\\Check");
+        assert_eq!(task.name(), "Check");
+        assert_eq!(task.registration_date.unwrap().to_rfc3339(), "2021-03-04T10:15:00.123456700+00:00");
+        assert!(task.registration_date_is_local);
+        assert!(task.fields().contains(&(TIME_BASIS.to_string(), LOCAL_SYSTEM_TIME.to_string())));
+        assert_eq!(task.author.as_deref(), Some("CORP\\mallory"));
+        assert_eq!(task.actions, vec!["C:\\ProgramData\\u.exe -k \"run\""]);
+        assert_eq!(
+            task.triggers,
+            vec!["Logon, for CORP\\alice", "Calendar at 2021-03-05T09:00:00, every 1 day(s), repeating every PT1H"]
+        );
+        assert_eq!(task.principal.as_deref(), Some("S-1-5-18 (HighestAvailable)"));
+        assert!(task.enabled && task.hidden);
+
+        assert!(is_task_xml(&data));
+        assert!(is_task_xml(b"<?xml version=\"1.0\"?>\n<!-- exported -->\n<Task version=\"1.4\">"));
+        assert!(!is_task_xml(b"<?xml version=\"1.0\"?>\n<project><Task/></project>"));
+        assert!(!is_task_xml(b"<Tasks></Tasks>"));
+    }
+
+    #[test]
+    fn test_parse_job() {
+        let job_string = |s: &str| {
+            let mut bytes = ((s.len() + 1) as u16).to_le_bytes().to_vec();
+            bytes.extend(utf16z(s));
+            bytes
+        };
+        let mut data = vec![0u8; JOB_FIXED_SIZE];
+        data[44..48].copy_from_slice(&0x0004_1300u32.to_le_bytes());
+        // Last run 2021-01-02 03:04:05
+        for (i, v) in [2021u16, 1, 6, 2, 3, 4, 5, 0].iter().enumerate() {
+            data[52 + i * 2..54 + i * 2].copy_from_slice(&v.to_le_bytes());
+        }
+        data.extend([1, 0]); // running instance count
+        let strings_offset = data.len() as u16;
+        data[20..22].copy_from_slice(&strings_offset.to_le_bytes());
+        for s in ["C:\\Windows\\evil.exe", "/q", "", "bob", "nightly"] {
+            data.extend(job_string(s));
+        }
+        data.extend([0, 0, 0, 0]); // user and reserved data
+        let triggers_offset = data.len() as u16;
+        data[22..24].copy_from_slice(&triggers_offset.to_le_bytes());
+        data.extend(1u16.to_le_bytes());
+        let mut trigger = vec![0u8; JOB_TRIGGER_SIZE];
+        for (offset, value) in [(4, 2021u16), (6, 1), (8, 1), (16, 22), (18, 30), (36, 2)] {
+            trigger[offset..offset + 2].copy_from_slice(&value.to_le_bytes());
+        }
+        trigger[32..36].copy_from_slice(&1u32.to_le_bytes());
+        data.extend(trigger);
+
+        let task = parse_task(&data, "Windows/Tasks/Nightly.job").unwrap();
+        assert_eq!(task.format, TaskFormat::Job);
+        assert_eq!(task.path, "\\Nightly");
+        assert_eq!(task.actions, vec!["C:\\Windows\\evil.exe /q"]);
+        assert_eq!(task.author.as_deref(), Some("bob"));
+        assert_eq!(task.description.as_deref(), Some("nightly"));
+        assert_eq!(task.triggers, vec!["Daily at 22:30 from 2021-01-01, every 2 day(s)"]);
+        assert_eq!(task.status.as_deref(), Some("Ready"));
+        assert_eq!(task.last_run.unwrap().to_rfc3339(), "2021-01-02T03:04:05+00:00");
+    }
+
+    #[test]
+    fn test_cache_actions_and_paths() {
+        let string = |s: &str| {
+            let bytes = utf16(s);
+            let mut data = (bytes.len() as u32).to_le_bytes().to_vec();
+            data.extend(bytes);
+            data
+        };
+        let mut data = 3u16.to_le_bytes().to_vec();
+        data.extend(string("Author"));
+        data.extend(ACTION_EXEC.to_le_bytes());
+        data.extend(string(""));
+        data.extend(string("cmd.exe"));
+        data.extend(string("/c whoami"));
+        data.extend(string(""));
+        data.extend([0, 0]);
+        assert_eq!(cache_actions(&data), vec!["cmd.exe /c whoami"]);
+
+        assert!(is_task_file("C/Windows/System32/Tasks/Microsoft/Windows/Defrag/ScheduledDefrag"));
+        assert!(is_task_file("Windows\\Tasks\\At1.job"));
+        assert!(!is_task_file("Windows/System32/config/SOFTWARE"));
+        assert_eq!(task_path("C/Windows/System32/Tasks/Microsoft/Windows/Defrag/ScheduledDefrag"), "\\Microsoft\\Windows\\Defrag\\ScheduledDefrag");
+    }
+}
@@ -6,11 +6,128 @@
 
 use crate::error::{Error, Result};
 use crate::property_store::PropertyStore;
+use crate::registry::{format_guid, read_u16, read_u32, read_u64, utf16_string};
 use byteorder::{LittleEndian, ReadBytesExt};
-use chrono::{DateTime, TimeZone, Utc};
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
 use serde::{Deserialize, Serialize};
 use std::io::{Cursor, Read, Seek, SeekFrom};
 
+/// CPL file shell item signature (0xFFFFFF38)
+const CPL_FILE_SIGNATURE: u32 = 0xFFFF_FF38;
+/// MTP storage shell item signature
+const MTP_STORAGE_SIGNATURE: u32 = 0x1031_2005;
+/// MTP file entry shell item signature
+const MTP_FILE_SIGNATURE: u32 = 0x0719_2006;
+/// Users property view shell item signature
+const USERS_PROPERTY_VIEW_SIGNATURE: u32 = 0x23FE_BBEE;
+/// Delegate item identifier {5E591A74-DF96-48D3-8D67-1733BCEE28BA}
+const DELEGATE_ITEM_GUID: [u8; 16] = [
+    0x74, 0x1A, 0x59, 0x5E, 0x96, 0xDF, 0xD3, 0x48, 0x8D, 0x67, 0x17, 0x33, 0xBC, 0xEE, 0x28, 0xBA,
+];
+
+/// Control Panel category names by identifier
+const CONTROL_PANEL_CATEGORIES: [&str; 12] = [
+    "All Control Panel Items",
+    "Appearance and Personalization",
+    "Hardware and Sound",
+    "Network and Internet",
+    "Sound, Speech and Audio Devices",
+    "System and Security",
+    "Clock, Language, and Region",
+    "Ease of Access",
+    "Programs",
+    "User Accounts",
+    "Security Center",
+    "Mobile PC",
+];
+
+/// Shell folder CLSIDs, known folder IDs and Control Panel items
+const KNOWN_FOLDERS: &[(&str, &str)] = &[
+    // Shell namespace roots
+    ("{20D04FE0-3AEA-1069-A2D8-08002B30309D}", "My Computer"),
+    ("{450D8FBA-AD25-11D0-98A8-0800361B1103}", "My Documents"),
+    ("{208D2C60-3AEA-1069-A2D7-08002B30309D}", "My Network Places"),
+    ("{F02C1A0D-BE21-4350-88B0-7367FC96EF3C}", "Network"),
+    ("{645FF040-5081-101B-9F08-00AA002F954E}", "Recycle Bin"),
+    ("{21EC2020-3AEA-1069-A2DD-08002B30309D}", "Control Panel"),
+    ("{26EE0668-A00A-44D7-9371-BEB064C98683}", "Control Panel"),
+    ("{59031A47-3F72-44A7-89C5-5595FE6B30EE}", "Users Files"),
+    ("{031E4825-7B94-4DC3-B131-E946B44C8DD5}", "Libraries"),
+    ("{679F85CB-0220-4080-B29B-5540CC05AAB6}", "Quick Access"),
+    ("{F874310E-B6B7-47DC-BC84-B9E6B38F5903}", "Home"),
+    ("{018D5C66-4533-4307-9B53-224DE2ED1FE6}", "OneDrive"),
+    ("{22877A6D-37A1-461A-91B0-DBDA5AAEBC99}", "Recent Places"),
+    ("{35786D3C-B075-49B9-88DD-029876E11C01}", "Portable Devices"),
+    ("{871C5380-42A0-1069-A2EA-08002B30309D}", "Internet Explorer"),
+    ("{B4FB3F98-C1EA-428D-A78A-D1F5659CBA93}", "HomeGroup"),
+    // This PC folders
+    ("{D3162B92-9365-467A-956B-92703ACA08AF}", "Documents"),
+    ("{088E3905-0323-4B02-9826-5D99428E115F}", "Downloads"),
+    ("{3DFDF296-DBEC-4FB4-81D1-6A3438BCF4DE}", "Music"),
+    ("{24AD3AD4-A569-4530-98E1-AB02F9417AA8}", "Pictures"),
+    ("{F86FA3AB-70D2-4FC7-9C99-FCBF05467F3A}", "Videos"),
+    ("{0DB7E03F-FC29-4DC6-9020-FF41B59E513A}", "3D Objects"),
+    // Known folder IDs (FOLDERID_*)
+    ("{B4BFCC3A-DB2C-424C-B029-7FE99A87C641}", "Desktop"),
+    ("{FDD39AD0-238F-46AF-ADB4-6C85480369C7}", "Documents"),
+    ("{374DE290-123F-4565-9164-39C4925E467B}", "Downloads"),
+    ("{4BD8D571-6D19-48D3-BE97-422220080E43}", "Music"),
+    ("{33E28130-4E1E-4676-835A-98395C3BC3BB}", "Pictures"),
+    ("{18989B1D-99B5-455B-841C-AB7C74E4DDFC}", "Videos"),
+    ("{5E6C858F-0E22-4760-9AFE-EA3317B67173}", "User Profile"),
+    ("{0762D272-C50A-4BB0-A382-697DCD729B80}", "Users"),
+    ("{DFDF76A2-C82A-4D63-906A-5644AC457385}", "Public"),
+    ("{3EB685DB-65F9-4CF6-A03A-E3EF65729F3D}", "AppData\\Roaming"),
+    ("{F1B32785-6FBA-4FCF-9D55-7B8E7F157091}", "AppData\\Local"),
+    ("{A520A1A4-1780-4FF6-BD18-167343C5AF16}", "AppData\\LocalLow"),
+    ("{62AB5D82-FDC1-4DC3-A9DD-070D1D495D97}", "ProgramData"),
+    ("{905E63B6-C1BF-494E-B29C-65B732D3D21A}", "Program Files"),
+    ("{6D809377-6AF0-444B-8957-A3773F02200E}", "Program Files"),
+    ("{F7F1ED05-9F6D-47A2-AAAE-29D317C6F066}", "Program Files\\Common Files"),
+    ("{7C5A40EF-A0FB-4BFC-874A-C0F2E0B9FA8E}", "Program Files (x86)"),
+    ("{F38BF404-1D43-42F2-9305-67DE0B28FC23}", "Windows"),
+    ("{1AC14E77-02E7-4E5D-B744-2EB1AE5198B7}", "System32"),
+    ("{D65231B0-B2F1-4857-A4CE-A8E7C6EA7D27}", "SysWOW64"),
+    ("{AE50C081-EBD2-438A-8655-8A092E34987A}", "Recent"),
+    ("{625B53C3-AB48-4EC1-BA1F-A1EF4146FC19}", "Start Menu"),
+    ("{A77F5D77-2E2B-44C3-A6A2-ABA601054A51}", "Programs"),
+    ("{B97D20BB-F46A-4C97-BA10-5E3608430854}", "Startup"),
+    ("{A4115719-D62E-491D-AA7C-E74B8BE3B067}", "Common Start Menu"),
+    ("{82A5EA35-D9CD-47C5-9629-E15D2F714E6E}", "Common Startup"),
+    ("{1777F761-68AD-4D8A-87BD-30B759FA33DD}", "Favorites"),
+    ("{BFB9D5E0-C6A9-404C-B2B2-AE6DB6AF4968}", "Links"),
+    ("{56784854-C6CB-462B-8169-88E350ACB882}", "Contacts"),
+    ("{4C5C32FF-BB9D-43B0-B5B4-2D72E54EAAA4}", "Saved Games"),
+    ("{7D1D3A04-DEBB-4115-95CF-2F29DA2920DA}", "Searches"),
+    ("{A63293E8-664E-48DB-A079-DF759E0509F7}", "Templates"),
+    ("{A52BBA46-E9E1-435F-B3D9-28DAA648C0F6}", "OneDrive"),
+    ("{2B0F765D-C0E9-4171-908E-08A611B84FF6}", "Cookies"),
+    ("{352481E8-33BE-4251-BA85-6007CAEDCF9D}", "Temporary Internet Files"),
+    ("{D9DC8A3B-B784-432E-A781-5A1130A75963}", "History"),
+    // Control Panel items
+    ("{7B81BE6A-CE2B-4676-A29E-EB907A5126C5}", "Programs and Features"),
+    ("{BB06C0E4-D293-4F75-8A90-CB05B6477EEE}", "System"),
+    ("{60632754-C523-4B62-B45C-4172DA012619}", "User Accounts"),
+    ("{8E908FC9-BECC-40F6-915B-F4CA0E70D03D}", "Network and Sharing Center"),
+    ("{025A5937-A6BE-4686-A844-36FE4BEC8B6D}", "Power Options"),
+    ("{D555645E-D4F8-4C29-A827-D93C859C4F2A}", "Ease of Access Center"),
+    ("{BB64F8A7-BEE7-4E1A-AB8D-7D8273F7FDB6}", "Action Center"),
+    ("{36EEF7DB-88AD-4E81-AD49-0E313F0C35F8}", "Windows Update"),
+    ("{4026492F-2F69-46B8-B9BF-5654FC07E423}", "Windows Firewall"),
+    ("{74246BFC-4C96-11D0-ABEF-0020AF6B0B7A}", "Device Manager"),
+    ("{A8A91A66-3A7D-4424-8D24-04E180695C7A}", "Devices and Printers"),
+    ("{D20EA4E1-3957-11D2-A40B-0C5020524153}", "Administrative Tools"),
+];
+
+/// Resolve a shell folder CLSID or known folder ID (with or without braces) to a name
+pub fn known_folder_name(guid: &str) -> Option<&'static str> {
+    let guid = guid.trim_matches(|c| c == '{' || c == '}');
+    KNOWN_FOLDERS
+        .iter()
+        .find(|(known, _)| known[1..known.len() - 1].eq_ignore_ascii_case(guid))
+        .map(|(_, name)| *name)
+}
+
 /// IDList (Item ID List) containing shell items
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ItemIdList {
@@ -54,11 +171,17 @@ pub enum ShellItemType {
     CompressedFolder,
     /// URI (web link)
     Uri,
-    /// Control Panel
+    /// Control Panel item (by CLSID) or CPL file
     ControlPanel,
+    /// Control Panel category
+    ControlPanelCategory,
+    /// Delegate item wrapping a file entry (users files folder, libraries)
+    Delegate,
+    /// Users property view item carrying a property store
+    UsersPropertyView,
     /// Extension block
     Extension,
-    /// MTP (Media Transfer Protocol) item
+    /// MTP (Media Transfer Protocol) storage or file entry
     Mtp,
     /// Unknown/unsupported type
     Unknown(u8),
@@ -132,6 +255,7 @@ pub enum ExtensionData {
         write_time: Option<DateTime<Utc>>,
         file_size_64: Option<u64>,
         localized_name: Option<String>,
+        file_reference: Option<u64>,
     },
     /// BEEF0005 - Extended directory information
     ExtendedDirInfo {
@@ -190,19 +314,27 @@ impl ItemIdList {
             
             // Build path component
             if let Some(name) = &item.parsed_data.name {
+                let name = name.trim_end_matches('\\');
                 if !name.is_empty() && name != "Desktop" {
-                    path_components.push(name.clone());
+                    let is_root = matches!(item.item_type, ShellItemType::RootFolder);
+                    path_components.push((is_root, name.to_string()));
                 }
             }
             
             items.push(item);
         }
         
+        // Root folders (My Computer, etc.) only name the path when nothing follows them
+        if path_components.iter().any(|(is_root, _)| !is_root) {
+            path_components.retain(|(is_root, _)| !is_root);
+        }
+        
         // Reconstruct full path
         let full_path = if path_components.is_empty() {
             None
         } else {
-            Some(path_components.join("\\"))
+            let names: Vec<String> = path_components.into_iter().map(|(_, name)| name).collect();
+            Some(names.join("\\"))
         };
         
         Ok(ItemIdList {
@@ -211,6 +343,23 @@ impl ItemIdList {
             full_path,
         })
     }
+    
+    /// Parse a bare PIDL (shell items without the leading IDList size), as stored in registry values
+    pub fn parse_pidl(data: &[u8]) -> Result<Self> {
+        let mut idlist_data = Vec::with_capacity(data.len() + 2);
+        idlist_data.extend_from_slice(&(data.len().min(u16::MAX as usize) as u16).to_le_bytes());
+        idlist_data.extend_from_slice(data);
+        Self::parse(&idlist_data)
+    }
+    
+    /// Timestamp properties from the property stores embedded in the items
+    pub fn property_timestamps(&self) -> Vec<(String, DateTime<Utc>)> {
+        self.items
+            .iter()
+            .filter_map(|item| item.property_store.as_ref())
+            .flat_map(|store| store.timestamps())
+            .collect()
+    }
 }
 
 impl ShellItem {
@@ -220,19 +369,24 @@ impl ShellItem {
             return Err(Error::ParseError("Empty shell item data".to_string()));
         }
         
-        // Determine item type from first byte
-        let type_indicator = data[0];
-        let item_type = ShellItemType::from_type_indicator(type_indicator);
+        // Determine item type from the class type indicator and, where ambiguous, a signature
+        let item_type = ShellItemType::classify(data);
         
         // Parse based on type
         let parsed_data = Self::parse_item_data(&item_type, data)?;
         
+        // Users property view items describe themselves with a property store
+        let property_store = match item_type {
+            ShellItemType::UsersPropertyView => Self::users_property_view_store(data),
+            _ => None,
+        };
+        
         Ok(ShellItem {
             size,
             item_type,
             raw_data: data.to_vec(),
             parsed_data,
-            property_store: None, // Will be populated from extension blocks
+            property_store,
         })
     }
     
@@ -251,6 +405,27 @@ impl ShellItem {
             ShellItemType::Network => {
                 Self::parse_network_item(data)
             },
+            ShellItemType::CompressedFolder => {
+                Self::parse_compressed_folder_item(data)
+            },
+            ShellItemType::Uri => {
+                Self::parse_uri_item(data)
+            },
+            ShellItemType::ControlPanel => {
+                Self::parse_control_panel_item(data)
+            },
+            ShellItemType::ControlPanelCategory => {
+                Self::parse_control_panel_category_item(data)
+            },
+            ShellItemType::Delegate => {
+                Self::parse_delegate_item(data)
+            },
+            ShellItemType::UsersPropertyView => {
+                Self::parse_users_property_view_item(data)
+            },
+            ShellItemType::Mtp => {
+                Self::parse_mtp_item(data)
+            },
             _ => {
                 // Default parsing for unknown types
                 Ok(ShellItemData {
@@ -270,98 +445,75 @@ impl ShellItem {
     
     /// Parse file/directory entry shell item
     fn parse_file_entry_item(data: &[u8]) -> Result<ShellItemData> {
-        if data.len() < 14 {
+        if data.len() < 12 {
             return Err(Error::ParseError("File entry shell item too small".to_string()));
         }
         
+        let type_indicator = data[0];
         let mut cursor = Cursor::new(data);
         
-        // Skip type indicator
-        cursor.read_u8()?;
+        // Skip type indicator and unknown byte
+        cursor.seek(SeekFrom::Start(2))?;
         
         // Read file entry structure
         let file_size_32 = cursor.read_u32::<LittleEndian>()?;
         let dos_date = cursor.read_u16::<LittleEndian>()?;
         let dos_time = cursor.read_u16::<LittleEndian>()?;
-        let attributes = cursor.read_u32::<LittleEndian>()?;
+        let attributes = cursor.read_u16::<LittleEndian>()? as u32;
         
-        // Read short name (null-terminated)
-        let mut short_name = String::new();
-        while cursor.position() < data.len() as u64 {
-            let b = cursor.read_u8()?;
-            if b == 0 {
-                break;
+        // Primary name is UTF-16 when flag 0x04 is set, otherwise ANSI (null-terminated)
+        let short_name = if type_indicator & 0x04 != 0 {
+            ExtensionBlock::read_unicode_string(&mut cursor)?.unwrap_or_default()
+        } else {
+            let mut name = String::new();
+            while cursor.position() < data.len() as u64 {
+                let b = cursor.read_u8()?;
+                if b == 0 {
+                    break;
+                }
+                name.push(b as char);
             }
-            short_name.push(b as char);
-        }
+            name
+        };
         
         // Parse DOS date/time
-        let mut modified = Self::dos_datetime_to_utc(dos_date, dos_time);
+        let modified = Self::dos_datetime_to_utc(dos_date, dos_time);
         
-        // Look for extension blocks
+        // Extension blocks follow the (padded) primary name
         let mut extension_blocks = Vec::new();
         let mut long_name = None;
         let mut created = None;
         let mut accessed = None;
-        let mut file_size_64 = None;
+        let mut file_reference = None;
         
-        // Parse extension blocks if present
-        while cursor.position() + 4 <= data.len() as u64 {
-            let pos = cursor.position();
+        if let Some(start) = Self::find_extension_block(data, cursor.position() as usize) {
+            cursor.seek(SeekFrom::Start(start as u64))?;
             
-            // Try to read extension block header
-            if let Ok(ext_size) = cursor.read_u16::<LittleEndian>() {
-                if ext_size == 0 || ext_size < 4 {
+            while cursor.position() + 8 <= data.len() as u64 {
+                let pos = cursor.position() as usize;
+                let ext_size = u16::from_le_bytes([data[pos], data[pos + 1]]) as usize;
+                if ext_size < 8 || pos + ext_size > data.len() {
                     break;
                 }
                 
-                if let Ok(version) = cursor.read_u16::<LittleEndian>() {
-                    if pos + ext_size as u64 <= data.len() as u64 {
-                        // We have a valid extension block
-                        cursor.seek(SeekFrom::Start(pos))?;
-                        if let Ok(block) = ExtensionBlock::parse(&mut cursor) {
-                            // Extract data from specific extension types
-                            match &block.parsed_data {
-                                ExtensionData::ExtendedFileInfo { 
-                                    long_name: ln, 
-                                    creation_time: ct, 
-                                    access_time: at, 
-                                    write_time: wt,
-                                    file_size_64: fs,
-                                    ..
-                                } => {
-                                    if ln.is_some() { long_name = ln.clone(); }
-                                    if ct.is_some() { created = *ct; }
-                                    if at.is_some() { accessed = *at; }
-                                    if wt.is_some() { modified = *wt; } // Override DOS time with FILETIME
-                                    if fs.is_some() { file_size_64 = *fs; }
-                                },
-                                ExtensionData::ExtendedDirInfo { 
-                                    long_name: ln, 
-                                    creation_time: ct, 
-                                    access_time: at, 
-                                    write_time: wt,
-                                    ..
-                                } => {
-                                    if ln.is_some() { long_name = ln.clone(); }
-                                    if ct.is_some() { created = *ct; }
-                                    if at.is_some() { accessed = *at; }
-                                    if wt.is_some() { modified = *wt; }
-                                },
-                                _ => {}
-                            }
-                            extension_blocks.push(block);
-                        } else {
-                            break;
+                match ExtensionBlock::parse(&mut cursor) {
+                    Ok(block) => {
+                        if let ExtensionData::ExtendedFileInfo {
+                            long_name: ln,
+                            creation_time: ct,
+                            access_time: at,
+                            file_reference: fr,
+                            ..
+                        } = &block.parsed_data {
+                            if ln.is_some() { long_name = ln.clone(); }
+                            if ct.is_some() { created = *ct; }
+                            if at.is_some() { accessed = *at; }
+                            if fr.is_some() { file_reference = *fr; }
                         }
-                    } else {
-                        break;
-                    }
-                } else {
-                    break;
+                        extension_blocks.push(block);
+                    },
+                    Err(_) => break,
                 }
-            } else {
-                break;
             }
         }
         
@@ -369,38 +521,49 @@ impl ShellItem {
         let name = long_name.as_ref().unwrap_or(&short_name).clone();
         let final_long_name = if long_name.as_ref() != Some(&short_name) { long_name } else { None };
         
+        let mut metadata = vec![
+            ("short_name".to_string(), short_name),
+            ("extension_blocks".to_string(), extension_blocks.len().to_string()),
+        ];
+        if let Some(reference) = file_reference {
+            metadata.push(("mft_entry".to_string(), (reference & 0xFFFF_FFFF_FFFF).to_string()));
+            metadata.push(("mft_sequence".to_string(), (reference >> 48).to_string()));
+        }
+        
         Ok(ShellItemData {
             name: if name.is_empty() { None } else { Some(name) },
             long_name: final_long_name,
             created,
             modified,
             accessed,
-            file_size: file_size_64.or(Some(file_size_32 as u64)),
+            file_size: Some(file_size_32 as u64),
             attributes: Some(attributes),
             clsid: None,
-            metadata: vec![
-                ("short_name".to_string(), short_name),
-                ("extension_blocks".to_string(), extension_blocks.len().to_string()),
-            ],
+            metadata,
+        })
+    }
+    
+    /// Locate the first BEEFxxxx extension block at or after `from`
+    fn find_extension_block(data: &[u8], from: usize) -> Option<usize> {
+        (from..data.len().saturating_sub(7)).find(|&pos| {
+            let size = u16::from_le_bytes([data[pos], data[pos + 1]]) as usize;
+            data[pos + 6] == 0xEF && data[pos + 7] == 0xBE && size >= 8 && pos + size <= data.len()
         })
     }
     
     /// Parse volume shell item
     fn parse_volume_item(data: &[u8]) -> Result<ShellItemData> {
-        if data.len() < 20 {
-            return Ok(ShellItemData::default());
-        }
-        
-        // Volume shell items have various formats
-        // This is a simplified parser
-        let name = if data.len() > 20 {
-            String::from_utf8_lossy(&data[20..]).trim_end_matches('\0').to_string()
+        // Volume items with flag 0x01 carry the drive letter (e.g. "C:\") as an ANSI string
+        let name = if data[0] & 0x01 != 0 && data.len() > 1 {
+            let end = data[1..].iter().position(|&b| b == 0).map(|p| p + 1).unwrap_or(data.len());
+            let name = String::from_utf8_lossy(&data[1..end]).to_string();
+            if name.is_empty() { None } else { Some(name) }
         } else {
-            "Drive".to_string()
+            None
         };
         
         Ok(ShellItemData {
-            name: Some(name),
+            name,
             long_name: None,
             created: None,
             modified: None,
@@ -414,33 +577,25 @@ impl ShellItem {
     
     /// Parse root folder shell item
     fn parse_root_folder_item(data: &[u8]) -> Result<ShellItemData> {
-        if data.len() >= 16 {
-            // Root folder items often contain CLSIDs
-            let mut clsid = [0u8; 16];
-            if data.len() >= 17 {
-                clsid.copy_from_slice(&data[1..17]);
-            }
-            
-            let name = match &clsid {
-                &[0x20, 0xd0, 0x4f, 0xe0, 0x3a, 0xea, 0x10, 0x69, 0xa2, 0xd8, 0x08, 0x00, 0x2b, 0x30, 0x30, 0x9d] => "My Computer",
-                &[0x21, 0xec, 0x20, 0x20, 0xea, 0x3a, 0x10, 0x69, 0xa2, 0xdd, 0x08, 0x00, 0x2b, 0x30, 0x30, 0x9d] => "Control Panel",
-                _ => "Desktop",
-            };
-            
-            Ok(ShellItemData {
-                name: Some(name.to_string()),
-                long_name: None,
-                created: None,
-                modified: None,
-                accessed: None,
-                file_size: None,
-                attributes: None,
-                clsid: Some(clsid),
-                metadata: vec![("type".to_string(), "RootFolder".to_string())],
-            })
-        } else {
-            Ok(ShellItemData::default())
-        }
+        // Sort index followed by the shell folder CLSID
+        let Some(guid_bytes) = data.get(2..18) else {
+            return Ok(ShellItemData::default());
+        };
+        let mut clsid = [0u8; 16];
+        clsid.copy_from_slice(guid_bytes);
+        let guid = format_guid(guid_bytes);
+        let name = known_folder_name(&guid).map(str::to_string).unwrap_or_else(|| guid.clone());
+        
+        Ok(ShellItemData {
+            name: Some(name),
+            clsid: Some(clsid),
+            metadata: vec![
+                ("type".to_string(), "RootFolder".to_string()),
+                ("guid".to_string(), guid),
+                ("sort_index".to_string(), data[1].to_string()),
+            ],
+            ..ShellItemData::default()
+        })
     }
     
     /// Parse network shell item
@@ -467,6 +622,219 @@ impl ShellItem {
         })
     }
     
+    /// Parse compressed (zip) folder shell item
+    fn parse_compressed_folder_item(data: &[u8]) -> Result<ShellItemData> {
+        // The last modification time is stored as a UTF-16 "MM/DD/YYYY  HH:MM:SS" string,
+        // followed by the entry name and its path within the archive
+        let modified = data.get(0x22..0x52).map(utf16_string).and_then(|text| Self::parse_zip_time(&text));
+        let mut strings = Self::utf16_strings(data.get(0x5A..).unwrap_or(&[]));
+        let name = strings.next();
+        
+        let mut metadata = vec![("type".to_string(), "CompressedFolder".to_string())];
+        if let Some(path) = strings.next() {
+            metadata.push(("archive_path".to_string(), path));
+        }
+        
+        Ok(ShellItemData {
+            name,
+            modified,
+            metadata,
+            ..ShellItemData::default()
+        })
+    }
+    
+    /// Parse a zip folder timestamp string
+    fn parse_zip_time(text: &str) -> Option<DateTime<Utc>> {
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        ["%m/%d/%Y %H:%M:%S", "%m/%d/%Y %H:%M"]
+            .iter()
+            .find_map(|format| NaiveDateTime::parse_from_str(&text, format).ok())
+            .map(|naive| naive.and_utc())
+    }
+    
+    /// Parse URI shell item
+    fn parse_uri_item(data: &[u8]) -> Result<ShellItemData> {
+        let flags = data.get(1).copied().unwrap_or(0);
+        let data_size = read_u16(data, 2).unwrap_or(0) as usize;
+        
+        // Optional data block holds the connection FILETIME; the URI follows it
+        let created = if data_size >= 16 {
+            read_u64(data, 12).and_then(ExtensionBlock::filetime_to_datetime)
+        } else {
+            None
+        };
+        let uri_data = data.get(4 + data_size..).unwrap_or(&[]);
+        let uri = if flags & 0x80 != 0 {
+            utf16_string(uri_data)
+        } else {
+            Self::ansi_string(uri_data)
+        };
+        
+        Ok(ShellItemData {
+            name: if uri.is_empty() { None } else { Some(uri) },
+            created,
+            metadata: vec![("type".to_string(), "Uri".to_string())],
+            ..ShellItemData::default()
+        })
+    }
+    
+    /// Parse Control Panel item (CLSID) or CPL file shell item
+    fn parse_control_panel_item(data: &[u8]) -> Result<ShellItemData> {
+        if read_u32(data, 2) == Some(CPL_FILE_SIGNATURE) {
+            // CPL file path followed by its display name and comments
+            let mut strings = Self::utf16_strings(data.get(20..).unwrap_or(&[]));
+            let path = strings.next();
+            let name = strings.next().or_else(|| path.clone());
+            let mut metadata = vec![("type".to_string(), "ControlPanel".to_string())];
+            if let Some(path) = path {
+                metadata.push(("cpl_file".to_string(), path));
+            }
+            return Ok(ShellItemData {
+                name,
+                metadata,
+                ..ShellItemData::default()
+            });
+        }
+        
+        let Some(guid_bytes) = data.get(12..28) else {
+            return Ok(ShellItemData::default());
+        };
+        let mut clsid = [0u8; 16];
+        clsid.copy_from_slice(guid_bytes);
+        let guid = format_guid(guid_bytes);
+        
+        Ok(ShellItemData {
+            name: Some(known_folder_name(&guid).map(str::to_string).unwrap_or_else(|| guid.clone())),
+            clsid: Some(clsid),
+            metadata: vec![("type".to_string(), "ControlPanel".to_string()), ("guid".to_string(), guid)],
+            ..ShellItemData::default()
+        })
+    }
+    
+    /// Parse Control Panel category shell item
+    fn parse_control_panel_category_item(data: &[u8]) -> Result<ShellItemData> {
+        let category = read_u32(data, 6).unwrap_or(u32::MAX);
+        let name = CONTROL_PANEL_CATEGORIES
+            .get(category as usize)
+            .map(|name| name.to_string())
+            .unwrap_or_else(|| format!("Control Panel category {}", category));
+        
+        Ok(ShellItemData {
+            name: Some(name),
+            metadata: vec![
+                ("type".to_string(), "ControlPanelCategory".to_string()),
+                ("category".to_string(), category.to_string()),
+            ],
+            ..ShellItemData::default()
+        })
+    }
+    
+    /// Parse delegate shell item ("CFSF" wrapper around a file entry)
+    fn parse_delegate_item(data: &[u8]) -> Result<ShellItemData> {
+        // The wrapped file entry starts after its own size field; extension blocks follow the delegate GUIDs
+        let Some(inner) = data.get(10..).filter(|inner| inner.len() >= 12) else {
+            return Ok(ShellItemData::default());
+        };
+        let mut parsed = Self::parse_file_entry_item(inner)?;
+        
+        parsed.metadata.insert(0, ("type".to_string(), "Delegate".to_string()));
+        let delegate_at = data.windows(16).position(|window| window == DELEGATE_ITEM_GUID);
+        if let Some(class) = delegate_at.and_then(|at| data.get(at + 16..at + 32)) {
+            let guid = format_guid(class);
+            if let Some(name) = known_folder_name(&guid) {
+                parsed.metadata.push(("delegate_folder".to_string(), name.to_string()));
+            }
+            parsed.metadata.push(("delegate_class".to_string(), guid));
+        }
+        Ok(parsed)
+    }
+    
+    /// Property store embedded in a users property view item
+    fn users_property_view_store(data: &[u8]) -> Option<PropertyStore> {
+        let size = read_u16(data, 8)? as usize;
+        PropertyStore::parse_all(data.get(12..12 + size)?).into_iter().next()
+    }
+    
+    /// Parse users property view shell item
+    fn parse_users_property_view_item(data: &[u8]) -> Result<ShellItemData> {
+        let store = Self::users_property_view_store(data);
+        let property = |key: &str| {
+            store.as_ref()
+                .and_then(|store| store.properties.get(key))
+                .map(|value| value.parsed_value.to_string())
+                .filter(|value| !value.is_empty())
+        };
+        
+        // System.ItemNameDisplay, falling back to System.ParsingName
+        let name = property("B725F130-47EF-101A-A5F1-02608C9EEBAC\\10")
+            .or_else(|| property("28636AA6-953D-11D2-B5D6-00C04FD918D0\\24"));
+        
+        Ok(ShellItemData {
+            name,
+            metadata: vec![
+                ("type".to_string(), "UsersPropertyView".to_string()),
+                ("properties".to_string(), store.map_or(0, |store| store.properties.len()).to_string()),
+            ],
+            ..ShellItemData::default()
+        })
+    }
+    
+    /// Parse MTP storage or file entry shell item
+    fn parse_mtp_item(data: &[u8]) -> Result<ShellItemData> {
+        match read_u32(data, 4) {
+            Some(MTP_STORAGE_SIGNATURE) => {
+                // Storage name, storage identifier and file system name
+                let mut strings = Self::utf16_strings(data.get(52..).unwrap_or(&[]));
+                let name = strings.next();
+                let mut metadata = vec![("type".to_string(), "MtpStorage".to_string())];
+                if let Some(identifier) = strings.next() {
+                    metadata.push(("storage_id".to_string(), identifier));
+                }
+                if let Some(file_system) = strings.next() {
+                    metadata.push(("file_system".to_string(), file_system));
+                }
+                Ok(ShellItemData {
+                    name,
+                    metadata,
+                    ..ShellItemData::default()
+                })
+            }
+            _ => {
+                // File entry: creation and modification FILETIMEs, then the name and object identifier
+                let mut strings = Self::utf16_strings(data.get(72..).unwrap_or(&[]));
+                let name = strings.next();
+                let mut metadata = vec![("type".to_string(), "MtpFile".to_string())];
+                if let Some(identifier) = strings.next() {
+                    metadata.push(("object_id".to_string(), identifier));
+                }
+                Ok(ShellItemData {
+                    name,
+                    created: read_u64(data, 24).and_then(ExtensionBlock::filetime_to_datetime),
+                    modified: read_u64(data, 32).and_then(ExtensionBlock::filetime_to_datetime),
+                    metadata,
+                    ..ShellItemData::default()
+                })
+            }
+        }
+    }
+    
+    /// Split consecutive NUL-terminated UTF-16 strings, skipping empty ones
+    fn utf16_strings(data: &[u8]) -> std::vec::IntoIter<String> {
+        let chars: Vec<u16> = data.chunks_exact(2).map(|chunk| u16::from_le_bytes([chunk[0], chunk[1]])).collect();
+        chars
+            .split(|&c| c == 0)
+            .filter(|s| !s.is_empty())
+            .map(String::from_utf16_lossy)
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+    
+    /// Decode a NUL-terminated ANSI string
+    fn ansi_string(data: &[u8]) -> String {
+        let end = data.iter().position(|&b| b == 0).unwrap_or(data.len());
+        String::from_utf8_lossy(&data[..end]).to_string()
+    }
+    
     /// Convert DOS date and time to UTC DateTime
     fn dos_datetime_to_utc(dos_date: u16, dos_time: u16) -> Option<DateTime<Utc>> {
         if dos_date == 0 && dos_time == 0 {
@@ -496,18 +864,33 @@ impl ShellItemType {
     /// Determine shell item type from type indicator
     fn from_type_indicator(type_indicator: u8) -> Self {
         match type_indicator {
+            0x01 => ShellItemType::ControlPanelCategory,
             0x1F => ShellItemType::RootFolder,
-            0x2F => ShellItemType::Volume,
+            0x20..=0x2F => ShellItemType::Volume,
+            0x30..=0x3F if type_indicator & 0x01 != 0 => ShellItemType::Directory,
             0x30..=0x3F => ShellItemType::File,
-            0x20..=0x2F => ShellItemType::Directory, 
             0x40..=0x4F => ShellItemType::Network,
             0x52 => ShellItemType::CompressedFolder,
             0x61 => ShellItemType::Uri,
             0x71 => ShellItemType::ControlPanel,
-            0x74 => ShellItemType::Mtp,
+            0x74 => ShellItemType::Delegate,
             _ => ShellItemType::Unknown(type_indicator),
         }
     }
+    
+    /// Determine shell item type, using signatures where the type indicator is ambiguous
+    fn classify(data: &[u8]) -> Self {
+        let type_indicator = data[0];
+        if read_u32(data, 2) == Some(CPL_FILE_SIGNATURE) {
+            return ShellItemType::ControlPanel;
+        }
+        match (type_indicator, read_u32(data, 4)) {
+            (0x00, Some(MTP_STORAGE_SIGNATURE | MTP_FILE_SIGNATURE)) => ShellItemType::Mtp,
+            (0x00 | 0x2E, Some(USERS_PROPERTY_VIEW_SIGNATURE)) => ShellItemType::UsersPropertyView,
+            (0x74, _) if data.get(4..8) != Some(b"CFSF") => ShellItemType::Unknown(type_indicator),
+            _ => Self::from_type_indicator(type_indicator),
+        }
+    }
 }
 
 impl ExtensionBlock {
@@ -540,7 +923,7 @@ impl ExtensionBlock {
     /// Parse extension block data based on signature
     fn parse_extension_data(signature: u32, version: u16, data: &[u8]) -> Result<ExtensionData> {
         match signature {
-            0xBEEF0004 => Self::parse_beef0004(data), // Extended file info
+            0xBEEF0004 => Self::parse_beef0004(version, data), // Extended file info
             0xBEEF0005 => Self::parse_beef0005(data), // Extended directory info  
             0xBEEF0006 => Self::parse_beef0006(data), // Property store
             _ => Ok(ExtensionData::Unknown(data.to_vec())),
@@ -548,40 +931,59 @@ impl ExtensionBlock {
     }
     
     /// Parse BEEF0004 extension (Extended file information)
-    fn parse_beef0004(data: &[u8]) -> Result<ExtensionData> {
-        if data.len() < 26 {
+    fn parse_beef0004(version: u16, data: &[u8]) -> Result<ExtensionData> {
+        if data.len() < 12 {
             return Ok(ExtensionData::Unknown(data.to_vec()));
         }
         
         let mut cursor = Cursor::new(data);
         
-        // Skip unknown fields
-        cursor.read_u32::<LittleEndian>()?; // Unknown
-        cursor.read_u16::<LittleEndian>()?; // Unknown
-        cursor.read_u16::<LittleEndian>()?; // Flags
-        
-        let file_size_64 = cursor.read_u64::<LittleEndian>()?;
-        let file_size_64 = if file_size_64 == 0 { None } else { Some(file_size_64) };
+        // Creation and last access times are FAT date/time values
+        let creation_date = cursor.read_u16::<LittleEndian>()?;
+        let creation_time = cursor.read_u16::<LittleEndian>()?;
+        let access_date = cursor.read_u16::<LittleEndian>()?;
+        let access_time = cursor.read_u16::<LittleEndian>()?;
+        cursor.read_u16::<LittleEndian>()?; // Identifier
         
-        // Read FILETIME timestamps
-        let creation_time = Self::read_filetime(&mut cursor)?;
-        let access_time = Self::read_filetime(&mut cursor)?;
-        let write_time = Self::read_filetime(&mut cursor)?;
+        let mut file_reference = None;
+        if version >= 7 {
+            cursor.read_u16::<LittleEndian>()?; // Unknown
+            let reference = cursor.read_u64::<LittleEndian>()?;
+            if reference != 0 {
+                file_reference = Some(reference);
+            }
+            cursor.read_u64::<LittleEndian>()?; // Unknown
+        }
+        if version >= 3 {
+            cursor.read_u16::<LittleEndian>()?; // Long string size
+        }
+        if version >= 9 {
+            cursor.read_u32::<LittleEndian>()?; // Unknown
+        }
+        if version >= 8 {
+            cursor.read_u32::<LittleEndian>()?; // Unknown
+        }
         
-        // Try to read long name if present
         let long_name = if cursor.position() < data.len() as u64 {
             Self::read_unicode_string(&mut cursor)?
         } else {
             None
         };
         
+        let localized_name = if version >= 7 && cursor.position() + 2 < data.len() as u64 {
+            Self::read_unicode_string(&mut cursor)?
+        } else {
+            None
+        };
+        
         Ok(ExtensionData::ExtendedFileInfo {
             long_name,
-            creation_time,
-            access_time,
-            write_time,
-            file_size_64,
-            localized_name: None,
+            creation_time: ShellItem::dos_datetime_to_utc(creation_date, creation_time),
+            access_time: ShellItem::dos_datetime_to_utc(access_date, access_time),
+            write_time: None,
+            file_size_64: None,
+            localized_name,
+            file_reference,
         })
     }
     
@@ -687,6 +1089,8 @@ impl Default for ShellItemData {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::registry::test_hive::{utf16, utf16z};
+    use chrono::{Datelike, Timelike};
     
     #[test]
     fn test_dos_datetime_conversion() {
@@ -713,4 +1117,138 @@ mod tests {
         assert!(matches!(ShellItemType::from_type_indicator(0x32), ShellItemType::File));
         assert!(matches!(ShellItemType::from_type_indicator(0x99), ShellItemType::Unknown(0x99)));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_pidl_with_volume_and_file_entry() {
+        let mut pidl = Vec::new();
+        
+        // Volume item "C:\\"
+        let mut volume = vec![0x2F];
+        volume.extend_from_slice(b"C:\\");
+        volume.resize(23, 0);
+        pidl.extend_from_slice(&((volume.len() + 2) as u16).to_le_bytes());
+        pidl.extend_from_slice(&volume);
+        
+        // File entry with ANSI short name and a BEEF0004 (v9) long name
+        let mut file = vec![0x32, 0x00];
+        file.extend_from_slice(&1234u32.to_le_bytes());
+        file.extend_from_slice(&[0x21, 0x50, 0x00, 0x60]); // 2020-01-01 12:00:00
+        file.extend_from_slice(&0x20u16.to_le_bytes());
+        file.extend_from_slice(b"REPORT~1.DOC\0\0");
+        let long_name = utf16z("report final.docx");
+        let mut beef = Vec::new();
+        beef.extend_from_slice(&0u16.to_le_bytes()); // size placeholder
+        beef.extend_from_slice(&9u16.to_le_bytes());
+        beef.extend_from_slice(&0xBEEF0004u32.to_le_bytes());
+        beef.extend_from_slice(&[0x21, 0x50, 0x00, 0x60, 0x21, 0x50, 0x00, 0x60]);
+        beef.extend_from_slice(&0x2Eu16.to_le_bytes());
+        beef.extend_from_slice(&0u16.to_le_bytes());
+        beef.extend_from_slice(&((3u64 << 48) | 42).to_le_bytes());
+        beef.extend_from_slice(&[0u8; 8]);
+        beef.extend_from_slice(&0u16.to_le_bytes());
+        beef.extend_from_slice(&[0u8; 8]);
+        beef.extend_from_slice(&long_name);
+        beef.extend_from_slice(&0u16.to_le_bytes());
+        let beef_len = beef.len() as u16;
+        beef[0..2].copy_from_slice(&beef_len.to_le_bytes());
+        file.extend_from_slice(&beef);
+        pidl.extend_from_slice(&((file.len() + 2) as u16).to_le_bytes());
+        pidl.extend_from_slice(&file);
+        pidl.extend_from_slice(&[0, 0]);
+        
+        let idlist = ItemIdList::parse_pidl(&pidl).unwrap();
+        assert_eq!(idlist.full_path.as_deref(), Some("C:\\report final.docx"));
+        
+        let data = &idlist.items[1].parsed_data;
+        assert_eq!(data.file_size, Some(1234));
+        assert_eq!(data.modified.unwrap().year(), 2020);
+        assert!(data.metadata.contains(&("mft_entry".to_string(), "42".to_string())));
+    }
+
+    #[test]
+    fn test_root_folder_and_known_folders() {
+        let mut pidl = Vec::new();
+        let mut root = vec![0x1F, 0x50];
+        root.extend_from_slice(&[0xE0, 0x4F, 0xD0, 0x20, 0xEA, 0x3A, 0x69, 0x10, 0xA2, 0xD8, 0x08, 0x00, 0x2B, 0x30, 0x30, 0x9D]);
+        pidl.extend_from_slice(&((root.len() + 2) as u16).to_le_bytes());
+        pidl.extend_from_slice(&root);
+        pidl.extend_from_slice(&[0, 0]);
+
+        let idlist = ItemIdList::parse_pidl(&pidl).unwrap();
+        assert_eq!(idlist.items[0].parsed_data.name.as_deref(), Some("My Computer"));
+        assert_eq!(idlist.full_path.as_deref(), Some("My Computer"));
+        assert_eq!(known_folder_name("fdd39ad0-238f-46af-adb4-6c85480369c7"), Some("Documents"));
+        assert_eq!(known_folder_name("{00000000-0000-0000-0000-000000000000}"), None);
+    }
+
+    #[test]
+    fn test_uri_control_panel_and_zip_items() {
+        let mut uri = vec![0x61, 0x80];
+        uri.extend_from_slice(&16u16.to_le_bytes());
+        uri.extend_from_slice(&[0u8; 8]);
+        uri.extend_from_slice(&132223104000000000u64.to_le_bytes()); // 2020-01-01
+        uri.extend(utf16z("https://example.com/share"));
+        let item = ShellItem::parse((uri.len() + 2) as u16, &uri).unwrap();
+        assert!(matches!(item.item_type, ShellItemType::Uri));
+        assert_eq!(item.parsed_data.name.as_deref(), Some("https://example.com/share"));
+        assert_eq!(item.parsed_data.created.unwrap().year(), 2020);
+
+        let mut category = vec![0x01, 0x00];
+        category.extend_from_slice(&0x39DE2184u32.to_le_bytes());
+        category.extend_from_slice(&5u32.to_le_bytes());
+        let item = ShellItem::parse((category.len() + 2) as u16, &category).unwrap();
+        assert_eq!(item.parsed_data.name.as_deref(), Some("System and Security"));
+
+        let mut zip = vec![0u8; 0x5A];
+        zip[0] = 0x52;
+        let date = utf16("01/15/2020  10:30:00");
+        zip[0x22..0x22 + date.len()].copy_from_slice(&date);
+        zip.extend(utf16("report.docx\0docs/report.docx\0"));
+        let item = ShellItem::parse((zip.len() + 2) as u16, &zip).unwrap();
+        assert!(matches!(item.item_type, ShellItemType::CompressedFolder));
+        assert_eq!(item.parsed_data.name.as_deref(), Some("report.docx"));
+        assert_eq!(item.parsed_data.modified.unwrap().hour(), 10);
+        assert!(item.parsed_data.accessed.is_none());
+        assert!(item.parsed_data.metadata.contains(&("archive_path".to_string(), "docs/report.docx".to_string())));
+    }
+
+    #[test]
+    fn test_delegate_and_mtp_items() {
+        let mut inner = vec![0x31, 0x00];
+        inner.extend_from_slice(&0u32.to_le_bytes());
+        inner.extend_from_slice(&[0x21, 0x50, 0x00, 0x60]);
+        inner.extend_from_slice(&0x10u16.to_le_bytes());
+        inner.extend_from_slice(b"Projects\0\0");
+        let mut delegate = vec![0x74, 0x00, 0x00, 0x00];
+        delegate.extend_from_slice(b"CFSF");
+        delegate.extend_from_slice(&((inner.len() + 2) as u16).to_le_bytes());
+        delegate.extend(inner);
+        delegate.extend_from_slice(&DELEGATE_ITEM_GUID);
+        delegate.extend_from_slice(&[0x47, 0x1A, 0x03, 0x59, 0x72, 0x3F, 0xA7, 0x44, 0x89, 0xC5, 0x55, 0x95, 0xFE, 0x6B, 0x30, 0xEE]);
+        let item = ShellItem::parse((delegate.len() + 2) as u16, &delegate).unwrap();
+        assert!(matches!(item.item_type, ShellItemType::Delegate));
+        assert_eq!(item.parsed_data.name.as_deref(), Some("Projects"));
+        assert!(item.parsed_data.metadata.contains(&("delegate_folder".to_string(), "Users Files".to_string())));
+
+        let mut storage = vec![0u8; 52];
+        storage[4..8].copy_from_slice(&MTP_STORAGE_SIGNATURE.to_le_bytes());
+        storage.extend(utf16("Internal shared storage\0SID-{10001,,}\0FAT32\0"));
+        let item = ShellItem::parse((storage.len() + 2) as u16, &storage).unwrap();
+        assert!(matches!(item.item_type, ShellItemType::Mtp));
+        assert_eq!(item.parsed_data.name.as_deref(), Some("Internal shared storage"));
+        assert!(item.parsed_data.metadata.contains(&("file_system".to_string(), "FAT32".to_string())));
+
+        let mut file = vec![0u8; 72];
+        file[4..8].copy_from_slice(&MTP_FILE_SIGNATURE.to_le_bytes());
+        file[24..32].copy_from_slice(&132223104000000000u64.to_le_bytes()); // 2020-01-01
+        file[32..40].copy_from_slice(&132539328000000000u64.to_le_bytes()); // 2021-01-01
+        file.extend(utf16("IMG_0001.JPG\0o10001\0"));
+        let item = ShellItem::parse((file.len() + 2) as u16, &file).unwrap();
+        assert!(matches!(item.item_type, ShellItemType::Mtp));
+        assert_eq!(item.parsed_data.name.as_deref(), Some("IMG_0001.JPG"));
+        assert_eq!(item.parsed_data.created.unwrap().year(), 2020);
+        assert_eq!(item.parsed_data.modified.unwrap().year(), 2021);
+        assert!(item.parsed_data.metadata.contains(&("type".to_string(), "MtpFile".to_string())));
+        assert!(item.parsed_data.metadata.contains(&("object_id".to_string(), "o10001".to_string())));
+    }
+}
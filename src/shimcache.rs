@@ -0,0 +1,426 @@
+//! AppCompatCache (ShimCache) parsing module for the SYSTEM hive
+//!
+//! Supports parsing of:
+//! - Windows 7 / Server 2008 R2 (x86 and x64) cache entries
+//! - Windows 8 / 8.1 / Server 2012 cache entries
+//! - Windows 10 / 11 cache entries
+
+use crate::error::{Error, Result};
+use crate::registry::{
+    filetime_to_datetime, read_u16, read_u32, read_u64, utf16_string, RegistryEventType, RegistryHive,
+    RegistryTimelineEvent, RegistryValueType,
+};
+use crate::types::EventTimestamps;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Windows 7 cache signature
+const WIN7_MAGIC: u32 = 0xBADC0FEE;
+/// Windows 7 header size
+const WIN7_HEADER_SIZE: usize = 0x80;
+/// Windows 8/8.1 header size
+const WIN8_HEADER_SIZE: usize = 0x80;
+/// Windows 8.0 entry signature
+const WIN8_ENTRY_MAGIC: &[u8; 4] = b"00ts";
+/// Windows 8.1 and 10/11 entry signature
+const WIN81_ENTRY_MAGIC: &[u8; 4] = b"10ts";
+/// Insert flag set when CSRSS recorded the process as executed (Windows 7/8)
+const CSRSS_EXECUTED_FLAG: u32 = 0x2;
+
+/// ShimCache format version
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ShimCacheFormat {
+    /// Windows 7 / Server 2008 R2, 32-bit
+    Windows7x86,
+    /// Windows 7 / Server 2008 R2, 64-bit
+    Windows7x64,
+    /// Windows 8.0 / Server 2012
+    Windows8,
+    /// Windows 8.1 / Server 2012 R2
+    Windows81,
+    /// Windows 10 / 11
+    Windows10,
+}
+
+impl ShimCacheFormat {
+    /// Display name for the format
+    pub fn name(&self) -> &'static str {
+        match self {
+            ShimCacheFormat::Windows7x86 => "Windows 7 (x86)",
+            ShimCacheFormat::Windows7x64 => "Windows 7 (x64)",
+            ShimCacheFormat::Windows8 => "Windows 8",
+            ShimCacheFormat::Windows81 => "Windows 8.1",
+            ShimCacheFormat::Windows10 => "Windows 10/11",
+        }
+    }
+}
+
+/// Single AppCompatCache entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShimCacheEntry {
+    /// Position in the cache (0 = most recently inserted)
+    pub position: usize,
+    /// Executable path
+    pub path: String,
+    /// $STANDARD_INFORMATION last modified time of the file
+    pub last_modified: Option<DateTime<Utc>>,
+    /// Executed flag (None when the format does not record it)
+    pub executed: Option<bool>,
+    /// Cache format
+    pub format: ShimCacheFormat,
+}
+
+/// Parse an AppCompatCache value
+pub fn parse_app_compat_cache(data: &[u8]) -> Result<Vec<ShimCacheEntry>> {
+    let header = read_u32(data, 0)
+        .ok_or_else(|| Error::ParseError("AppCompatCache data too small".to_string()))?;
+
+    if header == WIN7_MAGIC {
+        return parse_win7(data);
+    }
+
+    let header_size = header as usize;
+    match data.get(header_size..header_size + 4) {
+        Some(sig) if header_size == WIN8_HEADER_SIZE && sig == WIN8_ENTRY_MAGIC => {
+            parse_win8(data, header_size, ShimCacheFormat::Windows8)
+        }
+        Some(sig) if header_size == WIN8_HEADER_SIZE && sig == WIN81_ENTRY_MAGIC => {
+            parse_win8(data, header_size, ShimCacheFormat::Windows81)
+        }
+        Some(sig) if (header_size == 0x30 || header_size == 0x34) && sig == WIN81_ENTRY_MAGIC => {
+            parse_win10(data, header_size)
+        }
+        _ => Err(Error::ParseError(format!(
+            "Unsupported AppCompatCache format (header 0x{:08X})",
+            header
+        ))),
+    }
+}
+
+/// Parse Windows 7 entries (fixed-size entries with path offsets into the value)
+fn parse_win7(data: &[u8]) -> Result<Vec<ShimCacheEntry>> {
+    let count = read_u32(data, 4).unwrap_or(0) as usize;
+
+    // x64 entries have 4 bytes of padding after the path lengths, x86 entries a non-zero path offset
+    let format = if read_u32(data, WIN7_HEADER_SIZE + 4) == Some(0) {
+        ShimCacheFormat::Windows7x64
+    } else {
+        ShimCacheFormat::Windows7x86
+    };
+    let entry_size = if format == ShimCacheFormat::Windows7x64 { 48 } else { 32 };
+
+    let mut entries = Vec::new();
+    for position in 0..count {
+        let offset = WIN7_HEADER_SIZE + position * entry_size;
+        let Some(entry) = data.get(offset..offset + entry_size) else {
+            break;
+        };
+
+        let path_length = read_u16(entry, 0).unwrap_or(0) as usize;
+        let (path_offset, last_modified, insert_flags) = if format == ShimCacheFormat::Windows7x64 {
+            (read_u64(entry, 8).unwrap_or(0) as usize, read_u64(entry, 16), read_u32(entry, 24))
+        } else {
+            (read_u32(entry, 4).unwrap_or(0) as usize, read_u64(entry, 8), read_u32(entry, 16))
+        };
+
+        let path = data
+            .get(path_offset..path_offset + path_length)
+            .map(utf16_string)
+            .unwrap_or_default();
+
+        entries.push(ShimCacheEntry {
+            position,
+            path,
+            last_modified: last_modified.and_then(filetime_to_datetime),
+            executed: insert_flags.map(|flags| flags & CSRSS_EXECUTED_FLAG != 0),
+            format,
+        });
+    }
+
+    Ok(entries)
+}
+
+/// Parse Windows 8/8.1 entries
+fn parse_win8(data: &[u8], header_size: usize, format: ShimCacheFormat) -> Result<Vec<ShimCacheEntry>> {
+    let mut entries = Vec::new();
+
+    for (position, entry) in signed_entries(data, header_size).enumerate() {
+        let mut offset = 0;
+        let path = read_length_prefixed(entry, &mut offset);
+
+        // Package name (Store apps), skipped; Windows 8.0 entries have no package field
+        if format == ShimCacheFormat::Windows81 {
+            let package_length = read_u16(entry, offset).unwrap_or(0) as usize;
+            offset += 2 + package_length;
+        }
+
+        let insert_flags = read_u32(entry, offset);
+        let last_modified = read_u64(entry, offset + 8);
+
+        entries.push(ShimCacheEntry {
+            position,
+            path,
+            last_modified: last_modified.and_then(filetime_to_datetime),
+            executed: insert_flags.map(|flags| flags & CSRSS_EXECUTED_FLAG != 0),
+            format,
+        });
+    }
+
+    Ok(entries)
+}
+
+/// Parse Windows 10/11 entries
+fn parse_win10(data: &[u8], header_size: usize) -> Result<Vec<ShimCacheEntry>> {
+    let mut entries = Vec::new();
+
+    for (position, entry) in signed_entries(data, header_size).enumerate() {
+        let mut offset = 0;
+        let path = read_length_prefixed(entry, &mut offset);
+        let last_modified = read_u64(entry, offset);
+        let data_size = read_u32(entry, offset + 8).unwrap_or(0) as usize;
+
+        // The last DWORD of the entry data is 1 when the file was executed
+        let executed = if data_size >= 4 {
+            let end = offset + 12 + data_size;
+            read_u32(entry, end - 4).map(|flag| flag == 1)
+        } else {
+            None
+        };
+
+        entries.push(ShimCacheEntry {
+            position,
+            path,
+            last_modified: last_modified.and_then(filetime_to_datetime),
+            executed,
+            format: ShimCacheFormat::Windows10,
+        });
+    }
+
+    Ok(entries)
+}
+
+/// Iterate over "00ts"/"10ts" signed entries, yielding each entry's data
+fn signed_entries(data: &[u8], start: usize) -> impl Iterator<Item = &[u8]> {
+    let mut offset = start;
+    std::iter::from_fn(move || {
+        let signature = data.get(offset..offset + 4)?;
+        if signature != WIN8_ENTRY_MAGIC && signature != WIN81_ENTRY_MAGIC {
+            return None;
+        }
+        let length = read_u32(data, offset + 8)? as usize;
+        let entry = data.get(offset + 12..offset + 12 + length)?;
+        offset += 12 + length;
+        Some(entry)
+    })
+}
+
+/// Read a u16 length-prefixed UTF-16 string, advancing the offset
+fn read_length_prefixed(data: &[u8], offset: &mut usize) -> String {
+    let length = read_u16(data, *offset).unwrap_or(0) as usize;
+    *offset += 2;
+    let value = data.get(*offset..*offset + length).map(utf16_string).unwrap_or_default();
+    *offset += length;
+    value
+}
+
+/// Extract ShimCache entries from a SYSTEM hive as timeline events
+pub fn extract_timeline_events(hive: &RegistryHive) -> Vec<RegistryTimelineEvent> {
+    let Some(control_set) = hive.current_control_set() else {
+        return Vec::new();
+    };
+
+    let key_path = format!("{}\\Control\\Session Manager\\AppCompatCache", control_set);
+    let Some(key) = hive.open_key(&key_path) else {
+        return Vec::new();
+    };
+    let Some(value) = key.value("AppCompatCache") else {
+        return Vec::new();
+    };
+
+    let entries = match parse_app_compat_cache(&value.data) {
+        Ok(entries) => entries,
+        Err(e) => {
+            eprintln!("⚠️  Failed to parse AppCompatCache: {}", e);
+            return Vec::new();
+        }
+    };
+
+    entries
+        .into_iter()
+        .map(|entry| {
+            let executed = match entry.executed {
+                Some(true) => "Yes",
+                Some(false) => "No",
+                None => "N/A",
+            };
+
+            RegistryTimelineEvent {
+                key_path: key.path.clone(),
+                value_name: Some(value.name.clone()),
+                event_type: RegistryEventType::ValueSet,
+                value_type: Some(RegistryValueType::Binary),
+                data: None,
+                data_size: None,
+                timestamps: EventTimestamps {
+                    created: None,
+                    modified: entry.last_modified,
+                    accessed: None,
+                    mft_modified: None,
+                },
+                description: Some(format!("ShimCache: {}", entry.path)),
+                metadata: vec![
+                    ("Cache Position".to_string(), entry.position.to_string()),
+                    ("Path".to_string(), entry.path.clone()),
+                    ("Executed".to_string(), executed.to_string()),
+                    ("Format".to_string(), entry.format.name().to_string()),
+                    ("Control Set".to_string(), control_set.clone()),
+                ],
+                source: Some("ShimCache".to_string()),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::test_hive::utf16;
+    use chrono::Datelike;
+
+    fn win10_entry(path: &str, filetime: u64, data: &[u8]) -> Vec<u8> {
+        let path = utf16(path);
+        let mut body = Vec::new();
+        body.extend_from_slice(&(path.len() as u16).to_le_bytes());
+        body.extend_from_slice(&path);
+        body.extend_from_slice(&filetime.to_le_bytes());
+        body.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        body.extend_from_slice(data);
+
+        let mut entry = WIN81_ENTRY_MAGIC.to_vec();
+        entry.extend_from_slice(&0u32.to_le_bytes());
+        entry.extend_from_slice(&(body.len() as u32).to_le_bytes());
+        entry.extend_from_slice(&body);
+        entry
+    }
+
+    #[test]
+    fn test_parse_win10_cache() {
+        let mut data = vec![0u8; 0x34];
+        data[0..4].copy_from_slice(&0x34u32.to_le_bytes());
+        data.extend(win10_entry("C:\\Windows\\notepad.exe", 132223104000000000, &[0, 0, 0, 0, 1, 0, 0, 0]));
+        data.extend(win10_entry("C:\\Tools\\evil.exe", 0, &[]));
+
+        let entries = parse_app_compat_cache(&data).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].path, "C:\\Windows\\notepad.exe");
+        assert_eq!(entries[0].last_modified.unwrap().year(), 2020);
+        assert_eq!(entries[0].executed, Some(true));
+        assert_eq!(entries[1].position, 1);
+        assert_eq!(entries[1].executed, None);
+        assert_eq!(entries[1].format, ShimCacheFormat::Windows10);
+    }
+
+    fn win8_entry(magic: &[u8; 4], path: &str, package: Option<&str>, insert_flags: u32, filetime: u64) -> Vec<u8> {
+        let path = utf16(path);
+        let mut body = Vec::new();
+        body.extend_from_slice(&(path.len() as u16).to_le_bytes());
+        body.extend_from_slice(&path);
+        if let Some(package) = package {
+            let package = utf16(package);
+            body.extend_from_slice(&(package.len() as u16).to_le_bytes());
+            body.extend_from_slice(&package);
+        }
+        body.extend_from_slice(&insert_flags.to_le_bytes());
+        body.extend_from_slice(&0u32.to_le_bytes());
+        body.extend_from_slice(&filetime.to_le_bytes());
+        body.extend_from_slice(&0u32.to_le_bytes());
+
+        let mut entry = magic.to_vec();
+        entry.extend_from_slice(&0u32.to_le_bytes());
+        entry.extend_from_slice(&(body.len() as u32).to_le_bytes());
+        entry.extend_from_slice(&body);
+        entry
+    }
+
+    fn win8_cache(entries: &[Vec<u8>]) -> Vec<u8> {
+        let mut data = vec![0u8; WIN8_HEADER_SIZE];
+        data[0..4].copy_from_slice(&(WIN8_HEADER_SIZE as u32).to_le_bytes());
+        for entry in entries {
+            data.extend_from_slice(entry);
+        }
+        data
+    }
+
+    #[test]
+    fn test_parse_win8_cache() {
+        let data = win8_cache(&[
+            win8_entry(WIN8_ENTRY_MAGIC, "C:\\Windows\\notepad.exe", None, CSRSS_EXECUTED_FLAG, 132223104000000000),
+            win8_entry(WIN8_ENTRY_MAGIC, "C:\\Tools\\evil.exe", None, 0, 0),
+        ]);
+
+        let entries = parse_app_compat_cache(&data).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].format, ShimCacheFormat::Windows8);
+        assert_eq!(entries[0].path, "C:\\Windows\\notepad.exe");
+        assert_eq!(entries[0].last_modified.unwrap().year(), 2020);
+        assert_eq!(entries[0].executed, Some(true));
+        assert_eq!(entries[1].path, "C:\\Tools\\evil.exe");
+        assert!(entries[1].last_modified.is_none());
+        assert_eq!(entries[1].executed, Some(false));
+    }
+
+    #[test]
+    fn test_parse_win81_cache() {
+        let data = win8_cache(&[
+            win8_entry(
+                WIN81_ENTRY_MAGIC,
+                "SYSVOL\\Program Files\\WindowsApps\\app.exe",
+                Some("Microsoft.App_1.0.0.0_x64__8wekyb3d8bbwe"),
+                CSRSS_EXECUTED_FLAG,
+                132223104000000000,
+            ),
+            win8_entry(WIN81_ENTRY_MAGIC, "SYSVOL\\Windows\\calc.exe", Some(""), 0, 132223104000000000),
+        ]);
+
+        let entries = parse_app_compat_cache(&data).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].format, ShimCacheFormat::Windows81);
+        assert_eq!(entries[0].path, "SYSVOL\\Program Files\\WindowsApps\\app.exe");
+        assert_eq!(entries[0].last_modified.unwrap().year(), 2020);
+        assert_eq!(entries[0].executed, Some(true));
+        assert_eq!(entries[1].path, "SYSVOL\\Windows\\calc.exe");
+        assert_eq!(entries[1].last_modified.unwrap().year(), 2020);
+        assert_eq!(entries[1].executed, Some(false));
+    }
+
+    #[test]
+    fn test_parse_win7_x64_cache() {
+        let path = utf16("C:\\Windows\\calc.exe");
+        let path_offset = WIN7_HEADER_SIZE + 48;
+
+        let mut data = vec![0u8; WIN7_HEADER_SIZE];
+        data[0..4].copy_from_slice(&WIN7_MAGIC.to_le_bytes());
+        data[4..8].copy_from_slice(&1u32.to_le_bytes());
+
+        let mut entry = vec![0u8; 48];
+        entry[0..2].copy_from_slice(&(path.len() as u16).to_le_bytes());
+        entry[2..4].copy_from_slice(&(path.len() as u16 + 2).to_le_bytes());
+        entry[8..16].copy_from_slice(&(path_offset as u64).to_le_bytes());
+        entry[16..24].copy_from_slice(&132223104000000000u64.to_le_bytes());
+        entry[24..28].copy_from_slice(&CSRSS_EXECUTED_FLAG.to_le_bytes());
+        data.extend(entry);
+        data.extend(path);
+
+        let entries = parse_app_compat_cache(&data).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].format, ShimCacheFormat::Windows7x64);
+        assert_eq!(entries[0].path, "C:\\Windows\\calc.exe");
+        assert_eq!(entries[0].executed, Some(true));
+    }
+
+    #[test]
+    fn test_unsupported_cache() {
+        assert!(parse_app_compat_cache(&[0u8; 2]).is_err());
+        assert!(parse_app_compat_cache(&[0xFFu8; 64]).is_err());
+    }
+}
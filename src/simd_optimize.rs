@@ -298,35 +298,32 @@ pub fn convert_timestamps_simd(filetimes: &[u64]) -> Vec<Option<chrono::DateTime
 /// Memory-aligned buffer for optimal SIMD access
 pub struct AlignedBuffer {
     data: Vec<u8>,
+    offset: usize,
+    size: usize,
     alignment: usize,
 }
 
 impl AlignedBuffer {
     pub fn new(size: usize, alignment: usize) -> Self {
-        let mut data = Vec::with_capacity(size + alignment);
-        data.resize(size + alignment, 0);
+        let data = vec![0u8; size + alignment];
         
-        // Align the buffer
-        let ptr = data.as_mut_ptr();
-        let aligned_ptr = ((ptr as usize + alignment - 1) & !(alignment - 1)) as *mut u8;
-        let offset = aligned_ptr as usize - ptr as usize;
+        // Align the buffer (the allocation never moves, so the offset stays valid)
+        let ptr = data.as_ptr() as usize;
+        let offset = ((ptr + alignment - 1) & !(alignment - 1)) - ptr;
         
-        data.drain(0..offset);
-        data.truncate(size);
-        
-        Self { data, alignment }
+        Self { data, offset, size, alignment }
     }
     
     pub fn as_slice(&self) -> &[u8] {
-        &self.data
+        &self.data[self.offset..self.offset + self.size]
     }
     
     pub fn as_mut_slice(&mut self) -> &mut [u8] {
-        &mut self.data
+        &mut self.data[self.offset..self.offset + self.size]
     }
     
     pub fn is_aligned(&self) -> bool {
-        (self.data.as_ptr() as usize) % self.alignment == 0
+        (self.as_slice().as_ptr() as usize).is_multiple_of(self.alignment)
     }
 }
 
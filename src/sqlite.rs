@@ -0,0 +1,716 @@
+//! Read-only SQLite database file reader
+//!
+//! Supports parsing of:
+//! - The 100-byte database header (page size, reserved space, text encoding)
+//! - The sqlite_schema table and CREATE TABLE column lists
+//! - Table b-trees (interior and leaf pages) with overflow pages
+//! - Record format serial types (integers, floats, text in UTF-8/UTF-16, blobs)
+//!
+//! Only committed pages in the main database file are read; write-ahead log (-wal) contents,
+//! freelist pages and WITHOUT ROWID tables are not.
+
+use crate::error::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Database file signature
+const SQLITE_SIGNATURE: &[u8; 16] = b"SQLite format 3\0";
+/// Size of the database header on page 1
+const HEADER_SIZE: usize = 100;
+/// Maximum b-tree depth followed before the tree is treated as corrupt
+const MAX_TREE_DEPTH: usize = 64;
+
+/// Table b-tree page types
+const INTERIOR_TABLE_PAGE: u8 = 0x05;
+const LEAF_TABLE_PAGE: u8 = 0x0D;
+
+/// Value stored in a record column
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SqliteValue {
+    Null,
+    Integer(i64),
+    Real(f64),
+    Text(String),
+    Blob(Vec<u8>),
+}
+
+impl SqliteValue {
+    /// Integer value (real values are truncated)
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            SqliteValue::Integer(v) => Some(*v),
+            SqliteValue::Real(v) => Some(*v as i64),
+            _ => None,
+        }
+    }
+
+    /// Text value; blobs are decoded as UTF-8 since applications often store JSON or paths as blobs
+    pub fn as_text(&self) -> Option<String> {
+        match self {
+            SqliteValue::Text(s) => Some(s.clone()),
+            SqliteValue::Blob(b) => std::str::from_utf8(b).ok().map(|s| s.to_string()),
+            SqliteValue::Integer(v) => Some(v.to_string()),
+            SqliteValue::Real(v) => Some(v.to_string()),
+            SqliteValue::Null => None,
+        }
+    }
+
+    /// Blob value
+    pub fn as_blob(&self) -> Option<&[u8]> {
+        match self {
+            SqliteValue::Blob(b) => Some(b),
+            _ => None,
+        }
+    }
+}
+
+/// Table definition from sqlite_schema
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TableSchema {
+    /// Table name
+    pub name: String,
+    /// Root page of the table b-tree
+    pub root_page: u32,
+    /// Column names in declaration order
+    pub columns: Vec<String>,
+    /// Column declared INTEGER PRIMARY KEY, which aliases the rowid
+    pub rowid_column: Option<usize>,
+    /// Whether the table was created WITHOUT ROWID (stored as an index b-tree)
+    pub without_rowid: bool,
+}
+
+/// Rows read from one table
+#[derive(Debug, Clone)]
+pub struct Table {
+    /// Table name
+    pub name: String,
+    /// Column names
+    pub columns: Vec<String>,
+    /// Row values in column order
+    pub rows: Vec<Vec<SqliteValue>>,
+}
+
+/// One row with access by column name
+#[derive(Debug, Clone, Copy)]
+pub struct Row<'a> {
+    columns: &'a [String],
+    values: &'a [SqliteValue],
+}
+
+impl Table {
+    /// Iterate over rows with named column access
+    pub fn records(&self) -> impl Iterator<Item = Row<'_>> {
+        self.rows.iter().map(|values| Row { columns: &self.columns, values })
+    }
+}
+
+impl<'a> Row<'a> {
+    /// Value of a column (case-insensitive); columns added by later schema versions may be missing
+    pub fn get(&self, column: &str) -> Option<&'a SqliteValue> {
+        let index = self.columns.iter().position(|c| c.eq_ignore_ascii_case(column))?;
+        // Rows written before an ALTER TABLE ADD COLUMN have fewer values
+        Some(self.values.get(index).unwrap_or(&SqliteValue::Null))
+    }
+
+    /// Integer value of a column
+    pub fn integer(&self, column: &str) -> Option<i64> {
+        self.get(column).and_then(SqliteValue::as_i64)
+    }
+
+    /// Non-empty text value of a column
+    pub fn text(&self, column: &str) -> Option<String> {
+        self.get(column).and_then(SqliteValue::as_text).filter(|s| !s.is_empty())
+    }
+
+    /// Blob value of a column
+    pub fn blob(&self, column: &str) -> Option<&'a [u8]> {
+        self.get(column).and_then(SqliteValue::as_blob)
+    }
+}
+
+/// SQLite database opened from an in-memory image
+pub struct SqliteDatabase<'a> {
+    data: &'a [u8],
+    page_size: usize,
+    usable_size: usize,
+    text_encoding: u32,
+    tables: Vec<TableSchema>,
+}
+
+impl<'a> SqliteDatabase<'a> {
+    /// Open a database image and read its schema
+    pub fn open(data: &'a [u8]) -> Result<Self> {
+        if data.len() < HEADER_SIZE || !data.starts_with(SQLITE_SIGNATURE) {
+            return Err(Error::ParseError("Not an SQLite database".to_string()));
+        }
+
+        // A stored page size of 1 means 65536
+        let page_size = match be16(data, 16).unwrap_or(0) {
+            1 => 65536,
+            size => size as usize,
+        };
+        if page_size < 512 || !page_size.is_power_of_two() {
+            return Err(Error::ParseError(format!("Invalid SQLite page size {}", page_size)));
+        }
+        let reserved = data[20] as usize;
+
+        let mut database = SqliteDatabase {
+            data,
+            page_size,
+            usable_size: page_size - reserved,
+            text_encoding: be32(data, 56).unwrap_or(1),
+            tables: Vec::new(),
+        };
+        database.tables = database.read_schema()?;
+        Ok(database)
+    }
+
+    /// Tables defined in the schema
+    pub fn tables(&self) -> &[TableSchema] {
+        &self.tables
+    }
+
+    /// Schema of a table by name (case-insensitive)
+    pub fn table_schema(&self, name: &str) -> Option<&TableSchema> {
+        self.tables.iter().find(|t| t.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Read all rows of a table
+    pub fn read_table(&self, name: &str) -> Result<Table> {
+        let schema = self
+            .table_schema(name)
+            .ok_or_else(|| Error::ParseError(format!("SQLite table {} not found", name)))?;
+        if schema.without_rowid {
+            return Err(Error::ParseError(format!("WITHOUT ROWID table {} is not supported", name)));
+        }
+
+        let mut cells = Vec::new();
+        self.collect_cells(schema.root_page, 0, &mut HashSet::new(), &mut cells)?;
+        let rows = cells
+            .into_iter()
+            .map(|(rowid, payload)| {
+                let mut values = self.decode_record(&payload);
+                // The INTEGER PRIMARY KEY column is stored as NULL and takes the rowid
+                if let Some(index) = schema.rowid_column {
+                    if let Some(value @ SqliteValue::Null) = values.get_mut(index) {
+                        *value = SqliteValue::Integer(rowid);
+                    }
+                }
+                values
+            })
+            .collect();
+
+        Ok(Table { name: schema.name.clone(), columns: schema.columns.clone(), rows })
+    }
+
+    /// Read table definitions from sqlite_schema (page 1)
+    fn read_schema(&self) -> Result<Vec<TableSchema>> {
+        let mut cells = Vec::new();
+        self.collect_cells(1, 0, &mut HashSet::new(), &mut cells)?;
+
+        Ok(cells
+            .iter()
+            .filter_map(|(_, payload)| {
+                let values = self.decode_record(payload);
+                // Columns: type, name, tbl_name, rootpage, sql
+                if values.first()?.as_text()? != "table" {
+                    return None;
+                }
+                let name = values.get(1)?.as_text()?;
+                let root_page = values.get(3)?.as_i64()? as u32;
+                let sql = values.get(4)?.as_text()?;
+                let (columns, rowid_column) = parse_columns(&sql);
+                let without_rowid = sql.to_uppercase().split_whitespace().collect::<Vec<_>>().ends_with(&["WITHOUT", "ROWID"]);
+                Some(TableSchema { name, root_page, columns, rowid_column, without_rowid })
+            })
+            .collect())
+    }
+
+    /// Slice of a page by 1-based page number
+    fn page(&self, number: u32) -> Result<&'a [u8]> {
+        let start = (number as usize)
+            .checked_sub(1)
+            .map(|index| index * self.page_size)
+            .ok_or_else(|| Error::ParseError("SQLite page number 0".to_string()))?;
+        self.data
+            .get(start..start + self.page_size)
+            .ok_or_else(|| Error::ParseError(format!("SQLite page {} beyond end of file", number)))
+    }
+
+    /// Collect (rowid, payload) cells from a table b-tree in rowid order. Pages already visited
+    /// are skipped so corrupt child pointers cannot loop back into the tree.
+    fn collect_cells(
+        &self,
+        page_number: u32,
+        depth: usize,
+        visited: &mut HashSet<u32>,
+        cells: &mut Vec<(i64, Vec<u8>)>,
+    ) -> Result<()> {
+        if depth > MAX_TREE_DEPTH {
+            return Err(Error::ParseError("SQLite b-tree too deep".to_string()));
+        }
+        if !visited.insert(page_number) {
+            return Ok(());
+        }
+        let page = self.page(page_number)?;
+        let header = if page_number == 1 { HEADER_SIZE } else { 0 };
+        let truncated = || Error::ParseError(format!("SQLite page {} truncated", page_number));
+        let cell_count = be16(page, header + 3).ok_or_else(truncated)? as usize;
+
+        match page[header] {
+            LEAF_TABLE_PAGE => {
+                for i in 0..cell_count {
+                    let pointer = be16(page, header + 8 + i * 2).ok_or_else(truncated)? as usize;
+                    let (payload_size, n) = varint(page, pointer).ok_or_else(truncated)?;
+                    let (rowid, m) = varint(page, pointer + n).ok_or_else(truncated)?;
+                    let payload = self.payload(page, pointer + n + m, payload_size as usize)?;
+                    cells.push((rowid as i64, payload));
+                }
+            }
+            INTERIOR_TABLE_PAGE => {
+                for i in 0..cell_count {
+                    let pointer = be16(page, header + 12 + i * 2).ok_or_else(truncated)? as usize;
+                    let child = be32(page, pointer).ok_or_else(truncated)?;
+                    self.collect_cells(child, depth + 1, visited, cells)?;
+                }
+                let right_most = be32(page, header + 8).ok_or_else(truncated)?;
+                self.collect_cells(right_most, depth + 1, visited, cells)?;
+            }
+            other => {
+                return Err(Error::ParseError(format!("Unexpected SQLite page type 0x{:02X} on page {}", other, page_number)));
+            }
+        }
+        Ok(())
+    }
+
+    /// Read a table leaf payload, following overflow pages when it does not fit locally
+    fn payload(&self, page: &[u8], offset: usize, size: usize) -> Result<Vec<u8>> {
+        let max_local = self.usable_size - 35;
+        let local = if size <= max_local {
+            size
+        } else {
+            let min_local = (self.usable_size - 12) * 32 / 255 - 23;
+            let spill = min_local + (size - min_local) % (self.usable_size - 4);
+            if spill <= max_local { spill } else { min_local }
+        };
+
+        let truncated = || Error::ParseError("SQLite cell payload truncated".to_string());
+        let mut payload = page.get(offset..offset + local).ok_or_else(truncated)?.to_vec();
+        if local < size {
+            let mut next = be32(page, offset + local).ok_or_else(truncated)?;
+            let max_pages = self.data.len() / self.page_size;
+            let mut followed = 0;
+            while payload.len() < size && next != 0 {
+                followed += 1;
+                if followed > max_pages {
+                    return Err(Error::ParseError("SQLite overflow chain loops".to_string()));
+                }
+                let overflow = self.page(next)?;
+                let take = (size - payload.len()).min(self.usable_size - 4);
+                payload.extend_from_slice(&overflow[4..4 + take]);
+                next = be32(overflow, 0).unwrap_or(0);
+            }
+        }
+        Ok(payload)
+    }
+
+    /// Decode a record into column values
+    fn decode_record(&self, payload: &[u8]) -> Vec<SqliteValue> {
+        let Some((header_size, mut position)) = varint(payload, 0) else {
+            return Vec::new();
+        };
+        let header_size = header_size as usize;
+        let mut serial_types = Vec::new();
+        while position < header_size {
+            let Some((serial_type, n)) = varint(payload, position) else { break };
+            serial_types.push(serial_type);
+            position += n;
+        }
+
+        let mut body = header_size;
+        serial_types
+            .into_iter()
+            .map(|serial_type| {
+                let (value, length) = self.decode_value(payload.get(body..).unwrap_or(&[]), serial_type);
+                body += length;
+                value
+            })
+            .collect()
+    }
+
+    /// Decode one value of a serial type, returning it with its stored length
+    fn decode_value(&self, data: &[u8], serial_type: u64) -> (SqliteValue, usize) {
+        let integer = |length: usize| {
+            let bytes = data.get(..length)?;
+            // Sign-extend the big-endian two's complement value
+            let value = bytes.iter().fold(if bytes[0] & 0x80 != 0 { -1i64 } else { 0 }, |acc, &b| (acc << 8) | b as i64);
+            Some(SqliteValue::Integer(value))
+        };
+
+        let (value, length) = match serial_type {
+            0 => (Some(SqliteValue::Null), 0),
+            1 => (integer(1), 1),
+            2 => (integer(2), 2),
+            3 => (integer(3), 3),
+            4 => (integer(4), 4),
+            5 => (integer(6), 6),
+            6 => (integer(8), 8),
+            7 => (
+                data.get(..8).map(|b| SqliteValue::Real(f64::from_be_bytes(b.try_into().unwrap_or([0; 8])))),
+                8,
+            ),
+            8 => (Some(SqliteValue::Integer(0)), 0),
+            9 => (Some(SqliteValue::Integer(1)), 0),
+            n if n >= 12 && n % 2 == 0 => {
+                let length = ((n - 12) / 2) as usize;
+                (data.get(..length).map(|b| SqliteValue::Blob(b.to_vec())), length)
+            }
+            n if n >= 13 => {
+                let length = ((n - 13) / 2) as usize;
+                (data.get(..length).map(|b| SqliteValue::Text(self.decode_text(b))), length)
+            }
+            _ => (None, 0), // Reserved serial types 10 and 11
+        };
+        (value.unwrap_or(SqliteValue::Null), length)
+    }
+
+    /// Decode text in the database encoding
+    fn decode_text(&self, bytes: &[u8]) -> String {
+        match self.text_encoding {
+            2 | 3 => {
+                let units: Vec<u16> = bytes
+                    .chunks_exact(2)
+                    .map(|c| if self.text_encoding == 2 { u16::from_le_bytes([c[0], c[1]]) } else { u16::from_be_bytes([c[0], c[1]]) })
+                    .collect();
+                String::from_utf16_lossy(&units)
+            }
+            _ => String::from_utf8_lossy(bytes).to_string(),
+        }
+    }
+}
+
+/// Parse column names from a CREATE TABLE statement, with the INTEGER PRIMARY KEY column if any
+fn parse_columns(sql: &str) -> (Vec<String>, Option<usize>) {
+    let (Some(start), Some(end)) = (sql.find('('), sql.rfind(')')) else {
+        return (Vec::new(), None);
+    };
+    let body = sql.get(start + 1..end).unwrap_or("");
+
+    // Split on top-level commas, ignoring those inside parentheses or quotes
+    let mut definitions = Vec::new();
+    let mut depth = 0;
+    let mut quote: Option<char> = None;
+    let mut current = String::new();
+    for c in body.chars() {
+        match (quote, c) {
+            (Some(q), c) if c == q || (q == '[' && c == ']') => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'' | '`' | '[') => quote = Some(c),
+            (None, '(') => depth += 1,
+            (None, ')') => depth -= 1,
+            (None, ',') if depth == 0 => {
+                definitions.push(std::mem::take(&mut current));
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    definitions.push(current);
+
+    let mut columns = Vec::new();
+    let mut rowid_column = None;
+    for definition in definitions {
+        let definition = definition.trim();
+        let first_word = definition.split_whitespace().next().unwrap_or("").to_uppercase();
+        if definition.is_empty() || matches!(first_word.as_str(), "CONSTRAINT" | "PRIMARY" | "UNIQUE" | "CHECK" | "FOREIGN") {
+            continue;
+        }
+
+        let (name, rest) = match definition.chars().next() {
+            Some(open @ ('"' | '`' | '[' | '\'')) => {
+                let close = if open == '[' { ']' } else { open };
+                let end = definition[1..].find(close).map(|i| i + 1).unwrap_or(definition.len());
+                (&definition[1..end], definition.get(end + 1..).unwrap_or(""))
+            }
+            _ => {
+                let end = definition.find(char::is_whitespace).unwrap_or(definition.len());
+                (&definition[..end], &definition[end..])
+            }
+        };
+
+        let declaration = rest.split_whitespace().collect::<Vec<_>>().join(" ").to_uppercase();
+        if declaration.starts_with("INTEGER PRIMARY KEY") {
+            rowid_column = Some(columns.len());
+        }
+        columns.push(name.to_string());
+    }
+    (columns, rowid_column)
+}
+
+/// Read a variable-length integer, returning it with its length in bytes
+fn varint(data: &[u8], offset: usize) -> Option<(u64, usize)> {
+    let mut value = 0u64;
+    for i in 0..9 {
+        let byte = *data.get(offset + i)?;
+        if i == 8 {
+            return Some(((value << 8) | byte as u64, 9));
+        }
+        value = (value << 7) | (byte & 0x7F) as u64;
+        if byte & 0x80 == 0 {
+            return Some((value, i + 1));
+        }
+    }
+    None
+}
+
+/// Big-endian u16
+fn be16(data: &[u8], offset: usize) -> Option<u16> {
+    data.get(offset..offset + 2).map(|b| u16::from_be_bytes([b[0], b[1]]))
+}
+
+/// Big-endian u32
+fn be32(data: &[u8], offset: usize) -> Option<u32> {
+    data.get(offset..offset + 4).map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// Encode a record for a test database
+    fn record(values: &[SqliteValue]) -> Vec<u8> {
+        let mut header = Vec::new();
+        let mut body = Vec::new();
+        for value in values {
+            let serial_type = match value {
+                SqliteValue::Null => 0,
+                SqliteValue::Integer(v) => {
+                    body.extend_from_slice(&v.to_be_bytes());
+                    6
+                }
+                SqliteValue::Real(v) => {
+                    body.extend_from_slice(&v.to_be_bytes());
+                    7
+                }
+                SqliteValue::Text(s) => {
+                    body.extend_from_slice(s.as_bytes());
+                    s.len() * 2 + 13
+                }
+                SqliteValue::Blob(b) => {
+                    body.extend_from_slice(b);
+                    b.len() * 2 + 12
+                }
+            };
+            header.extend(encode_varint(serial_type as u64));
+        }
+        let mut data = encode_varint(header.len() as u64 + 1);
+        data.extend(header);
+        data.extend(body);
+        data
+    }
+
+    fn encode_varint(value: u64) -> Vec<u8> {
+        let mut groups = vec![(value & 0x7F) as u8];
+        let mut rest = value >> 7;
+        while rest > 0 {
+            groups.push((rest & 0x7F) as u8 | 0x80);
+            rest >>= 7;
+        }
+        groups.reverse();
+        groups
+    }
+
+    /// Build a single-table database with 4096-byte pages: page 1 schema, page 2 table leaf
+    pub(crate) fn build_database(create_sql: &str, rows: &[Vec<SqliteValue>]) -> Vec<u8> {
+        build_tables(&[(create_sql, rows.to_vec())])
+    }
+
+    /// Test database page size
+    const PAGE_SIZE: usize = 4096;
+
+    /// Table leaf page holding `cells`, with the b-tree header at `header`
+    fn leaf_page(header: usize, cells: Vec<Vec<u8>>) -> Vec<u8> {
+        let mut page = vec![0u8; PAGE_SIZE];
+        page[header] = LEAF_TABLE_PAGE;
+        page[header + 3..header + 5].copy_from_slice(&(cells.len() as u16).to_be_bytes());
+        let mut content = PAGE_SIZE;
+        for (i, cell) in cells.iter().enumerate() {
+            content -= cell.len();
+            page[content..content + cell.len()].copy_from_slice(cell);
+            page[header + 8 + i * 2..header + 10 + i * 2].copy_from_slice(&(content as u16).to_be_bytes());
+        }
+        page[header + 5..header + 7].copy_from_slice(&(content as u16).to_be_bytes());
+        page
+    }
+
+    /// Table interior page with (child page, key rowid) cells and a right-most child
+    fn interior_page(children: &[(u32, i64)], right_most: u32) -> Vec<u8> {
+        let mut page = vec![0u8; PAGE_SIZE];
+        page[0] = INTERIOR_TABLE_PAGE;
+        page[3..5].copy_from_slice(&(children.len() as u16).to_be_bytes());
+        page[8..12].copy_from_slice(&right_most.to_be_bytes());
+        let mut content = PAGE_SIZE;
+        for (i, &(child, rowid)) in children.iter().enumerate() {
+            let mut cell = child.to_be_bytes().to_vec();
+            cell.extend(encode_varint(rowid as u64));
+            content -= cell.len();
+            page[content..content + cell.len()].copy_from_slice(&cell);
+            page[12 + i * 2..14 + i * 2].copy_from_slice(&(content as u16).to_be_bytes());
+        }
+        page[5..7].copy_from_slice(&(content as u16).to_be_bytes());
+        page
+    }
+
+    /// Table leaf cell with a payload stored entirely on the page
+    fn table_cell(rowid: i64, payload: Vec<u8>) -> Vec<u8> {
+        let mut cell = encode_varint(payload.len() as u64);
+        cell.extend(encode_varint(rowid as u64));
+        cell.extend(payload);
+        cell
+    }
+
+    /// Page 1 with the database header and a schema row per (CREATE TABLE, root page)
+    fn schema_page(tables: &[(&str, u32)], page_count: u32) -> Vec<u8> {
+        let schema_rows = tables
+            .iter()
+            .enumerate()
+            .map(|(i, (create_sql, root_page))| {
+                let name = create_sql.split_whitespace().nth(2).unwrap_or("t").to_string();
+                let name = name.split('(').next().unwrap_or(&name).to_string();
+                table_cell(
+                    i as i64 + 1,
+                    record(&[
+                        SqliteValue::Text("table".to_string()),
+                        SqliteValue::Text(name.clone()),
+                        SqliteValue::Text(name),
+                        SqliteValue::Integer(*root_page as i64),
+                        SqliteValue::Text(create_sql.to_string()),
+                    ]),
+                )
+            })
+            .collect();
+        let mut data = leaf_page(HEADER_SIZE, schema_rows);
+        data[..16].copy_from_slice(SQLITE_SIGNATURE);
+        data[16..18].copy_from_slice(&(PAGE_SIZE as u16).to_be_bytes());
+        data[28..32].copy_from_slice(&page_count.to_be_bytes());
+        data[56..60].copy_from_slice(&1u32.to_be_bytes());
+        data
+    }
+
+    /// Build a database with 4096-byte pages: page 1 schema, then one leaf page per table
+    pub(crate) fn build_tables(tables: &[(&str, Vec<Vec<SqliteValue>>)]) -> Vec<u8> {
+        let roots: Vec<(&str, u32)> = tables.iter().enumerate().map(|(i, (sql, _))| (*sql, i as u32 + 2)).collect();
+        let mut data = schema_page(&roots, tables.len() as u32 + 1);
+        for (_, rows) in tables {
+            data.extend(leaf_page(0, rows.iter().enumerate().map(|(i, row)| table_cell(i as i64 + 1, record(row))).collect()));
+        }
+        data
+    }
+
+    #[test]
+    fn test_varint() {
+        assert_eq!(varint(&[0x05], 0), Some((5, 1)));
+        assert_eq!(varint(&[0x81, 0x00], 0), Some((128, 2)));
+        assert_eq!(varint(&[0xFF; 9], 0), Some((u64::MAX, 9)));
+        assert_eq!(varint(&[0x81], 0), None);
+        assert_eq!(encode_varint(300), vec![0x82, 0x2C]);
+    }
+
+    #[test]
+    fn test_parse_columns() {
+        let (columns, rowid) = parse_columns(
+            "CREATE TABLE [Activity]([Id] GUID PRIMARY KEY NOT NULL, \"App Id\" TEXT, value NUMERIC(10, 2), PRIMARY KEY(Id))",
+        );
+        assert_eq!(columns, vec!["Id", "App Id", "value"]);
+        assert_eq!(rowid, None);
+        let (columns, rowid) = parse_columns("CREATE TABLE urls(id INTEGER PRIMARY KEY AUTOINCREMENT, url LONGVARCHAR)");
+        assert_eq!(columns, vec!["id", "url"]);
+        assert_eq!(rowid, Some(0));
+    }
+
+    #[test]
+    fn test_read_table() {
+        let data = build_database(
+            "CREATE TABLE urls (id INTEGER PRIMARY KEY, url TEXT, visits INTEGER, favicon BLOB)",
+            &[
+                vec![SqliteValue::Null, SqliteValue::Text("https://example.com/".to_string()), SqliteValue::Integer(-3), SqliteValue::Blob(vec![1, 2])],
+                vec![SqliteValue::Null, SqliteValue::Text("https://example.org/".to_string()), SqliteValue::Integer(7)],
+            ],
+        );
+        let database = SqliteDatabase::open(&data).unwrap();
+        assert_eq!(database.tables().len(), 1);
+        let table = database.read_table("URLS").unwrap();
+        let rows: Vec<Row> = table.records().collect();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].integer("id"), Some(1));
+        assert_eq!(rows[0].text("url").as_deref(), Some("https://example.com/"));
+        assert_eq!(rows[0].integer("visits"), Some(-3));
+        assert_eq!(rows[0].blob("favicon"), Some(&[1u8, 2][..]));
+        assert_eq!(rows[1].get("favicon"), Some(&SqliteValue::Null));
+        assert!(rows[1].get("missing").is_none());
+        assert!(database.read_table("visits").is_err());
+        assert!(SqliteDatabase::open(b"not a database").is_err());
+    }
+
+    /// Rows of a single-column text table built from explicit pages (page 2 is the root)
+    fn read_names(pages: Vec<Vec<u8>>) -> Result<Vec<String>> {
+        let mut data = schema_page(&[("CREATE TABLE names (name TEXT)", 2)], pages.len() as u32 + 1);
+        data.extend(pages.concat());
+        let database = SqliteDatabase::open(&data)?;
+        let table = database.read_table("names")?;
+        Ok(table.records().filter_map(|row| row.text("name")).collect())
+    }
+
+    fn name_cell(rowid: i64, name: &str) -> Vec<u8> {
+        table_cell(rowid, record(&[SqliteValue::Text(name.to_string())]))
+    }
+
+    #[test]
+    fn test_interior_page() {
+        let names = read_names(vec![
+            interior_page(&[(3, 1), (4, 3)], 5),
+            leaf_page(0, vec![name_cell(1, "a")]),
+            leaf_page(0, vec![name_cell(2, "b"), name_cell(3, "c")]),
+            leaf_page(0, vec![name_cell(4, "d")]),
+        ])
+        .unwrap();
+        assert_eq!(names, vec!["a", "b", "c", "d"]);
+    }
+
+    #[test]
+    fn test_overflow_chain() {
+        let name: String = (0..10_000).map(|i| (b'a' + (i % 26) as u8) as char).collect();
+        let payload = record(&[SqliteValue::Text(name.clone())]);
+        // Local part for a 4096-byte usable page, then two full overflow pages
+        let min_local = (PAGE_SIZE - 12) * 32 / 255 - 23;
+        let local = min_local + (payload.len() - min_local) % (PAGE_SIZE - 4);
+        let mut cell = encode_varint(payload.len() as u64);
+        cell.extend(encode_varint(1));
+        cell.extend_from_slice(&payload[..local]);
+        cell.extend(3u32.to_be_bytes());
+
+        let mut pages = vec![leaf_page(0, vec![cell])];
+        for (i, chunk) in payload[local..].chunks(PAGE_SIZE - 4).enumerate() {
+            let next = if local + (i + 1) * (PAGE_SIZE - 4) < payload.len() { i as u32 + 4 } else { 0 };
+            let mut page = next.to_be_bytes().to_vec();
+            page.extend_from_slice(chunk);
+            page.resize(PAGE_SIZE, 0);
+            pages.push(page);
+        }
+        assert_eq!(pages.len(), 3);
+
+        assert_eq!(read_names(pages).unwrap(), vec![name]);
+    }
+
+    #[test]
+    fn test_self_referencing_page() {
+        // Every child pointer leads back to the root, which must only be read once
+        let names = read_names(vec![
+            interior_page(&[(2, 1), (2, 2), (2, 3)], 3),
+            leaf_page(0, vec![name_cell(1, "a")]),
+        ])
+        .unwrap();
+        assert_eq!(names, vec!["a"]);
+    }
+}
@@ -0,0 +1,253 @@
+//! System Resource Usage Monitor (SRUDB.dat) parsing module
+//!
+//! Supports parsing of:
+//! - SruDbIdMapTable application paths, service names and user SIDs
+//! - Network data usage (bytes sent and received per application, user and interface)
+//! - Application resource usage (CPU cycle times, foreground time, disk I/O)
+//! - Network connectivity (connection start and duration per interface)
+//!
+//! Records are written in hourly buckets and kept for 30 to 60 days.
+
+use crate::error::Result;
+use crate::ese::{ole_time, EseDatabase, Row};
+use crate::registry::{filetime_to_datetime, format_sid, utf16_string};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Table mapping AppId/UserId values to application names and SIDs
+const ID_MAP_TABLE: &str = "SruDbIdMapTable";
+/// IdType of user SIDs in the ID map
+const ID_TYPE_SID: i64 = 3;
+
+/// SRUM extension tables mapped to events
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SrumTable {
+    NetworkUsage,
+    ApplicationResourceUsage,
+    NetworkConnectivity,
+}
+
+impl SrumTable {
+    const ALL: [SrumTable; 3] = [SrumTable::NetworkUsage, SrumTable::ApplicationResourceUsage, SrumTable::NetworkConnectivity];
+
+    /// Extension GUID used as the table name
+    pub fn guid(&self) -> &'static str {
+        match self {
+            SrumTable::NetworkUsage => "{973F5D5C-1D90-4944-BE8E-24B94231A174}",
+            SrumTable::ApplicationResourceUsage => "{D10CA2FE-6FCF-4F6D-848E-B2E99266FA89}",
+            SrumTable::NetworkConnectivity => "{DD6636C4-8929-4683-974E-22C046A43763}",
+        }
+    }
+
+    /// Readable table name
+    pub fn name(&self) -> &'static str {
+        match self {
+            SrumTable::NetworkUsage => "Network Usage",
+            SrumTable::ApplicationResourceUsage => "Application Resource Usage",
+            SrumTable::NetworkConnectivity => "Network Connectivity",
+        }
+    }
+
+    /// Counter columns reported for the table, with their labels
+    fn counters(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            SrumTable::NetworkUsage => &[("BytesSent", "Bytes Sent"), ("BytesRecvd", "Bytes Received")],
+            SrumTable::ApplicationResourceUsage => &[
+                ("ForegroundCycleTime", "Foreground Cycle Time"),
+                ("BackgroundCycleTime", "Background Cycle Time"),
+                ("ForegroundBytesRead", "Foreground Bytes Read"),
+                ("ForegroundBytesWritten", "Foreground Bytes Written"),
+                ("BackgroundBytesRead", "Background Bytes Read"),
+                ("BackgroundBytesWritten", "Background Bytes Written"),
+            ],
+            SrumTable::NetworkConnectivity => &[],
+        }
+    }
+}
+
+/// One hourly SRUM record
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SrumRecord {
+    /// Source table
+    pub table: SrumTable,
+    /// AutoIncId of the record
+    pub id: i64,
+    /// Time the record was written (end of the usage period)
+    pub timestamp: Option<DateTime<Utc>>,
+    /// Application path or service name from the ID map
+    pub application: Option<String>,
+    /// User SID from the ID map
+    pub user_sid: Option<String>,
+    /// Network interface type from the interface LUID
+    pub interface: Option<String>,
+    /// Wireless or mobile profile index
+    pub profile_id: Option<i64>,
+    /// Connection start (network connectivity)
+    pub connect_start: Option<DateTime<Utc>>,
+    /// Seconds connected (network connectivity)
+    pub connected_seconds: Option<i64>,
+    /// Seconds the application was in the foreground (application resource usage)
+    pub face_time_seconds: Option<f64>,
+    /// Table-specific counters
+    pub counters: Vec<(String, i64)>,
+}
+
+impl SrumRecord {
+    /// Labelled values for event metadata
+    pub fn fields(&self) -> Vec<(String, String)> {
+        let mut fields = vec![("Table".to_string(), self.table.name().to_string())];
+        let optional = [
+            ("Application", self.application.clone()),
+            ("SID", self.user_sid.clone()),
+            ("Interface", self.interface.clone()),
+            ("L2 Profile ID", self.profile_id.filter(|&p| p != 0).map(|p| p.to_string())),
+            ("Connect Start", self.connect_start.map(|t| t.to_rfc3339())),
+            ("Connected Time", self.connected_seconds.map(|s| format!("{}s", s))),
+            ("Face Time", self.face_time_seconds.map(|s| format!("{:.1}s", s))),
+        ];
+        fields.extend(optional.into_iter().filter_map(|(label, value)| Some((label.to_string(), value?))));
+        fields.extend(self.counters.iter().map(|(label, value)| (label.clone(), value.to_string())));
+        fields.push(("Record ID".to_string(), self.id.to_string()));
+        fields
+    }
+
+    fn from_row(row: &Row, table: SrumTable, ids: &HashMap<i64, String>) -> Self {
+        let lookup = |column: &str| row.integer(column).and_then(|id| ids.get(&id).cloned());
+        SrumRecord {
+            table,
+            id: row.integer("AutoIncId").unwrap_or(0),
+            timestamp: row.real("TimeStamp").and_then(ole_time),
+            application: lookup("AppId"),
+            user_sid: lookup("UserId"),
+            interface: row.integer("InterfaceLuid").map(interface_type),
+            profile_id: row.integer("L2ProfileId"),
+            connect_start: row.integer("ConnectStartTime").and_then(|t| filetime_to_datetime(t as u64)),
+            connected_seconds: row.integer("ConnectedTime"),
+            // FaceTime is in 100-nanosecond units
+            face_time_seconds: row.integer("FaceTime").map(|t| t as f64 / 10_000_000.0),
+            counters: table
+                .counters()
+                .iter()
+                .filter_map(|(column, label)| Some((label.to_string(), row.integer(column)?)))
+                .collect(),
+        }
+    }
+}
+
+/// Parse the network usage, application resource usage and network connectivity tables of SRUDB.dat
+pub fn parse_srum(data: &[u8]) -> Result<Vec<SrumRecord>> {
+    let database = EseDatabase::open(data)?;
+    let ids = id_map(&database)?;
+
+    let mut records = Vec::new();
+    for table in SrumTable::ALL {
+        if database.table_schema(table.guid()).is_none() {
+            continue;
+        }
+        let rows = database.read_table(table.guid())?;
+        records.extend(rows.records().map(|row| SrumRecord::from_row(&row, table, &ids)));
+    }
+    Ok(records)
+}
+
+/// Map IdIndex values to application names and SIDs
+fn id_map(database: &EseDatabase) -> Result<HashMap<i64, String>> {
+    if database.table_schema(ID_MAP_TABLE).is_none() {
+        return Ok(HashMap::new());
+    }
+    let table = database.read_table(ID_MAP_TABLE)?;
+    Ok(table
+        .records()
+        .filter_map(|row| {
+            let blob = row.blob("IdBlob")?;
+            let value = match row.integer("IdType") {
+                Some(ID_TYPE_SID) => format_sid(blob),
+                _ => utf16_string(blob),
+            };
+            Some((row.integer("IdIndex")?, value)).filter(|(_, value)| !value.is_empty())
+        })
+        .collect())
+}
+
+/// Name the interface type held in the top 16 bits of an interface LUID (IANA ifType)
+fn interface_type(luid: i64) -> String {
+    match (luid as u64) >> 48 {
+        6 => "Ethernet".to_string(),
+        23 => "PPP".to_string(),
+        24 => "Loopback".to_string(),
+        71 => "Wi-Fi".to_string(),
+        131 => "Tunnel".to_string(),
+        243 | 244 => "Mobile Broadband".to_string(),
+        other => format!("Interface Type {}", other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ese::tests::{build_database, column};
+    use crate::ese::{EseValue, COLUMN_DATE_TIME, COLUMN_LONG, COLUMN_LONG_BINARY, COLUMN_LONG_LONG, COLUMN_UNSIGNED_BYTE};
+    use crate::registry::test_hive::utf16;
+
+    #[test]
+    fn test_interface_type() {
+        assert_eq!(interface_type(71 << 48 | 1), "Wi-Fi");
+        assert_eq!(interface_type(6 << 48), "Ethernet");
+        assert_eq!(interface_type(1 << 48), "Interface Type 1");
+    }
+
+    #[test]
+    fn test_parse_srum() {
+        let id_map = vec![column(1, "IdType", COLUMN_UNSIGNED_BYTE), column(2, "IdIndex", COLUMN_LONG), column(256, "IdBlob", COLUMN_LONG_BINARY)];
+        let sid = vec![1, 5, 0, 0, 0, 0, 0, 5, 21, 0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 0xE9, 0x03, 0, 0];
+        let long_path = "\\Device\\HarddiskVolume3\\Users\\alice\\AppData\\Local\\Programs\\Tool\\tool.exe";
+        let network = vec![
+            column(1, "AutoIncId", COLUMN_LONG),
+            column(2, "TimeStamp", COLUMN_DATE_TIME),
+            column(3, "AppId", COLUMN_LONG),
+            column(4, "UserId", COLUMN_LONG),
+            column(5, "InterfaceLuid", COLUMN_LONG_LONG),
+            column(6, "L2ProfileId", COLUMN_LONG),
+            column(7, "L2ProfileFlags", COLUMN_LONG),
+            column(8, "BytesSent", COLUMN_LONG_LONG),
+            column(9, "BytesRecvd", COLUMN_LONG_LONG),
+        ];
+        let data = build_database(&[
+            (
+                ID_MAP_TABLE,
+                id_map,
+                vec![
+                    vec![EseValue::Integer(0), EseValue::Integer(10), EseValue::Binary(utf16(long_path))],
+                    vec![EseValue::Integer(ID_TYPE_SID), EseValue::Integer(11), EseValue::Binary(sid)],
+                ],
+            ),
+            (
+                SrumTable::NetworkUsage.guid(),
+                network,
+                vec![vec![
+                    EseValue::Integer(1),
+                    EseValue::Real(44197.5),
+                    EseValue::Integer(10),
+                    EseValue::Integer(11),
+                    EseValue::Integer(71 << 48 | 1),
+                    EseValue::Integer(3),
+                    EseValue::Integer(0),
+                    EseValue::Integer(1024),
+                    EseValue::Integer(4096),
+                ]],
+            ),
+        ]);
+
+        let records = parse_srum(&data).unwrap();
+        assert_eq!(records.len(), 1);
+        let record = &records[0];
+        assert_eq!(record.table, SrumTable::NetworkUsage);
+        assert_eq!(record.timestamp.unwrap().timestamp(), 1_609_502_400);
+        assert_eq!(record.application.as_deref(), Some(long_path));
+        assert_eq!(record.user_sid.as_deref(), Some("S-1-5-21-1-2-3-1001"));
+        assert_eq!(record.interface.as_deref(), Some("Wi-Fi"));
+        assert_eq!(record.counters, vec![("Bytes Sent".to_string(), 1024), ("Bytes Received".to_string(), 4096)]);
+        assert!(record.fields().contains(&("SID".to_string(), "S-1-5-21-1-2-3-1001".to_string())));
+    }
+}
@@ -0,0 +1,307 @@
+//! System context derived from SYSTEM and SOFTWARE hives
+//!
+//! Supports parsing of:
+//! - Control\TimeZoneInformation (zone name and bias)
+//! - Control\ComputerName\ComputerName
+//! - Microsoft\Windows NT\CurrentVersion (product, build, install date, owner)
+//! - Microsoft\Windows NT\CurrentVersion\ProfileList (SID to profile mapping)
+
+use crate::registry::{filetime_to_datetime, RegistryHive};
+use crate::types::Event;
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+
+/// Metadata label giving the time basis of an event's timestamps
+pub const TIME_BASIS: &str = "Time Basis";
+/// Time basis of events whose timestamps are local system time stored as if they were UTC
+pub const LOCAL_SYSTEM_TIME: &str = "Local system time";
+
+/// Time zone configuration from TimeZoneInformation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeZoneSetting {
+    /// Windows time zone key name (e.g. "Pacific Standard Time")
+    pub key_name: Option<String>,
+    /// Standard time name
+    pub standard_name: Option<String>,
+    /// Bias in minutes (UTC = local time + bias)
+    pub bias: Option<i32>,
+    /// Active bias in minutes at the time the hive was written
+    pub active_bias: Option<i32>,
+}
+
+impl TimeZoneSetting {
+    /// Windows zone name, preferring TimeZoneKeyName over StandardName
+    pub fn name(&self) -> Option<&str> {
+        self.key_name.as_deref().or(self.standard_name.as_deref())
+    }
+
+    /// Resolve to a display timezone (by zone name, falling back to the bias)
+    pub fn to_tz(&self) -> Option<Tz> {
+        if let Some(tz) = self.name().and_then(crate::datetime::windows_timezone_to_tz) {
+            return Some(tz);
+        }
+        let bias = self.bias?;
+        if bias % 60 != 0 {
+            return None;
+        }
+        crate::datetime::parse_timezone(&format!("UTC{:+}", -bias / 60)).ok()
+    }
+
+    /// Convert a local system time (stored as if it were UTC) to UTC
+    pub fn local_to_utc(&self, local: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let naive = local.naive_utc();
+        self.to_tz()
+            .and_then(|tz| tz.from_local_datetime(&naive).earliest())
+            .map(|t| t.with_timezone(&Utc))
+            .or_else(|| Some(local + Duration::minutes(self.bias? as i64)))
+    }
+}
+
+/// User profile from ProfileList
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserProfile {
+    /// User SID
+    pub sid: String,
+    /// Profile directory (ProfileImagePath)
+    pub profile_path: String,
+    /// User name (profile directory name)
+    pub user: String,
+}
+
+/// System context gathered from the available hives
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SystemContext {
+    /// Computer name
+    pub computer_name: Option<String>,
+    /// Product name (e.g. "Windows 10 Pro")
+    pub product_name: Option<String>,
+    /// Display version / release ID (e.g. "22H2")
+    pub display_version: Option<String>,
+    /// Build number, including the update build revision when present
+    pub build: Option<String>,
+    /// Operating system install date
+    pub install_date: Option<DateTime<Utc>>,
+    /// Registered owner
+    pub registered_owner: Option<String>,
+    /// Time zone configuration
+    pub timezone: Option<TimeZoneSetting>,
+    /// User profiles
+    pub profiles: Vec<UserProfile>,
+}
+
+impl SystemContext {
+    /// Build the context from any combination of hives
+    pub fn from_hives(hives: &[&RegistryHive]) -> Self {
+        let mut context = SystemContext::default();
+
+        for hive in hives {
+            if let Some(control_set) = hive.current_control_set() {
+                if let Some(key) = hive.open_key(&format!("{}\\Control\\TimeZoneInformation", control_set)) {
+                    context.timezone = Some(TimeZoneSetting {
+                        key_name: key.string_value("TimeZoneKeyName"),
+                        standard_name: key.string_value("StandardName"),
+                        bias: key.value("Bias").and_then(|v| v.dword_data()).map(|b| b as i32),
+                        active_bias: key.value("ActiveTimeBias").and_then(|v| v.dword_data()).map(|b| b as i32),
+                    });
+                }
+                if let Some(key) = hive.open_key(&format!("{}\\Control\\ComputerName\\ComputerName", control_set)) {
+                    context.computer_name = key.string_value("ComputerName");
+                }
+            }
+
+            if let Some(key) = hive.open_key("Microsoft\\Windows NT\\CurrentVersion") {
+                context.product_name = key.string_value("ProductName");
+                context.display_version =
+                    key.string_value("DisplayVersion").or_else(|| key.string_value("ReleaseId"));
+                context.build = key.string_value("CurrentBuildNumber")
+                    .or_else(|| key.string_value("CurrentBuild"))
+                    .map(|build| match key.value("UBR").and_then(|v| v.dword_data()) {
+                        Some(ubr) => format!("{}.{}", build, ubr),
+                        None => build,
+                    });
+                context.install_date = key
+                    .value("InstallTime")
+                    .and_then(|v| v.qword_data())
+                    .and_then(filetime_to_datetime)
+                    .or_else(|| {
+                        key.value("InstallDate")
+                            .and_then(|v| v.dword_data())
+                            .filter(|&d| d != 0)
+                            .and_then(|d| DateTime::from_timestamp(d as i64, 0))
+                    });
+                context.registered_owner = key.string_value("RegisteredOwner");
+
+                if let Some(profiles) = key.subkey("ProfileList") {
+                    context.profiles = profiles
+                        .subkeys
+                        .iter()
+                        .filter_map(|profile| {
+                            let profile_path = profile.string_value("ProfileImagePath")?;
+                            let user = profile_path.rsplit(['\\', '/']).next().unwrap_or(&profile_path).to_string();
+                            Some(UserProfile {
+                                sid: profile.name.clone(),
+                                profile_path,
+                                user,
+                            })
+                        })
+                        .collect();
+                }
+            }
+        }
+
+        context
+    }
+
+    /// Check whether any context was found
+    pub fn is_empty(&self) -> bool {
+        self.computer_name.is_none()
+            && self.product_name.is_none()
+            && self.timezone.is_none()
+            && self.profiles.is_empty()
+    }
+
+    /// Convert events recorded in local system time to UTC using the system time zone
+    pub fn convert_local_times(&self, records: &mut [Event]) {
+        let Some(timezone) = &self.timezone else {
+            return;
+        };
+        let zone = timezone.name().unwrap_or("system time zone").to_string();
+
+        for record in records.iter_mut() {
+            let Some(basis) = record.metadata.iter_mut().find(|(k, v)| k == TIME_BASIS && v == LOCAL_SYSTEM_TIME) else {
+                continue;
+            };
+            basis.1 = format!("UTC (converted from {} local time)", zone);
+            let timestamps = &mut record.timestamps;
+            for timestamp in [&mut timestamps.created, &mut timestamps.modified, &mut timestamps.accessed, &mut timestamps.mft_modified] {
+                *timestamp = timestamp.and_then(|t| timezone.local_to_utc(t));
+            }
+        }
+    }
+
+    /// Find the profile owning an NTUSER.DAT / UsrClass.dat hive by its path
+    pub fn profile_for_hive(&self, hive_path: &str) -> Option<&UserProfile> {
+        let user = hive_owner_directory(hive_path)?;
+        self.profiles.iter().find(|p| p.user.eq_ignore_ascii_case(&user))
+    }
+
+    /// One-line summary for headers and progress output
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(name) = &self.computer_name {
+            parts.push(name.clone());
+        }
+        let os: Vec<&str> = [&self.product_name, &self.display_version]
+            .into_iter()
+            .filter_map(|p| p.as_deref())
+            .collect();
+        if !os.is_empty() {
+            parts.push(os.join(" "));
+        }
+        if let Some(build) = &self.build {
+            parts.push(format!("build {}", build));
+        }
+        if let Some(zone) = self.timezone.as_ref().and_then(|tz| tz.name()) {
+            parts.push(zone.to_string());
+        }
+        if !self.profiles.is_empty() {
+            parts.push(format!("{} profiles", self.profiles.len()));
+        }
+        parts.join(" | ")
+    }
+}
+
+/// Profile directory name for a per-user hive (Users\<name>\NTUSER.DAT or ...\UsrClass.dat)
+pub fn hive_owner_directory(hive_path: &str) -> Option<String> {
+    let parts: Vec<&str> = hive_path.split(['/', '\\']).filter(|p| !p.is_empty()).collect();
+    let file = parts.last()?.to_lowercase();
+    if file.contains("ntuser") {
+        parts.len().checked_sub(2).map(|i| parts[i].to_string())
+    } else if file.contains("usrclass") {
+        // Users\<name>\AppData\Local\Microsoft\Windows\UsrClass.dat
+        let users = parts.iter().rposition(|p| p.eq_ignore_ascii_case("Users"))?;
+        parts.get(users + 1).map(|p| p.to_string())
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hive_owner_directory() {
+        assert_eq!(hive_owner_directory("C/Users/alice/NTUSER.DAT").as_deref(), Some("alice"));
+        assert_eq!(
+            hive_owner_directory("C:\\Users\\bob\\AppData\\Local\\Microsoft\\Windows\\UsrClass.dat").as_deref(),
+            Some("bob")
+        );
+        assert_eq!(hive_owner_directory("C/Windows/System32/config/SYSTEM"), None);
+    }
+
+    #[test]
+    fn test_timezone_setting_to_tz() {
+        let named = TimeZoneSetting {
+            key_name: Some("Pacific Standard Time".to_string()),
+            standard_name: None,
+            bias: Some(480),
+            active_bias: None,
+        };
+        assert_eq!(named.to_tz(), Some(Tz::America__Los_Angeles));
+
+        // Unknown names fall back to the bias
+        let biased = TimeZoneSetting {
+            key_name: Some("Custom Zone".to_string()),
+            standard_name: None,
+            bias: Some(-480),
+            active_bias: None,
+        };
+        assert_eq!(biased.to_tz(), Some(Tz::Asia__Hong_Kong));
+    }
+
+    #[test]
+    fn test_convert_local_times() {
+        let context = SystemContext {
+            timezone: Some(TimeZoneSetting {
+                key_name: Some("Pacific Standard Time".to_string()),
+                standard_name: None,
+                bias: Some(480),
+                active_bias: Some(420),
+            }),
+            ..Default::default()
+        };
+        let local = |s: &str| DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc);
+        let mut record = Event::default();
+        record.timestamps.created = Some(local("2023-01-10T09:00:00Z"));
+        record.timestamps.modified = Some(local("2023-03-14T09:26:53Z"));
+        record.metadata.push((TIME_BASIS.to_string(), LOCAL_SYSTEM_TIME.to_string()));
+        let mut untouched = record.clone();
+        untouched.metadata.clear();
+
+        let mut records = vec![record, untouched];
+        context.convert_local_times(&mut records);
+
+        // Standard time is UTC-8, daylight saving time UTC-7
+        assert_eq!(records[0].timestamps.created.unwrap().to_rfc3339(), "2023-01-10T17:00:00+00:00");
+        assert_eq!(records[0].timestamps.modified.unwrap().to_rfc3339(), "2023-03-14T16:26:53+00:00");
+        assert!(records[0].metadata[0].1.contains("Pacific Standard Time"));
+        assert_eq!(records[1].timestamps.created.unwrap().to_rfc3339(), "2023-01-10T09:00:00+00:00");
+    }
+
+    #[test]
+    fn test_profile_for_hive() {
+        let context = SystemContext {
+            profiles: vec![UserProfile {
+                sid: "S-1-5-21-1-2-3-1001".to_string(),
+                profile_path: "C:\\Users\\Alice".to_string(),
+                user: "Alice".to_string(),
+            }],
+            ..Default::default()
+        };
+        let profile = context.profile_for_hive("evidence/C/Users/alice/NTUSER.DAT").unwrap();
+        assert_eq!(profile.sid, "S-1-5-21-1-2-3-1001");
+        assert!(context.profile_for_hive("C/Users/bob/NTUSER.DAT").is_none());
+    }
+}
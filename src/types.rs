@@ -34,6 +34,9 @@ pub struct Event {
     pub location: Option<String>,
     /// Event source type (MFT, LNK, Registry, etc.)
     pub event_source: Option<String>,
+    /// Source-specific details as name/value pairs (MRU position, key path, etc.)
+    #[serde(default)]
+    pub metadata: Vec<(String, String)>,
 }
 
 /// Timestamp collection for forensic timeline events
@@ -161,6 +164,108 @@ impl TimestampType {
                 TimestampType::Accessed => "Jumplist entry accessed",
                 TimestampType::MftModified => "Jumplist entry modified", // MftModified not applicable to Jumplist
             },
+            Some("ShimCache") => match self {
+                TimestampType::Created => "ShimCache entry created",
+                TimestampType::Modified => "ShimCache file modified",
+                TimestampType::Accessed => "ShimCache entry accessed",
+                TimestampType::MftModified => "ShimCache file modified", // MftModified not applicable to ShimCache
+            },
+            Some("Amcache") => match self {
+                TimestampType::Created => "Amcache link/install date",
+                TimestampType::Modified => "Amcache entry written",
+                TimestampType::Accessed => "Amcache entry accessed",
+                TimestampType::MftModified => "Amcache entry written", // MftModified not applicable to Amcache
+            },
+            Some("BAM") | Some("DAM") => match self {
+                TimestampType::Created => "Program executed",
+                TimestampType::Modified => "Program last executed",
+                TimestampType::Accessed => "Program last executed",
+                TimestampType::MftModified => "Program last executed", // MftModified not applicable to BAM/DAM
+            },
+            Some("USB") => match self {
+                TimestampType::Created => "USB device first installed",
+                TimestampType::Modified => "USB device connected",
+                TimestampType::Accessed => "USB device removed",
+                TimestampType::MftModified => "USB device connected", // MftModified not applicable to USB
+            },
+            Some("SAM") => match self {
+                TimestampType::Created => "Account created",
+                TimestampType::Modified => "Account activity",
+                TimestampType::Accessed => "Account activity",
+                TimestampType::MftModified => "Account activity", // MftModified not applicable to SAM
+            },
+            Some("Prefetch") => match self {
+                TimestampType::Created => "Program executed",
+                TimestampType::Modified => "Program executed",
+                TimestampType::Accessed => "Program executed",
+                TimestampType::MftModified => "Program executed", // MftModified not applicable to Prefetch
+            },
+            Some("EVTX") => match self {
+                TimestampType::Created => "Event logged",
+                TimestampType::Modified => "Event logged",
+                TimestampType::Accessed => "Event logged",
+                TimestampType::MftModified => "Event logged", // MftModified not applicable to EVTX
+            },
+            Some("RecycleBin") => match self {
+                TimestampType::Created => "File deleted to Recycle Bin",
+                TimestampType::Modified => "File deleted to Recycle Bin",
+                TimestampType::Accessed => "File deleted to Recycle Bin",
+                TimestampType::MftModified => "File deleted to Recycle Bin", // MftModified not applicable to RecycleBin
+            },
+            Some("Network") => match self {
+                TimestampType::Created => "Network first connected",
+                TimestampType::Modified => "Network last connected",
+                TimestampType::Accessed => "Network last connected",
+                TimestampType::MftModified => "Network last connected", // MftModified not applicable to Network
+            },
+            Some("ObjectID") => match self {
+                TimestampType::Created => "Object ID created",
+                TimestampType::Modified => "Object ID created",
+                TimestampType::Accessed => "Object ID created",
+                TimestampType::MftModified => "Object ID created", // MftModified not applicable to ObjectID
+            },
+            Some("PropertyStore") => match self {
+                TimestampType::Created => "Property timestamp",
+                TimestampType::Modified => "Property timestamp",
+                TimestampType::Accessed => "Property timestamp",
+                TimestampType::MftModified => "Property timestamp", // MftModified not applicable to PropertyStore
+            },
+            Some("WindowsTimeline") => match self {
+                TimestampType::Created => "Activity started",
+                TimestampType::Modified => "Activity modified",
+                TimestampType::Accessed => "Activity ended",
+                TimestampType::MftModified => "Activity modified", // MftModified not applicable to WindowsTimeline
+            },
+            Some("ScheduledTask") => match self {
+                TimestampType::Created => "Task registered",
+                TimestampType::Modified => "Task last succeeded",
+                TimestampType::Accessed => "Task last run",
+                TimestampType::MftModified => "Task last run", // MftModified not applicable to ScheduledTask
+            },
+            Some("SRUM") => match self {
+                TimestampType::Created => "Connection started",
+                TimestampType::Modified => "Usage recorded",
+                TimestampType::Accessed => "Usage recorded",
+                TimestampType::MftModified => "Usage recorded", // MftModified not applicable to SRUM
+            },
+            Some("Browser") => match self {
+                TimestampType::Created => "Download started",
+                TimestampType::Modified => "Download finished",
+                TimestampType::Accessed => "URL visited",
+                TimestampType::MftModified => "Download finished", // MftModified not applicable to Browser
+            },
+            Some("Office") => match self {
+                TimestampType::Created => "Document created",
+                TimestampType::Modified => "Document last saved",
+                TimestampType::Accessed => "Document last printed",
+                TimestampType::MftModified => "Document last saved", // MftModified not applicable to Office
+            },
+            Some("Persistence") => match self {
+                TimestampType::Created => "Autostart entry created",
+                TimestampType::Modified => "Autostart key written",
+                TimestampType::Accessed => "Autostart key written",
+                TimestampType::MftModified => "Autostart key written", // MftModified not applicable to Persistence
+            },
             _ => self.display_name(), // Default to MFT descriptions for MFT events or unknown sources
         }
     }
@@ -0,0 +1,444 @@
+//! USB device history analysis across SYSTEM, SOFTWARE and NTUSER.DAT hives
+//!
+//! Supports joining of:
+//! - Enum\USBSTOR and Enum\USB device instances (with Properties install/arrival/removal times)
+//! - MountedDevices volume GUIDs and drive letters
+//! - Windows Portable Devices friendly names and EMDMgmt volume labels/serials
+//! - Per-user MountPoints2 volume GUIDs
+
+use crate::registry::{
+    filetime_to_datetime, utf16_string, RegistryEventType, RegistryHive, RegistryKey, RegistryTimelineEvent,
+};
+use crate::system_context::hive_owner_directory;
+use crate::types::EventTimestamps;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Device property set holding install/arrival/removal FILETIMEs
+const DEVICE_PROPERTY_GUID: &str = "{83da6326-97a6-4088-9453-a1923f573b29}";
+/// First install date property
+const PROPERTY_FIRST_INSTALL: u32 = 0x64;
+/// Last arrival date property
+const PROPERTY_LAST_ARRIVAL: u32 = 0x66;
+/// Last removal date property
+const PROPERTY_LAST_REMOVAL: u32 = 0x67;
+
+/// Joined history for one USB storage device
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UsbDevice {
+    /// Device instance ID (serial number, with "&0" suffix when Windows generated it)
+    pub instance_id: String,
+    /// Serial number (None when Windows generated the instance ID)
+    pub serial: Option<String>,
+    /// Vendor from the USBSTOR device class key
+    pub vendor: Option<String>,
+    /// Product from the USBSTOR device class key
+    pub product: Option<String>,
+    /// Revision from the USBSTOR device class key
+    pub revision: Option<String>,
+    /// USB vendor ID (from Enum\USB)
+    pub vid: Option<String>,
+    /// USB product ID (from Enum\USB)
+    pub pid: Option<String>,
+    /// Device friendly name
+    pub friendly_name: Option<String>,
+    /// Volume label (Windows Portable Devices / EMDMgmt)
+    pub volume_name: Option<String>,
+    /// Volume serial number (EMDMgmt)
+    pub volume_serial: Option<String>,
+    /// Volume GUID (MountedDevices)
+    pub volume_guid: Option<String>,
+    /// Last assigned drive letter (MountedDevices)
+    pub drive_letter: Option<String>,
+    /// First install time
+    pub first_install: Option<DateTime<Utc>>,
+    /// Last arrival (connect) time
+    pub last_arrival: Option<DateTime<Utc>>,
+    /// Last removal (disconnect) time
+    pub last_removal: Option<DateTime<Utc>>,
+    /// Users that mounted the volume, with the MountPoints2 key last write time
+    pub user_mounts: Vec<(String, Option<DateTime<Utc>>)>,
+}
+
+impl UsbDevice {
+    /// Human-readable device name
+    pub fn display_name(&self) -> String {
+        if let Some(name) = &self.friendly_name {
+            return name.clone();
+        }
+        let parts: Vec<&str> = [&self.vendor, &self.product]
+            .into_iter()
+            .filter_map(|p| p.as_deref())
+            .collect();
+        if parts.is_empty() {
+            self.instance_id.clone()
+        } else {
+            parts.join(" ")
+        }
+    }
+}
+
+/// Build one record per USB storage device from any combination of hives
+pub fn parse_usb_devices(hives: &[&RegistryHive]) -> Vec<UsbDevice> {
+    let mut devices = Vec::new();
+
+    for hive in hives {
+        let Some(control_set) = hive.current_control_set() else {
+            continue;
+        };
+        let Some(usbstor) = hive.open_key(&format!("{}\\Enum\\USBSTOR", control_set)) else {
+            continue;
+        };
+        let usb = hive.open_key(&format!("{}\\Enum\\USB", control_set));
+        let mounted = hive.open_key("MountedDevices");
+
+        for class in &usbstor.subkeys {
+            let (vendor, product, revision) = parse_device_class(&class.name);
+            for instance in &class.subkeys {
+                let mut device = UsbDevice {
+                    instance_id: instance.name.clone(),
+                    serial: serial_from_instance(&instance.name),
+                    vendor: vendor.clone(),
+                    product: product.clone(),
+                    revision: revision.clone(),
+                    friendly_name: instance.string_value("FriendlyName"),
+                    first_install: property_time(instance, PROPERTY_FIRST_INSTALL),
+                    last_arrival: property_time(instance, PROPERTY_LAST_ARRIVAL),
+                    last_removal: property_time(instance, PROPERTY_LAST_REMOVAL),
+                    ..Default::default()
+                };
+
+                if let Some(usb) = &usb {
+                    join_usb_enum(&mut device, usb);
+                }
+                if let Some(mounted) = &mounted {
+                    join_mounted_devices(&mut device, mounted);
+                }
+                devices.push(device);
+            }
+        }
+    }
+
+    for hive in hives {
+        if let Some(wpd) = hive.open_key("Microsoft\\Windows Portable Devices\\Devices") {
+            for device in &mut devices {
+                let id = device.instance_id.to_uppercase();
+                if let Some(key) = wpd.subkeys.iter().find(|k| k.name.to_uppercase().contains(&id)) {
+                    if let Some(name) = key.string_value("FriendlyName") {
+                        device.volume_name = Some(name);
+                    }
+                }
+            }
+        }
+
+        if let Some(emd) = hive.open_key("Microsoft\\Windows NT\\CurrentVersion\\EMDMgmt") {
+            for device in &mut devices {
+                let id = device.instance_id.to_uppercase();
+                if let Some(key) = emd.subkeys.iter().find(|k| k.name.to_uppercase().contains(&id)) {
+                    let (label, serial) = parse_emd_name(&key.name);
+                    if device.volume_name.is_none() {
+                        device.volume_name = label;
+                    }
+                    device.volume_serial = serial;
+                }
+            }
+        }
+
+        if let Some(mount_points) = hive.open_key("Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\MountPoints2") {
+            let user = hive_owner_directory(&hive.file_path).unwrap_or_else(|| hive.file_path.clone());
+            for device in &mut devices {
+                let Some(guid) = &device.volume_guid else {
+                    continue;
+                };
+                if let Some(key) = mount_points.subkeys.iter().find(|k| k.name.eq_ignore_ascii_case(guid)) {
+                    device.user_mounts.push((user.clone(), key.last_written));
+                }
+            }
+        }
+    }
+
+    devices
+}
+
+/// Split a USBSTOR class key name (Disk&Ven_X&Prod_Y&Rev_Z) into vendor, product and revision
+fn parse_device_class(name: &str) -> (Option<String>, Option<String>, Option<String>) {
+    let mut vendor = None;
+    let mut product = None;
+    let mut revision = None;
+    for part in name.split('&') {
+        let field = |prefix: &str| {
+            part.get(..prefix.len())
+                .filter(|p| p.eq_ignore_ascii_case(prefix))
+                .map(|_| part[prefix.len()..].replace('_', " ").trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+        vendor = vendor.or_else(|| field("Ven_"));
+        product = product.or_else(|| field("Prod_"));
+        revision = revision.or_else(|| field("Rev_"));
+    }
+    (vendor, product, revision)
+}
+
+/// Instance IDs whose second character is '&' were generated by Windows (no serial number)
+fn serial_from_instance(instance_id: &str) -> Option<String> {
+    if instance_id.chars().nth(1) == Some('&') {
+        return None;
+    }
+    let serial = instance_id.strip_suffix("&0").unwrap_or(instance_id);
+    Some(serial.to_string())
+}
+
+/// Read a Properties\{83da6326-...}\00xx FILETIME (Windows 8+ and Windows 7 layouts)
+fn property_time(instance: &RegistryKey, id: u32) -> Option<DateTime<Utc>> {
+    let properties = instance.subkey("Properties")?.subkey(DEVICE_PROPERTY_GUID)?;
+    let key = properties
+        .subkeys
+        .iter()
+        .find(|k| u32::from_str_radix(&k.name, 16).ok() == Some(id))?;
+    let value = key
+        .value("")
+        .or_else(|| key.subkey("00000000").and_then(|k| k.value("Data")))?;
+    value.qword_data().and_then(filetime_to_datetime)
+}
+
+/// Take VID/PID (and missing timestamps) from Enum\USB\VID_xxxx&PID_yyyy\<serial>
+fn join_usb_enum(device: &mut UsbDevice, usb: &RegistryKey) {
+    let Some(serial) = &device.serial else {
+        return;
+    };
+
+    for class in &usb.subkeys {
+        let Some(instance) = class.subkey(serial) else {
+            continue;
+        };
+        for part in class.name.split('&') {
+            if let Some(vid) = part.strip_prefix("VID_") {
+                device.vid = Some(vid.to_string());
+            } else if let Some(pid) = part.strip_prefix("PID_") {
+                device.pid = Some(pid.to_string());
+            }
+        }
+        device.first_install = device.first_install.or_else(|| property_time(instance, PROPERTY_FIRST_INSTALL));
+        device.last_arrival = device.last_arrival.or_else(|| property_time(instance, PROPERTY_LAST_ARRIVAL));
+        device.last_removal = device.last_removal.or_else(|| property_time(instance, PROPERTY_LAST_REMOVAL));
+        return;
+    }
+}
+
+/// Find the volume GUID and drive letter whose MountedDevices data references the device
+fn join_mounted_devices(device: &mut UsbDevice, mounted: &RegistryKey) {
+    let id = format!("#{}#", device.instance_id.to_uppercase());
+    let mut device_data = None;
+
+    for value in &mounted.values {
+        if !utf16_string(&value.data).to_uppercase().contains(&id) {
+            continue;
+        }
+        if let Some(guid) = value.name.strip_prefix("\\??\\Volume") {
+            device.volume_guid = Some(guid.to_string());
+            device_data = Some(value.data.clone());
+        } else if let Some(letter) = value.name.strip_prefix("\\DosDevices\\") {
+            device.drive_letter = Some(letter.to_string());
+        }
+    }
+
+    // Drive letters may also point at the volume by identical data
+    if device.drive_letter.is_none() {
+        if let Some(data) = device_data {
+            device.drive_letter = mounted
+                .values
+                .iter()
+                .find(|v| v.name.starts_with("\\DosDevices\\") && v.data == data)
+                .map(|v| v.name["\\DosDevices\\".len()..].to_string());
+        }
+    }
+}
+
+/// Parse "..._VolumeLabel_VolumeSerial" EMDMgmt key names (serial is decimal)
+fn parse_emd_name(name: &str) -> (Option<String>, Option<String>) {
+    let tail = name.rsplit('}').next().unwrap_or("");
+    let mut parts = tail.rsplitn(2, '_');
+    let serial = parts
+        .next()
+        .and_then(|s| s.parse::<u32>().ok())
+        .map(|s| format!("{:04X}-{:04X}", s >> 16, s & 0xFFFF));
+    let label = parts
+        .next()
+        .map(|l| l.trim_start_matches('_').to_string())
+        .filter(|l| !l.is_empty());
+    (label, serial)
+}
+
+/// Extract USB device transitions (install, arrival, removal, user mounts) as timeline events
+pub fn extract_timeline_events(hives: &[&RegistryHive]) -> Vec<RegistryTimelineEvent> {
+    let mut events = Vec::new();
+
+    for device in parse_usb_devices(hives) {
+        let name = device.display_name();
+        let mut metadata = vec![("Instance ID".to_string(), device.instance_id.clone())];
+        let fields = [
+            ("Serial", &device.serial),
+            ("Vendor", &device.vendor),
+            ("Product", &device.product),
+            ("Revision", &device.revision),
+            ("VID", &device.vid),
+            ("PID", &device.pid),
+            ("Friendly Name", &device.friendly_name),
+            ("Volume Name", &device.volume_name),
+            ("Volume Serial", &device.volume_serial),
+            ("Volume GUID", &device.volume_guid),
+            ("Drive Letter", &device.drive_letter),
+        ];
+        for (label, value) in fields {
+            if let Some(value) = value {
+                metadata.push((label.to_string(), value.clone()));
+            }
+        }
+
+        let drive = device
+            .drive_letter
+            .as_ref()
+            .map(|d| format!(" ({})", d))
+            .unwrap_or_default();
+
+        // Install, arrival and removal map onto created, modified and accessed
+        events.push(RegistryTimelineEvent {
+            key_path: format!("Enum\\USBSTOR\\{}", device.instance_id),
+            value_name: None,
+            event_type: RegistryEventType::KeyModified,
+            value_type: None,
+            data: None,
+            data_size: None,
+            timestamps: EventTimestamps {
+                created: device.first_install,
+                modified: device.last_arrival,
+                accessed: device.last_removal,
+                mft_modified: None,
+            },
+            description: Some(format!("USB: {}{}", name, drive)),
+            metadata: metadata.clone(),
+            source: Some("USB".to_string()),
+        });
+
+        for (user, mounted) in &device.user_mounts {
+            let mut user_metadata = metadata.clone();
+            user_metadata.push(("User".to_string(), user.clone()));
+            events.push(RegistryTimelineEvent {
+                key_path: format!(
+                    "Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\MountPoints2\\{}",
+                    device.volume_guid.clone().unwrap_or_default()
+                ),
+                value_name: None,
+                event_type: RegistryEventType::KeyModified,
+                value_type: None,
+                data: None,
+                data_size: None,
+                timestamps: EventTimestamps {
+                    created: None,
+                    modified: *mounted,
+                    accessed: None,
+                    mft_modified: None,
+                },
+                description: Some(format!("USB: {}{} mounted by {}", name, drive, user)),
+                metadata: user_metadata,
+                source: Some("USB".to_string()),
+            });
+        }
+    }
+
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::test_hive::{utf16, TestKey};
+    use chrono::Datelike;
+
+    #[test]
+    fn test_parse_device_class() {
+        let (vendor, product, revision) = parse_device_class("Disk&Ven_SanDisk&Prod_Cruzer_Blade&Rev_1.00");
+        assert_eq!(vendor.as_deref(), Some("SanDisk"));
+        assert_eq!(product.as_deref(), Some("Cruzer Blade"));
+        assert_eq!(revision.as_deref(), Some("1.00"));
+    }
+
+    #[test]
+    fn test_serial_from_instance() {
+        assert_eq!(serial_from_instance("4C530001230516117152&0").as_deref(), Some("4C530001230516117152"));
+        assert_eq!(serial_from_instance("7&2a4e3b1f&0"), None);
+    }
+
+    #[test]
+    fn test_parse_emd_name() {
+        let name = "_??_USBSTOR#Disk&Ven_SanDisk&Prod_Cruzer&Rev_1.00#4C53&0#{53f56307-b6bf-11d0-94f2-00a0c91efb8b}EVIDENCE_1234567890";
+        let (label, serial) = parse_emd_name(name);
+        assert_eq!(label.as_deref(), Some("EVIDENCE"));
+        assert_eq!(serial.as_deref(), Some("4996-02D2"));
+    }
+
+    #[test]
+    fn test_parse_usb_devices_across_hives() {
+        let instance_id = "4C530001230516117152&0";
+        let class = "Disk&Ven_SanDisk&Prod_Cruzer&Rev_1.00";
+        let volume_guid = "{1d3e2c4b-5a69-11ee-8c99-0242ac120002}";
+        let device_path = format!(
+            "_??_USBSTOR#{}#{}#{{53f56307-b6bf-11d0-94f2-00a0c91efb8b}}",
+            class.to_uppercase(),
+            instance_id
+        );
+        let filetime = |ft: u64| ft.to_le_bytes().to_vec();
+
+        let properties = TestKey::new(DEVICE_PROPERTY_GUID)
+            .subkey(TestKey::new("0064").value("", 0x10, filetime(132223104000000000)))
+            .subkey(TestKey::new("0066").value("", 0x10, filetime(132539328000000000)));
+        let system = TestKey::new("ROOT")
+            .with("", TestKey::new("Select").dword("Current", 1))
+            .with(
+                &format!("ControlSet001\\Enum\\USBSTOR\\{}", class),
+                TestKey::new(instance_id)
+                    .string("FriendlyName", "SanDisk Cruzer USB Device")
+                    .with("Properties", properties),
+            )
+            .with("ControlSet001\\Enum\\USB\\VID_0781&PID_5567", TestKey::new("4C530001230516117152"))
+            .with(
+                "",
+                TestKey::new("MountedDevices")
+                    .binary(&format!("\\??\\Volume{}", volume_guid), &utf16(&device_path))
+                    .binary("\\DosDevices\\E:", &utf16(&device_path)),
+            )
+            .open("SYSTEM");
+        let software = TestKey::new("ROOT")
+            .with(
+                "Microsoft\\Windows NT\\CurrentVersion\\EMDMgmt",
+                TestKey::new(&format!("{}EVIDENCE_1234567890", device_path)),
+            )
+            .open("SOFTWARE");
+        let ntuser = TestKey::new("ROOT")
+            .with(
+                "Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\MountPoints2",
+                TestKey::new(volume_guid),
+            )
+            .open("C:/Users/alice/NTUSER.DAT");
+
+        let devices = parse_usb_devices(&[&system, &software, &ntuser]);
+        assert_eq!(devices.len(), 1);
+        let device = &devices[0];
+        assert_eq!(device.serial.as_deref(), Some("4C530001230516117152"));
+        assert_eq!(device.vendor.as_deref(), Some("SanDisk"));
+        assert_eq!(device.vid.as_deref(), Some("0781"));
+        assert_eq!(device.pid.as_deref(), Some("5567"));
+        assert_eq!(device.first_install.unwrap().year(), 2020);
+        assert_eq!(device.last_arrival.unwrap().year(), 2021);
+        assert_eq!(device.volume_guid.as_deref(), Some(volume_guid));
+        assert_eq!(device.drive_letter.as_deref(), Some("E:"));
+        assert_eq!(device.volume_name.as_deref(), Some("EVIDENCE"));
+        assert_eq!(device.volume_serial.as_deref(), Some("4996-02D2"));
+        assert_eq!(device.user_mounts.len(), 1);
+        assert_eq!(device.user_mounts[0].0, "alice");
+
+        let events = extract_timeline_events(&[&system, &software, &ntuser]);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[1].description.as_deref(), Some("USB: SanDisk Cruzer USB Device (E:) mounted by alice"));
+    }
+}
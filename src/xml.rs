@@ -0,0 +1,29 @@
+//! Minimal XML text helpers shared by the OOXML and task XML parsers
+
+/// Text content of the first element with the given local name (namespace prefix ignored)
+pub(crate) fn xml_element(xml: &str, local_name: &str) -> Option<String> {
+    let mut rest = xml;
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start + 1..];
+        let tag_end = rest.find('>')?;
+        let tag = &rest[..tag_end];
+        let name = tag.split(|c: char| c.is_whitespace() || c == '/').next().unwrap_or("");
+        let name = name.rsplit(':').next().unwrap_or(name);
+        if name == local_name && !tag.ends_with('/') && !tag.starts_with('/') {
+            let content = &rest[tag_end + 1..];
+            let value = xml_unescape(content[..content.find("</")?].trim());
+            return Some(value).filter(|v| !v.is_empty());
+        }
+        rest = &rest[tag_end..];
+    }
+    None
+}
+
+/// Replace the predefined XML entities
+fn xml_unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
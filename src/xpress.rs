@@ -0,0 +1,224 @@
+//! Xpress Huffman (LZ77 + Huffman) decompression as specified in MS-XCA
+//!
+//! Supports decompression of:
+//! - Xpress Huffman streams (Windows 10+ compressed Prefetch, MAM format)
+
+use crate::error::{Error, Result};
+
+/// Symbols in the Huffman alphabet (256 literals + 256 match symbols)
+const SYMBOL_COUNT: usize = 512;
+/// Maximum Huffman code length
+const MAX_CODE_LENGTH: u32 = 15;
+/// Output produced per Huffman table
+const BLOCK_SIZE: usize = 65536;
+/// Size of the per-block table of 4-bit code lengths
+const TABLE_SIZE: usize = SYMBOL_COUNT / 2;
+
+/// Decompress an Xpress Huffman stream into exactly `output_size` bytes
+pub fn decompress_huffman(input: &[u8], output_size: usize) -> Result<Vec<u8>> {
+    let mut output = Vec::with_capacity(output_size);
+    let mut position = 0;
+
+    while output.len() < output_size {
+        let table = input
+            .get(position..position + TABLE_SIZE)
+            .ok_or_else(|| Error::ParseError("Xpress Huffman table truncated".to_string()))?;
+        let decode_table = build_decode_table(table)?;
+
+        let mut bits = BitReader::new(input, position + TABLE_SIZE)?;
+        let block_end = (output.len() + BLOCK_SIZE).min(output_size);
+
+        while output.len() < block_end {
+            let (symbol, length) = decode_table[bits.peek(MAX_CODE_LENGTH) as usize];
+            if length == 0 {
+                return Err(Error::ParseError("Invalid Xpress Huffman code".to_string()));
+            }
+            bits.skip(length as u32)?;
+
+            if symbol < 256 {
+                output.push(symbol as u8);
+                continue;
+            }
+
+            let symbol = symbol - 256;
+            let mut match_length = (symbol & 0x0F) as usize;
+            let offset_bits = (symbol >> 4) as u32;
+
+            if match_length == 15 {
+                match_length = bits.read_byte()? as usize;
+                if match_length == 255 {
+                    match_length = bits.read_u16()? as usize;
+                    if match_length == 0 {
+                        match_length = bits.read_u32()? as usize;
+                    }
+                    if match_length < 15 {
+                        return Err(Error::ParseError("Invalid Xpress match length".to_string()));
+                    }
+                    match_length -= 15;
+                }
+                match_length += 15;
+            }
+            match_length += 3;
+
+            let match_offset = (bits.peek(offset_bits) as usize) + (1 << offset_bits);
+            bits.skip(offset_bits)?;
+
+            if match_offset > output.len() {
+                return Err(Error::ParseError("Xpress match offset before start of output".to_string()));
+            }
+            // Byte-by-byte copy: matches may overlap the bytes they produce
+            let start = output.len() - match_offset;
+            for i in 0..match_length.min(output_size - output.len()) {
+                output.push(output[start + i]);
+            }
+        }
+
+        position = bits.position;
+    }
+
+    Ok(output)
+}
+
+/// Build a 2^15-entry lookup table of (symbol, code length) from canonical code lengths
+fn build_decode_table(table: &[u8]) -> Result<Vec<(u16, u8)>> {
+    let lengths: Vec<u8> = (0..SYMBOL_COUNT)
+        .map(|i| if i % 2 == 0 { table[i / 2] & 0x0F } else { table[i / 2] >> 4 })
+        .collect();
+
+    let mut decode_table = vec![(0u16, 0u8); 1 << MAX_CODE_LENGTH];
+    let mut code: usize = 0;
+
+    for length in 1..=MAX_CODE_LENGTH as u8 {
+        for (symbol, _) in lengths.iter().enumerate().filter(|(_, &l)| l == length) {
+            let shift = MAX_CODE_LENGTH as u8 - length;
+            let start = code << shift;
+            let end = (code + 1) << shift;
+            if end > decode_table.len() {
+                return Err(Error::ParseError("Oversubscribed Xpress Huffman table".to_string()));
+            }
+            decode_table[start..end].fill((symbol as u16, length));
+            code += 1;
+        }
+        code <<= 1;
+    }
+
+    Ok(decode_table)
+}
+
+/// MS-XCA bit reader: 16-bit little-endian words consumed most significant bit first
+struct BitReader<'a> {
+    input: &'a [u8],
+    position: usize,
+    next_bits: u32,
+    extra_bits: i32,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(input: &'a [u8], position: usize) -> Result<Self> {
+        let mut reader = Self {
+            input,
+            position,
+            next_bits: 0,
+            extra_bits: 16,
+        };
+        let high = reader.word()? as u32;
+        let low = reader.word()? as u32;
+        reader.next_bits = (high << 16) | low;
+        Ok(reader)
+    }
+
+    /// Top `count` bits without consuming them
+    fn peek(&self, count: u32) -> u32 {
+        if count == 0 {
+            0
+        } else {
+            self.next_bits >> (32 - count)
+        }
+    }
+
+    /// Consume `count` bits, refilling from the next word when needed
+    fn skip(&mut self, count: u32) -> Result<()> {
+        if count == 0 {
+            return Ok(());
+        }
+        self.next_bits <<= count;
+        self.extra_bits -= count as i32;
+        if self.extra_bits < 0 {
+            // Input may end mid-block; missing words read as zero
+            let word = self.word().unwrap_or(0) as u32;
+            self.next_bits |= word << (-self.extra_bits) as u32;
+            self.extra_bits += 16;
+        }
+        Ok(())
+    }
+
+    fn word(&mut self) -> Result<u16> {
+        let bytes = self
+            .input
+            .get(self.position..self.position + 2)
+            .ok_or_else(|| Error::ParseError("Xpress Huffman stream truncated".to_string()))?;
+        self.position += 2;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn read_byte(&mut self) -> Result<u8> {
+        let byte = *self
+            .input
+            .get(self.position)
+            .ok_or_else(|| Error::ParseError("Xpress Huffman stream truncated".to_string()))?;
+        self.position += 1;
+        Ok(byte)
+    }
+
+    fn read_u16(&mut self) -> Result<u16> {
+        self.word()
+    }
+
+    fn read_u32(&mut self) -> Result<u32> {
+        let low = self.word()? as u32;
+        let high = self.word()? as u32;
+        Ok((high << 16) | low)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Table where every literal and symbol 256 (match length 3, offset 1) uses a 9-bit code
+    fn uniform_table() -> Vec<u8> {
+        vec![0x99; TABLE_SIZE]
+    }
+
+    /// Pack 9-bit codes MSB-first into 16-bit little-endian words
+    fn pack_codes(codes: &[u16]) -> Vec<u8> {
+        let mut bits: Vec<bool> = Vec::new();
+        for &code in codes {
+            for i in (0..9).rev() {
+                bits.push(code >> i & 1 == 1);
+            }
+        }
+        bits.resize(bits.len().div_ceil(16) * 16 + 32, false);
+        bits.chunks(16)
+            .flat_map(|chunk| {
+                let word = chunk.iter().fold(0u16, |acc, &b| (acc << 1) | b as u16);
+                word.to_le_bytes()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_literals_and_overlapping_match() {
+        // Canonical codes equal the symbol value when all 512 lengths are 9
+        let mut input = uniform_table();
+        input.extend(pack_codes(&[b'a' as u16, b'b' as u16, 256]));
+
+        let output = decompress_huffman(&input, 5).unwrap();
+        assert_eq!(output, b"abbbb");
+    }
+
+    #[test]
+    fn test_truncated_input() {
+        assert!(decompress_huffman(&[0u8; 10], 4).is_err());
+    }
+}